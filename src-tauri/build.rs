@@ -1,3 +1,5 @@
 fn main() {
-    tauri_build::build()
+    // Headless builds have no Tauri app to prepare
+    #[cfg(feature = "desktop")]
+    tauri_build::build();
 }
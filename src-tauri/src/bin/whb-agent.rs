@@ -0,0 +1,62 @@
+// src-tauri/src/bin/whb-agent.rs
+// Headless check agent. Run it on a server in another network and add it to the app,
+// which then asks it to confirm failures before raising incidents.
+//
+//   whb-agent --token <secret> [--listen 0.0.0.0:7171] [--name eu-west] [--region Frankfurt]
+//
+// The token can also come from WHB_AGENT_TOKEN.
+use webheartbeat_lib::models::agent::{AGENT_PROTOCOL_VERSION, AgentInfo};
+use webheartbeat_lib::services::agent_service::serve_agent;
+
+const DEFAULT_LISTEN: &str = "0.0.0.0:7171";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), String> {
+    let mut listen = DEFAULT_LISTEN.to_string();
+    let mut token = std::env::var("WHB_AGENT_TOKEN").ok();
+    let mut name = None;
+    let mut region = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--listen" => listen = value()?,
+            "--token" => token = Some(value()?),
+            "--name" => name = Some(value()?),
+            "--region" => region = Some(value()?),
+            "--help" | "-h" => {
+                println!(
+                    "Usage: whb-agent --token <secret> [--listen {}] [--name <name>] [--region <region>]",
+                    DEFAULT_LISTEN
+                );
+                return Ok(());
+            }
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    let token = token
+        .filter(|token| !token.trim().is_empty())
+        .ok_or("A token is required: pass --token or set WHB_AGENT_TOKEN")?;
+    let info = AgentInfo {
+        name: name
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .unwrap_or_else(|| "agent".to_string()),
+        region,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol: AGENT_PROTOCOL_VERSION,
+    };
+
+    serve_agent(&listen, info, token).await
+}
@@ -0,0 +1,660 @@
+// src-tauri/src/bin/whb.rs
+// Command-line access to the monitor for servers and cron jobs. Works on the same data
+// files as the desktop app and prints every result as JSON on stdout; errors are
+// printed as {"error": "..."} on stderr with exit code 1.
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tokio::task::JoinSet;
+use webheartbeat_lib::models::check::CheckResult;
+use webheartbeat_lib::models::report::ReportRequest;
+use webheartbeat_lib::models::screenshot::ScreenshotOptions;
+use webheartbeat_lib::models::status_page::StatusPageOptions;
+use webheartbeat_lib::models::website::Website;
+use webheartbeat_lib::models::wpscan::WpscanOptions;
+use webheartbeat_lib::services::agent_service::{AgentService, confirm_check};
+use webheartbeat_lib::services::alert_rule_service::AlertRuleService;
+use webheartbeat_lib::services::alert_rules::RuleAlert;
+use webheartbeat_lib::services::alert_service::{
+    AlertService, certificate_event, vulnerability_event,
+};
+use webheartbeat_lib::services::backup_service::{
+    list_local_backups, read_backup, write_local_backup,
+};
+use webheartbeat_lib::services::certificate_service::certificate_expiry;
+use webheartbeat_lib::services::check_history_service::CheckHistoryService;
+use webheartbeat_lib::services::check_service::probe_website;
+use webheartbeat_lib::services::import_export_service::{ExportOptions, export_data, import_data};
+use webheartbeat_lib::services::maintenance_service::MaintenanceService;
+use webheartbeat_lib::services::report_service::validate_request;
+use webheartbeat_lib::services::report_service::{ReportService, ReportSources, generate_report};
+use webheartbeat_lib::services::scan_storage_service::ScanStorageService;
+use webheartbeat_lib::services::scan_storage_service::scan_stored_website;
+use webheartbeat_lib::services::screenshot_history_service::ScreenshotHistoryService;
+use webheartbeat_lib::services::screenshot_service::ScreenshotService;
+use webheartbeat_lib::services::search_service::{BulkTarget, SearchFilters, select_websites};
+use webheartbeat_lib::services::settings_service::read_settings;
+use webheartbeat_lib::services::status_page::publish_status_page;
+use webheartbeat_lib::services::status_page::validate_options;
+use webheartbeat_lib::services::storage_service::StorageService;
+use webheartbeat_lib::services::wpscan_service::WpscanService;
+
+const USAGE: &str = "\
+Usage: whb [--data-dir <dir>] <command> [arguments]
+
+Commands:
+  websites [ids] [filters]              List websites
+  check [ids] [filters]                 Run status checks, record them and send alerts
+  screenshot <id> [--format png|jpeg|webp] [--full-page] [--output <file>]
+  scan [ids] [filters] [--api-key <key>] [--offline-db <file>] [--aggressive --yes]
+  export [--format json|full-backup] [--output <file>]
+  import <file> [--merge]               Replace (or merge into) the stored websites
+  backup                                Write a backup of all websites to <dir>/backups
+  backups                               List the backups in <dir>/backups
+  restore <backup file>                 Replace the stored websites with a backup
+  status-page [ids] [filters] --output <dir> [--title <title>]
+              [--group-by tag|industry|none] [--days <1-90>]
+  report (--website <id> | --tag <client tag>) [--template <name>]
+         [--format html,pdf] [--days <1-90>] [--output <dir>]
+
+Filters: --query <text> --status online|offline|unknown --industry <industry>
+         --project-status <status> --wordpress true|false --favorite true|false
+
+The data directory defaults to WHB_DATA_DIR, or the parent directory like the app.
+The WPScan API key can also come from WHB_WPSCAN_API_KEY.";
+
+// Options that take no value
+const SWITCHES: &[&str] = &["merge", "full-page", "aggressive", "yes", "help"];
+// Options that take one
+const OPTIONS: &[&str] = &[
+    "data-dir",
+    "format",
+    "output",
+    "api-key",
+    "offline-db",
+    "title",
+    "group-by",
+    "days",
+    "website",
+    "tag",
+    "template",
+    "query",
+    "status",
+    "industry",
+    "project-status",
+    "wordpress",
+    "favorite",
+];
+// Websites checked in parallel, like bulk jobs in the app
+const CHECK_CONCURRENCY: usize = 8;
+
+#[tokio::main]
+async fn main() {
+    let result = match parse_args(std::env::args().skip(1)) {
+        Ok(args) if args.command == "help" => {
+            println!("{}", USAGE);
+            return;
+        }
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(value) => {
+            let json = serde_json::to_string_pretty(&value).unwrap_or_default();
+            println!("{}", json);
+        }
+        Err(e) => {
+            eprintln!("{}", json!({ "error": e }));
+            std::process::exit(1);
+        }
+    }
+}
+
+struct Args {
+    command: String,
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    switches: HashSet<String>,
+}
+
+impl Args {
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn bool_option(&self, name: &str) -> Result<Option<bool>, String> {
+        self.option(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("--{} takes true or false", name))
+            })
+            .transpose()
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut words = Vec::new();
+    let mut options = HashMap::new();
+    let mut switches = HashSet::new();
+
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(name) if SWITCHES.contains(&name) => {
+                switches.insert(name.to_string());
+            }
+            Some(name) if OPTIONS.contains(&name) => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for --{}", name))?;
+                options.insert(name.to_string(), value);
+            }
+            Some(name) => return Err(format!("Unknown option: --{}, see whb --help", name)),
+            None => words.push(arg),
+        }
+    }
+
+    if switches.contains("help") || words.is_empty() {
+        return Ok(Args {
+            command: "help".to_string(),
+            positional: Vec::new(),
+            options,
+            switches,
+        });
+    }
+    let command = words.remove(0);
+    Ok(Args {
+        command,
+        positional: words,
+        options,
+        switches,
+    })
+}
+
+/// Paths of the data files, laid out like the desktop app's
+struct DataDir(PathBuf);
+
+impl DataDir {
+    fn path(&self, name: &str) -> String {
+        self.0.join(name).to_string_lossy().to_string()
+    }
+}
+
+async fn run(args: Args) -> Result<Value, String> {
+    let data_dir = DataDir(PathBuf::from(
+        args.option("data-dir")
+            .map(str::to_string)
+            .or_else(|| std::env::var("WHB_DATA_DIR").ok())
+            .unwrap_or_else(|| "..".to_string()),
+    ));
+    let storage = StorageService::new(data_dir.path("websites.json"));
+
+    match args.command.as_str() {
+        "websites" => to_json(&target_websites(&storage, &args)?),
+        "check" => {
+            let websites = target_websites(&storage, &args)?;
+            check_websites(&data_dir, &storage, websites).await
+        }
+        "screenshot" => screenshot(&data_dir, &storage, &args).await,
+        "scan" => {
+            let websites = target_websites(&storage, &args)?;
+            scan_websites(&data_dir, &storage, websites, &args).await
+        }
+        "export" => {
+            let options = ExportOptions {
+                format: args.option("format").unwrap_or("json").to_string(),
+                include_notes: true,
+                include_custom_statuses: true,
+            };
+            let data = export_data(&storage, &options)?;
+            match args.option("output") {
+                Some(output) => {
+                    std::fs::write(output, &data)
+                        .map_err(|e| format!("Failed to write export: {}", e))?;
+                    Ok(json!({ "output": output }))
+                }
+                None => serde_json::from_str(&data)
+                    .map_err(|e| format!("Failed to parse export: {}", e)),
+            }
+        }
+        "import" => {
+            let file = single_positional(&args, "an import file")?;
+            let data = std::fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+            let result = import_data(&data, &storage, args.switches.contains("merge")).await?;
+            Ok(json!({
+                "imported_count": result.imported_count,
+                "skipped_count": result.skipped_count,
+                "total_websites": result.websites.len(),
+            }))
+        }
+        "backup" => {
+            let websites = storage.get_websites().map_err(|e| e.to_string())?;
+            to_json(&write_local_backup(&data_dir.path("backups"), &websites)?)
+        }
+        "backups" => to_json(&list_local_backups(&data_dir.path("backups"))),
+        "restore" => {
+            let file = single_positional(&args, "a backup file")?;
+            let websites = read_backup(file)?;
+            storage
+                .save_websites(&websites)
+                .map_err(|e| format!("Failed to save websites: {}", e))?;
+            Ok(json!({ "restored_count": websites.len() }))
+        }
+        "status-page" => {
+            let websites = target_websites(&storage, &args)?;
+            let defaults = StatusPageOptions::default();
+            let options = StatusPageOptions {
+                title: args.option("title").unwrap_or(&defaults.title).to_string(),
+                output_dir: args.option("output").unwrap_or_default().to_string(),
+                group_by: args
+                    .option("group-by")
+                    .unwrap_or(&defaults.group_by)
+                    .to_string(),
+                days: match args.option("days") {
+                    Some(days) => days
+                        .parse()
+                        .map_err(|_| format!("Not a number of days: {}", days))?,
+                    None => defaults.days,
+                },
+            };
+            validate_options(&options)?;
+
+            let history = CheckHistoryService::new(data_dir.path("checks"));
+            let (page, files) =
+                publish_status_page(&websites, &history, &options, chrono::Utc::now())?;
+            Ok(json!({
+                "files": files,
+                "overall_status": page.overall_status,
+                "websites": websites.len(),
+            }))
+        }
+        "report" => report(&data_dir, &storage, &args).await,
+        other => Err(format!("Unknown command: {}, see whb --help", other)),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("Failed to serialize result: {}", e))
+}
+
+fn single_positional<'a>(args: &'a Args, what: &str) -> Result<&'a str, String> {
+    match args.positional.as_slice() {
+        [value] => Ok(value),
+        _ => Err(format!("{} expects {}", args.command, what)),
+    }
+}
+
+/// Websites named by id on the command line and narrowed by the search filters;
+/// all websites when neither is given
+fn target_websites(storage: &StorageService, args: &Args) -> Result<Vec<Website>, String> {
+    let ids = args
+        .positional
+        .iter()
+        .map(|id| id.parse().map_err(|_| format!("Not a website id: {}", id)))
+        .collect::<Result<Vec<i64>, String>>()?;
+
+    let filter_names = [
+        "query",
+        "status",
+        "industry",
+        "project-status",
+        "wordpress",
+        "favorite",
+    ];
+    let filters = if filter_names
+        .iter()
+        .any(|name| args.options.contains_key(*name))
+    {
+        Some(SearchFilters {
+            query: args.option("query").unwrap_or_default().to_string(),
+            status: args.option("status").unwrap_or("all").to_string(),
+            project_status: args.option("project-status").unwrap_or("all").to_string(),
+            industry: args.option("industry").unwrap_or("all").to_string(),
+            favorite: args.bool_option("favorite")?,
+            is_wordpress: args.bool_option("wordpress")?,
+            limit: None,
+        })
+    } else {
+        None
+    };
+
+    let websites = storage.get_websites().map_err(|e| e.to_string())?;
+    Ok(select_websites(
+        websites,
+        &BulkTarget {
+            ids: (!ids.is_empty()).then_some(ids),
+            filters,
+        },
+    ))
+}
+
+#[derive(Serialize)]
+struct StatusLine {
+    website_id: i64,
+    name: String,
+    url: String,
+    status: u16,
+    response_time_ms: Option<u64>,
+    vantage: Option<String>, // Remote agent whose check was recorded
+    maintenance: bool,
+    unconfirmed: bool, // A failure no remote agent answered about
+}
+
+/// The desktop app's status check: remote agents confirm failures, then the check goes
+/// into the history and through the alert rules, and the certificate expiry is stored.
+/// A website that fails is reported with its error, and the alerts gathered for the
+/// others are still sent.
+async fn check_websites(
+    data_dir: &DataDir,
+    storage: &StorageService,
+    websites: Vec<Website>,
+) -> Result<Value, String> {
+    let recorder = StatusRecorder {
+        history: CheckHistoryService::new(data_dir.path("checks")),
+        maintenance: MaintenanceService::new(data_dir.path("maintenance.json")),
+        rules: AlertRuleService::new(
+            data_dir.path("alert_rules.json"),
+            data_dir.path("alert_state.json"),
+        ),
+        storage,
+    };
+    let agents = AgentService::new(data_dir.path("agents.json")).enabled_agents();
+    let client = reqwest::Client::new();
+
+    let mut pending = websites.into_iter();
+    let mut running = JoinSet::new();
+    let mut lines = Vec::new();
+    let mut alerts = Vec::new();
+
+    loop {
+        while running.len() < CHECK_CONCURRENCY {
+            let Some(website) = pending.next() else {
+                break;
+            };
+            let client = client.clone();
+            let agents = agents.clone();
+            running.spawn(async move {
+                let check = probe_website(&website.url).await.map(|check| CheckResult {
+                    website_id: website.id,
+                    ..check
+                });
+                let confirmed = match check {
+                    Ok(check) => Ok(confirm_check(&client, &agents, &website.url, check).await),
+                    Err(e) => Err(e),
+                };
+                let certificate = certificate_expiry(&website.url).await.ok().flatten();
+                (website, confirmed, certificate)
+            });
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let (website, confirmed, certificate) = match joined {
+            Ok(checked) => checked,
+            Err(e) => {
+                lines.push((
+                    None,
+                    json!({ "error": format!("Check task failed: {}", e) }),
+                ));
+                continue;
+            }
+        };
+
+        let website_id = website.id;
+        let line = confirmed.and_then(|confirmed| {
+            recorder.record(website, confirmed.check, certificate, &mut alerts)
+        });
+        match line.and_then(|line| to_json(&line)) {
+            Ok(line) => lines.push((Some(website_id), line)),
+            Err(e) => lines.push((
+                Some(website_id),
+                json!({ "website_id": website_id, "error": e }),
+            )),
+        }
+    }
+
+    deliver_alerts(data_dir, alerts).await;
+    lines.sort_by_key(|(website_id, _)| *website_id);
+    Ok(Value::Array(
+        lines.into_iter().map(|(_, line)| line).collect(),
+    ))
+}
+
+/// Where the status checks of a run are recorded
+struct StatusRecorder<'a> {
+    history: CheckHistoryService,
+    maintenance: MaintenanceService,
+    rules: AlertRuleService,
+    storage: &'a StorageService,
+}
+
+impl StatusRecorder<'_> {
+    /// Record a check and store the status. Alerts are added as soon as they are raised,
+    /// so a later failure does not lose them.
+    fn record(
+        &self,
+        website: Website,
+        check: CheckResult,
+        certificate: Option<chrono::DateTime<chrono::Utc>>,
+        alerts: &mut Vec<RuleAlert>,
+    ) -> Result<StatusLine, String> {
+        let now = chrono::Utc::now();
+        let in_maintenance = self.maintenance.in_maintenance(&website, now);
+        let shown = CheckResult {
+            maintenance: in_maintenance,
+            ..check
+        };
+        self.history.record(&shown)?;
+        alerts.extend(
+            self.rules
+                .process_check(&website, &shown, now, in_maintenance)?,
+        );
+
+        let expiring =
+            certificate.and_then(|expires_at| certificate_event(&website, expires_at, now));
+        alerts.extend(self.rules.route(&website, expiring.into_iter().collect()));
+
+        self.storage
+            .modify_website(website.id, |w| {
+                w.status = Some(shown.status);
+                w.last_checked = Some(now.to_rfc3339());
+                if let Some(expires_at) = certificate {
+                    w.certificate_expires_at = Some(expires_at.to_rfc3339());
+                    w.certificate_checked_at = Some(now.to_rfc3339());
+                }
+            })
+            .map_err(|e| e.to_string())?;
+
+        Ok(StatusLine {
+            website_id: website.id,
+            name: website.name,
+            url: website.url,
+            status: shown.status,
+            response_time_ms: shown.response_time_ms,
+            vantage: shown.vantage,
+            maintenance: in_maintenance,
+            unconfirmed: shown.unconfirmed,
+        })
+    }
+}
+
+async fn screenshot(
+    data_dir: &DataDir,
+    storage: &StorageService,
+    args: &Args,
+) -> Result<Value, String> {
+    let id: i64 = single_positional(args, "a website id")?
+        .parse()
+        .map_err(|_| "screenshot expects a website id".to_string())?;
+    let website = storage
+        .get_websites()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Website with id {} not found", id))?;
+
+    let options = ScreenshotOptions {
+        format: args.option("format").unwrap_or("png").to_string(),
+        full_page: args.switches.contains("full-page"),
+        ..Default::default()
+    };
+    ScreenshotService::validate(&options)?;
+
+    let browser = headless_chrome::Browser::default()
+        .map_err(|e| format!("Failed to launch browser: {}", e))?;
+    let url = website.url.clone();
+    let history = ScreenshotHistoryService::new(data_dir.path("screenshots"));
+    let (data, capture) = tokio::task::spawn_blocking(move || {
+        let data = ScreenshotService::capture(&browser, &url, &options)?;
+        // Diffing against the previous capture is image work as well
+        let capture = history.record_capture(website.id, &data);
+        Ok::<_, String>((data, capture))
+    })
+    .await
+    .map_err(|e| format!("Screenshot task failed: {}", e))??;
+
+    if let Some(output) = args.option("output") {
+        std::fs::write(output, &data).map_err(|e| format!("Failed to write screenshot: {}", e))?;
+    }
+    to_json(&capture?)
+}
+
+/// A report for one website or client group, written next to the data files unless
+/// --output says otherwise
+async fn report(
+    data_dir: &DataDir,
+    storage: &StorageService,
+    args: &Args,
+) -> Result<Value, String> {
+    let (scope, target) = match (args.option("website"), args.option("tag")) {
+        (Some(id), None) => ("website", id),
+        (None, Some(tag)) => ("tag", tag),
+        _ => return Err("report expects either --website <id> or --tag <tag>".to_string()),
+    };
+    let defaults = ReportRequest::default();
+    let request = ReportRequest {
+        scope: scope.to_string(),
+        target: target.to_string(),
+        template: args
+            .option("template")
+            .unwrap_or(&defaults.template)
+            .to_string(),
+        formats: match args.option("format") {
+            Some(formats) => formats.split(',').map(|f| f.trim().to_string()).collect(),
+            None => defaults.formats,
+        },
+        period_days: args
+            .option("days")
+            .map(|days| {
+                days.parse()
+                    .map_err(|_| format!("Not a number of days: {}", days))
+            })
+            .transpose()?,
+        output_dir: Some(
+            args.option("output")
+                .map(str::to_string)
+                .unwrap_or_else(|| data_dir.path("reports")),
+        ),
+    };
+    validate_request(&request)?;
+
+    let reports = ReportService::new(
+        data_dir.path("reports.json"),
+        data_dir.path("report_templates"),
+    );
+    let history = CheckHistoryService::new(data_dir.path("checks"));
+    let scans = ScanStorageService::new(data_dir.path("scans"));
+    let screenshots = ScreenshotHistoryService::new(data_dir.path("screenshots"));
+    let sources = ReportSources {
+        storage,
+        history: &history,
+        scans: &scans,
+        screenshots: &screenshots,
+    };
+    to_json(&generate_report(&reports, &sources, &request, chrono::Utc::now()).await?)
+}
+
+async fn scan_websites(
+    data_dir: &DataDir,
+    storage: &StorageService,
+    websites: Vec<Website>,
+    args: &Args,
+) -> Result<Value, String> {
+    let options = WpscanOptions {
+        offline_db_path: args.option("offline-db").map(str::to_string),
+        aggressive: args.switches.contains("aggressive"),
+        ..Default::default()
+    };
+    if options.aggressive && !args.switches.contains("yes") {
+        return Err(
+            "Aggressive plugin enumeration probes about a hundred URLs on each target server; \
+             add --yes to confirm you are allowed to do this"
+                .to_string(),
+        );
+    }
+    let api_key = args
+        .option("api-key")
+        .map(str::to_string)
+        .or_else(|| std::env::var("WHB_WPSCAN_API_KEY").ok());
+    let wpscan = WpscanService::with_options(api_key, &options)?;
+
+    let scans = ScanStorageService::new(data_dir.path("scans"));
+    let rules = AlertRuleService::new(
+        data_dir.path("alert_rules.json"),
+        data_dir.path("alert_state.json"),
+    );
+    let mut results = Vec::new();
+    let mut alerts = Vec::new();
+
+    for website in websites {
+        match scan_stored_website(&wpscan, storage, &scans, &website).await {
+            Ok((result, stored)) => {
+                if let Some((updated, record)) = stored {
+                    let events = vulnerability_event(&updated, &record).into_iter().collect();
+                    alerts.extend(rules.route(&updated, events));
+                }
+                results.push(json!({ "website_id": website.id, "result": result }));
+            }
+            Err(e) => results.push(json!({ "website_id": website.id, "error": e })),
+        }
+    }
+
+    deliver_alerts(data_dir, alerts).await;
+    Ok(Value::Array(results))
+}
+
+/// Send alerts through the configured channels unless notifications are switched off.
+/// Desktop notifications need the app and are reported as failed deliveries.
+async fn deliver_alerts(data_dir: &DataDir, alerts: Vec<RuleAlert>) {
+    if alerts.is_empty() {
+        return;
+    }
+    if matches!(read_settings(), Ok(Some(settings)) if !settings.enable_notifications) {
+        eprintln!(
+            "Notifications are disabled, dropping {} alerts",
+            alerts.len()
+        );
+        return;
+    }
+
+    let service = AlertService::new(data_dir.path("alerts.json"));
+    for alert in &alerts {
+        for delivery in service
+            .dispatch(&alert.event, &alert.channel_ids, None)
+            .await
+            .into_iter()
+            .filter(|delivery| !delivery.success)
+        {
+            eprintln!(
+                "Failed to deliver {} alert to {}: {}",
+                delivery.event_kind,
+                delivery.channel_id,
+                delivery.error.unwrap_or_default()
+            );
+        }
+    }
+}
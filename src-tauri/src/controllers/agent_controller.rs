@@ -0,0 +1,61 @@
+// controllers/agent_controller.rs
+use crate::models::agent::{AgentFailure, AgentInfo, RemoteAgent};
+use crate::models::check::CheckResult;
+use crate::services::agent_service::AgentService;
+use tauri::{AppHandle, Manager, State, command};
+
+#[command]
+pub async fn get_remote_agents(
+    agents: State<'_, AgentService>,
+) -> Result<Vec<RemoteAgent>, String> {
+    Ok(agents.get_agents())
+}
+
+#[command]
+pub async fn save_remote_agents(
+    agents: Vec<RemoteAgent>,
+    agent_service: State<'_, AgentService>,
+) -> Result<(), String> {
+    for (index, agent) in agents.iter().enumerate() {
+        validate_agent(agent)?;
+        if agents[..index].iter().any(|other| other.id == agent.id) {
+            return Err(format!("Duplicate agent id: {}", agent.id));
+        }
+    }
+
+    agent_service.save_agents(agents)
+}
+
+/// Enabled agents that could not be asked about the last failure they were sent
+#[command]
+pub async fn get_agent_failures(
+    agents: State<'_, AgentService>,
+) -> Result<Vec<AgentFailure>, String> {
+    Ok(agents.get_failures())
+}
+
+/// Reach an agent with its token, enabled or not
+#[command]
+pub async fn test_remote_agent(
+    agent: RemoteAgent,
+    agents: State<'_, AgentService>,
+) -> Result<AgentInfo, String> {
+    validate_agent(&agent)?;
+    agents.info(&agent).await
+}
+
+fn validate_agent(agent: &RemoteAgent) -> Result<(), String> {
+    if !agent.url.starts_with("http://") && !agent.url.starts_with("https://") {
+        return Err(format!("Agent {} needs an http(s) URL", agent.name));
+    }
+    if agent.token.trim().is_empty() {
+        return Err(format!("Agent {} needs a token", agent.name));
+    }
+    Ok(())
+}
+
+/// Have the remote agents confirm a failed check; it is marked unconfirmed when none
+/// of them answered
+pub async fn confirmed_check(app: &AppHandle, url: &str, check: CheckResult) -> CheckResult {
+    app.state::<AgentService>().confirm(url, check).await
+}
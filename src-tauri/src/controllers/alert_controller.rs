@@ -0,0 +1,225 @@
+// controllers/alert_controller.rs
+use crate::controllers::settings_controller::load_settings;
+use crate::models::alert::{AlertChannel, AlertDelivery, AlertEvent, AlertRule, AlertState};
+use crate::models::check::CheckResult;
+use crate::models::website::Website;
+use crate::services::alert_rule_service::AlertRuleService;
+use crate::services::alert_rules::RuleAlert;
+use crate::services::alert_service::AlertService;
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::maintenance_service::MaintenanceService;
+use crate::services::metrics_service::MetricsService;
+use crate::services::storage_service::StorageService;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State, command};
+
+const CHANNEL_TYPES: &[&str] = &["desktop", "email", "webhook", "slack", "discord"];
+const RULE_SCOPES: &[&str] = &["all", "website", "tag", "industry"];
+// How often reminders and escalations are looked at between checks
+const TIMER_INTERVAL: Duration = Duration::from_secs(60);
+
+#[command]
+pub async fn get_alert_channels(
+    alerts: State<'_, AlertService>,
+) -> Result<Vec<AlertChannel>, String> {
+    Ok(alerts.get_channels())
+}
+
+#[command]
+pub async fn save_alert_channels(
+    channels: Vec<AlertChannel>,
+    alerts: State<'_, AlertService>,
+) -> Result<(), String> {
+    for (index, channel) in channels.iter().enumerate() {
+        validate_channel(channel)?;
+        if channels[..index].iter().any(|other| other.id == channel.id) {
+            return Err(format!("Duplicate alert channel id: {}", channel.id));
+        }
+    }
+
+    alerts.save_channels(channels)
+}
+
+/// Send a test alert through one channel, enabled or not
+#[command]
+pub async fn test_alert_channel(
+    app: AppHandle,
+    channel: AlertChannel,
+    alerts: State<'_, AlertService>,
+) -> Result<AlertDelivery, String> {
+    validate_channel(&channel)?;
+
+    let event = AlertEvent {
+        kind: "test".to_string(),
+        website_id: 0,
+        website_name: "WebHealth Monitor".to_string(),
+        url: String::new(),
+        title: "Test alert".to_string(),
+        message: format!(
+            "The {} channel \"{}\" works.",
+            channel.channel_type, channel.name
+        ),
+        severity: "info".to_string(),
+        occurred_at: chrono::Utc::now().to_rfc3339(),
+    };
+    Ok(alerts.deliver(&channel, &event, Some(&app)).await)
+}
+
+fn validate_channel(channel: &AlertChannel) -> Result<(), String> {
+    if !CHANNEL_TYPES.contains(&channel.channel_type.as_str()) {
+        return Err(format!("Unknown channel type: {}", channel.channel_type));
+    }
+    match channel.channel_type.as_str() {
+        "webhook" | "slack" | "discord"
+            if !channel
+                .webhook_url
+                .as_deref()
+                .is_some_and(|url| url.starts_with("http://") || url.starts_with("https://")) =>
+        {
+            Err(format!(
+                "Channel {} needs an http(s) webhook URL",
+                channel.name
+            ))
+        }
+        "email" if channel.smtp.as_ref().is_none_or(|smtp| smtp.to.is_empty()) => Err(format!(
+            "Channel {} needs SMTP settings and at least one recipient",
+            channel.name
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[command]
+pub async fn get_alert_rules(rules: State<'_, AlertRuleService>) -> Result<Vec<AlertRule>, String> {
+    Ok(rules.get_rules())
+}
+
+#[command]
+pub async fn save_alert_rules(
+    rules: Vec<AlertRule>,
+    alerts: State<'_, AlertService>,
+    alert_rules: State<'_, AlertRuleService>,
+) -> Result<(), String> {
+    let channels = alerts.get_channels();
+    for (index, rule) in rules.iter().enumerate() {
+        if !RULE_SCOPES.contains(&rule.scope.as_str()) {
+            return Err(format!("Unknown rule scope: {}", rule.scope));
+        }
+        if rule.scope != "all" && rule.target.as_deref().is_none_or(str::is_empty) {
+            return Err(format!(
+                "Rule {} needs a {} to apply to",
+                rule.name, rule.scope
+            ));
+        }
+        if rules[..index].iter().any(|other| other.id == rule.id) {
+            return Err(format!("Duplicate alert rule id: {}", rule.id));
+        }
+        if let Some(unknown) = rule
+            .channel_ids
+            .iter()
+            .chain(&rule.escalation_channel_ids)
+            .find(|id| !channels.iter().any(|channel| &channel.id == *id))
+        {
+            return Err(format!(
+                "Rule {} uses unknown channel {}",
+                rule.name, unknown
+            ));
+        }
+    }
+
+    alert_rules.save_rules(rules)
+}
+
+/// Current incident state of every website that has been checked
+#[command]
+pub async fn get_alert_states(
+    rules: State<'_, AlertRuleService>,
+) -> Result<Vec<AlertState>, String> {
+    Ok(rules.get_states())
+}
+
+/// Store a check in the history and run it through the website's alert rule.
+/// Checks made during maintenance are flagged so they stay out of uptime figures.
+pub fn record_check(app: &AppHandle, website: &Website, check: CheckResult) {
+    let now = chrono::Utc::now();
+    let in_maintenance = app
+        .state::<MaintenanceService>()
+        .in_maintenance(website, now);
+    let check = CheckResult {
+        maintenance: in_maintenance,
+        ..check
+    };
+    if let Err(e) = app.state::<CheckHistoryService>().record(&check) {
+        eprintln!("Failed to record check of {}: {}", website.name, e);
+    }
+    app.state::<MetricsService>().observe(&check);
+
+    match app
+        .state::<AlertRuleService>()
+        .process_check(website, &check, now, in_maintenance)
+    {
+        Ok(alerts) => send_alerts(app, alerts),
+        Err(e) => eprintln!("Failed to evaluate alert rules for {}: {}", website.name, e),
+    }
+}
+
+/// Look for due reminders and escalations every minute, for as long as the app runs
+pub fn start_alert_timers(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TIMER_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let websites = match app.state::<StorageService>().get_websites() {
+                Ok(websites) => websites,
+                Err(e) => {
+                    eprintln!("Alert timers could not load websites: {}", e);
+                    continue;
+                }
+            };
+            let now = chrono::Utc::now();
+            let maintenance = app.state::<MaintenanceService>();
+            match app
+                .state::<AlertRuleService>()
+                .process_timers(&websites, now, |website| {
+                    maintenance.in_maintenance(website, now)
+                }) {
+                Ok(alerts) => send_alerts(&app, alerts),
+                Err(e) => eprintln!("Failed to evaluate alert timers: {}", e),
+            }
+        }
+    });
+}
+
+/// Deliver alerts in the background unless notifications are switched off in the settings.
+/// Each event is also emitted to the UI as an `alert` event.
+pub fn send_alerts(app: &AppHandle, alerts: Vec<RuleAlert>) {
+    if alerts.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let enabled = match load_settings().await {
+            Ok(Some(settings)) => settings.enable_notifications,
+            _ => true,
+        };
+        if !enabled {
+            println!(
+                "Notifications are disabled, dropping {} alerts",
+                alerts.len()
+            );
+            return;
+        }
+
+        let service = app.state::<AlertService>();
+        for alert in &alerts {
+            let event = &alert.event;
+            println!("Alert for {}: {}", event.website_name, event.title);
+            let _ = app.emit("alert", event);
+            service
+                .dispatch(event, &alert.channel_ids, Some(&app))
+                .await;
+        }
+    });
+}
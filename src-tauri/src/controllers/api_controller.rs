@@ -0,0 +1,475 @@
+// controllers/api_controller.rs
+use crate::controllers::website_controller::check_stored_website;
+use crate::models::api::ApiSettings;
+use crate::models::check::CheckResult;
+use crate::models::maintenance::UptimeSummary;
+use crate::models::website::Website;
+use crate::models::wpscan::ScanRecord;
+use crate::services::bearer_token::authorized;
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::maintenance_service::uptime_summary;
+use crate::services::metrics_service::MetricsService;
+use crate::services::scan_storage_service::ScanStorageService;
+use crate::services::search_service::{
+    SearchFilters, SearchResult, SearchStats, perform_search, search_stats,
+};
+use crate::services::storage_service::StorageService;
+use axum::extract::{self, Path, Query, Request};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, State, command};
+use tokio::sync::oneshot;
+
+const TOKEN_LENGTH: usize = 32;
+
+/// Settings of the local API and the running server, if any
+#[derive(Debug)]
+pub struct ApiServer {
+    file_path: String,
+    settings: Mutex<ApiSettings>,
+    running: Mutex<Option<RunningServer>>,
+}
+
+#[derive(Debug)]
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+    stopped: oneshot::Receiver<()>,
+}
+
+impl ApiServer {
+    pub fn new(file_path: String) -> Self {
+        let settings = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            file_path,
+            settings: Mutex::new(settings),
+            running: Mutex::new(None),
+        }
+    }
+
+    pub fn get_settings(&self) -> ApiSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    fn save_settings(&self, settings: &ApiSettings) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize API settings: {}", e))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| format!("Failed to save API settings: {}", e))?;
+
+        *self.settings.lock().unwrap() = settings.clone();
+        Ok(())
+    }
+
+    fn listens_on(&self, port: u16) -> bool {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|running| running.port == port)
+    }
+
+    /// Stop the server and wait until its port is free again
+    async fn stop(&self) {
+        let running = self.running.lock().unwrap().take();
+        if let Some(running) = running {
+            let _ = running.shutdown.send(());
+            let _ = running.stopped.await;
+        }
+    }
+}
+
+#[command]
+pub async fn get_api_settings(api: State<'_, ApiServer>) -> Result<ApiSettings, String> {
+    Ok(api.get_settings())
+}
+
+/// Save the API settings and start, restart or stop the server to match them.
+/// Returns the saved settings, with a generated token if none was given. Nothing
+/// changes unless the new address can be listened on and the settings are saved.
+#[command]
+pub async fn save_api_settings(
+    app: AppHandle,
+    settings: ApiSettings,
+    api: State<'_, ApiServer>,
+) -> Result<ApiSettings, String> {
+    if settings.port < 1024 {
+        return Err("The API port must be 1024 or higher".to_string());
+    }
+    if settings.host.parse::<IpAddr>().is_err() {
+        return Err(format!("Invalid API host address: {}", settings.host));
+    }
+
+    let mut settings = settings;
+    if settings.token.trim().is_empty() {
+        settings.token = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+    }
+
+    let mut listener = bind(&settings).await;
+    // The running server keeps its port until it stops
+    let stopped = listener.is_err() && api.listens_on(settings.port);
+    if stopped {
+        api.stop().await;
+        listener = bind(&settings).await;
+    }
+
+    match listener.and_then(|listener| api.save_settings(&settings).map(|()| listener)) {
+        Ok(listener) => {
+            api.stop().await;
+            if let Some(listener) = listener {
+                serve(&app, listener, &settings);
+            }
+            Ok(settings)
+        }
+        Err(e) if stopped => {
+            if let Err(restart) = start_api_server(app).await {
+                eprintln!("Local API could not restart: {}", restart);
+            }
+            Err(e)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Start the server when the API or the metrics endpoint is enabled in the settings
+pub async fn start_api_server(app: AppHandle) -> Result<(), String> {
+    let settings = app.state::<ApiServer>().get_settings();
+    if let Some(listener) = bind(&settings).await? {
+        serve(&app, listener, &settings);
+    }
+    Ok(())
+}
+
+/// Listen on the address in the settings; None when neither the API nor the metrics
+/// endpoint is enabled. A server without a token is refused, as it would let anyone in.
+async fn bind(settings: &ApiSettings) -> Result<Option<tokio::net::TcpListener>, String> {
+    if !settings.enabled && !settings.metrics_enabled {
+        return Ok(None);
+    }
+    if settings.token.trim().is_empty() {
+        return Err(
+            "The local API needs a token, save the API settings to generate one".to_string(),
+        );
+    }
+
+    let address = format!("{}:{}", settings.host, settings.port);
+    tokio::net::TcpListener::bind(&address)
+        .await
+        .map(Some)
+        .map_err(|e| format!("Failed to listen on {}: {}", address, e))
+}
+
+fn serve(app: &AppHandle, listener: tokio::net::TcpListener, settings: &ApiSettings) {
+    let api = app.state::<ApiServer>();
+    let (shutdown, shutdown_requested) = oneshot::channel();
+    let (stopped_sender, stopped) = oneshot::channel();
+    *api.running.lock().unwrap() = Some(RunningServer {
+        port: settings.port,
+        shutdown,
+        stopped,
+    });
+
+    let address = format!("{}:{}", settings.host, settings.port);
+    let router = api_router(app.clone(), settings);
+    tauri::async_runtime::spawn(async move {
+        println!("Local API listening on http://{}", address);
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_requested.await;
+        });
+        match server.await {
+            Ok(()) => println!("Local API on {} stopped", address),
+            Err(e) => eprintln!("Local API on {} failed: {}", address, e),
+        }
+        let _ = stopped_sender.send(());
+    });
+}
+
+struct ApiContext {
+    app: AppHandle,
+}
+
+type ApiContextState = extract::State<Arc<ApiContext>>;
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+/// Every route needs `Authorization: Bearer <token>`:
+///
+/// - `GET /api/v1/websites` searches with the dashboard filters as query parameters
+/// - `GET /api/v1/websites/{id}`
+/// - `POST /api/v1/websites/{id}/check` runs a status check like the dashboard
+/// - `GET /api/v1/websites/{id}/history?days=1` lists recorded checks
+/// - `GET /api/v1/websites/{id}/uptime?days=30`
+/// - `GET /api/v1/websites/{id}/scans` and `GET /api/v1/websites/{id}/scans/latest`
+/// - `GET /api/v1/stats`
+/// - `GET /metrics` in the Prometheus text format, when metrics are enabled
+fn api_router(app: AppHandle, settings: &ApiSettings) -> Router {
+    let context = Arc::new(ApiContext { app });
+    let mut router = Router::new();
+    if settings.enabled {
+        router = router
+            .route("/api/v1/websites", get(list_websites))
+            .route("/api/v1/websites/:id", get(get_website))
+            .route("/api/v1/websites/:id/check", post(check_website))
+            .route("/api/v1/websites/:id/history", get(get_history))
+            .route("/api/v1/websites/:id/uptime", get(get_uptime))
+            .route("/api/v1/websites/:id/scans", get(get_scans))
+            .route("/api/v1/websites/:id/scans/latest", get(get_latest_scan))
+            .route("/api/v1/stats", get(get_stats));
+    }
+    if settings.metrics_enabled {
+        router = router.route("/metrics", get(get_metrics));
+    }
+    require_token(router.with_state(context), &settings.token)
+}
+
+/// Answer 401 to requests without the token, before any route runs
+fn require_token(router: Router, token: &str) -> Router {
+    router.layer(middleware::from_fn_with_state(
+        Arc::<str>::from(token),
+        authorize,
+    ))
+}
+
+async fn authorize(
+    extract::State(token): extract::State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !authorized(request.headers(), &token) {
+        return Err(api_error(StatusCode::UNAUTHORIZED, "Invalid API token"));
+    }
+    Ok(next.run(request).await)
+}
+
+fn api_error(status: StatusCode, message: impl Into<String>) -> ApiError {
+    (status, Json(json!({ "error": message.into() })))
+}
+
+fn internal(e: impl ToString) -> ApiError {
+    api_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+/// Query parameters of `GET /api/v1/websites`; missing filters match everything
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct WebsiteQuery {
+    query: Option<String>,
+    status: Option<String>,
+    project_status: Option<String>,
+    industry: Option<String>,
+    favorite: Option<bool>,
+    wordpress: Option<bool>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct DaysQuery {
+    days: Option<u32>,
+}
+
+fn websites(context: &ApiContext) -> Result<Vec<Website>, ApiError> {
+    context
+        .app
+        .state::<StorageService>()
+        .get_websites()
+        .map_err(internal)
+}
+
+fn website(context: &ApiContext, id: i64) -> Result<Website, ApiError> {
+    websites(context)?
+        .into_iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::NOT_FOUND,
+                format!("Website with id {} not found", id),
+            )
+        })
+}
+
+async fn list_websites(
+    extract::State(context): ApiContextState,
+    Query(query): Query<WebsiteQuery>,
+) -> Result<Json<SearchResult>, ApiError> {
+    let filters = SearchFilters {
+        query: query.query.unwrap_or_default(),
+        status: query.status.unwrap_or_else(|| "all".to_string()),
+        project_status: query.project_status.unwrap_or_else(|| "all".to_string()),
+        industry: query.industry.unwrap_or_else(|| "all".to_string()),
+        favorite: query.favorite,
+        is_wordpress: query.wordpress,
+        limit: query.limit,
+    };
+    Ok(Json(perform_search(websites(&context)?, filters)))
+}
+
+async fn get_website(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+) -> Result<Json<Website>, ApiError> {
+    Ok(Json(website(&context, id)?))
+}
+
+async fn check_website(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+) -> Result<Json<CheckResult>, ApiError> {
+    let website = website(&context, id)?;
+    let check = check_stored_website(&context.app, &website)
+        .await
+        .map_err(internal)?;
+
+    // Nobody else stores the status of API checks, unlike dashboard checks
+    context
+        .app
+        .state::<StorageService>()
+        .modify_website(id, |w| {
+            w.status = Some(check.status);
+            w.last_checked = Some(chrono::Utc::now().to_rfc3339());
+        })
+        .map_err(internal)?;
+    Ok(Json(check))
+}
+
+async fn get_history(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+    Query(query): Query<DaysQuery>,
+) -> Result<Json<Vec<CheckResult>>, ApiError> {
+    let since = chrono::Utc::now() - chrono::Duration::days(query.days.unwrap_or(1) as i64);
+    let history = context.app.state::<CheckHistoryService>();
+    Ok(Json(
+        history.get_history(id, Some(since)).map_err(internal)?,
+    ))
+}
+
+async fn get_uptime(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+    Query(query): Query<DaysQuery>,
+) -> Result<Json<UptimeSummary>, ApiError> {
+    let days = query.days.unwrap_or(30);
+    let since = chrono::Utc::now() - chrono::Duration::days(days as i64);
+    let history = context.app.state::<CheckHistoryService>();
+    let checks = history.get_history(id, Some(since)).map_err(internal)?;
+    Ok(Json(uptime_summary(id, days, &checks)))
+}
+
+async fn get_scans(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<ScanRecord>>, ApiError> {
+    let scans = context.app.state::<ScanStorageService>();
+    Ok(Json(scans.get_history(id).map_err(internal)?))
+}
+
+async fn get_latest_scan(
+    extract::State(context): ApiContextState,
+    Path(id): Path<i64>,
+) -> Result<Json<ScanRecord>, ApiError> {
+    let scans = context.app.state::<ScanStorageService>();
+    scans
+        .get_latest(id)
+        .map_err(internal)?
+        .map(Json)
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::NOT_FOUND,
+                format!("No scan of website {} yet", id),
+            )
+        })
+}
+
+async fn get_stats(
+    extract::State(context): ApiContextState,
+) -> Result<Json<SearchStats>, ApiError> {
+    Ok(Json(search_stats(&websites(&context)?)))
+}
+
+async fn get_metrics(extract::State(context): ApiContextState) -> Result<Response, ApiError> {
+    let websites = websites(&context)?;
+    let scans = context.app.state::<ScanStorageService>();
+    let mut latest_scans = HashMap::new();
+    for website in &websites {
+        if let Some(record) = scans.get_latest(website.id).map_err(internal)? {
+            latest_scans.insert(website.id, record);
+        }
+    }
+
+    let metrics = context.app.state::<MetricsService>();
+    let body = metrics.render(&websites, &latest_scans, chrono::Utc::now());
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn routes_need_the_api_token() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v1/stats", listener.local_addr().unwrap());
+        let router = require_token(
+            Router::new().route("/api/v1/stats", get(|| async { "{}" })),
+            "secret",
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let status = |request: reqwest::RequestBuilder| async move {
+            request.send().await.unwrap().status().as_u16()
+        };
+        assert_eq!(status(client.get(&url)).await, 401);
+        assert_eq!(status(client.get(&url).bearer_auth("")).await, 401);
+        assert_eq!(status(client.get(&url).bearer_auth("wrong")).await, 401);
+        assert_eq!(status(client.get(&url).bearer_auth("secret")).await, 200);
+    }
+
+    #[tokio::test]
+    async fn servers_without_a_token_are_refused() {
+        let settings = ApiSettings {
+            enabled: true,
+            port: 0,
+            ..Default::default()
+        };
+        let refused = bind(&settings).await.unwrap_err();
+        assert!(refused.contains("token"), "{}", refused);
+
+        let disabled = ApiSettings {
+            enabled: false,
+            ..settings.clone()
+        };
+        assert!(bind(&disabled).await.unwrap().is_none());
+
+        let listening = ApiSettings {
+            token: "secret".to_string(),
+            ..settings
+        };
+        assert!(bind(&listening).await.unwrap().is_some());
+    }
+}
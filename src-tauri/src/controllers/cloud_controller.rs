@@ -1,4 +1,6 @@
+use crate::models::cloud::{BackupInfo, CloudBackupResult};
 use crate::models::website::Website;
+use crate::services::backup_service::{list_local_backups, read_backup, write_local_backup};
 use once_cell::sync::Lazy;
 use rand::Rng;
 use reqwest;
@@ -7,15 +9,6 @@ use std::fs;
 use std::sync::Mutex;
 use tauri::command;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CloudBackupResult {
-    pub success: bool,
-    pub message: String,
-    pub backup_path: Option<String>,
-    pub drive_url: Option<String>,
-    pub timestamp: String,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GoogleAuthResult {
     pub success: bool,
@@ -242,27 +235,7 @@ pub async fn disconnect_google_drive() -> Result<(), String> {
 // Local backup functions (fallback)
 #[command]
 pub async fn backup_local(websites: Vec<Website>) -> Result<CloudBackupResult, String> {
-    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
-    let filename = format!("website_backup_{}.json", timestamp);
-
-    let backup_dir = "backups";
-    fs::create_dir_all(backup_dir)
-        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
-
-    let backup_path = format!("{}/{}", backup_dir, filename);
-
-    let backup_data = serde_json::to_string_pretty(&websites)
-        .map_err(|e| format!("Failed to serialize backup: {}", e))?;
-
-    fs::write(&backup_path, backup_data).map_err(|e| format!("Failed to write backup: {}", e))?;
-
-    Ok(CloudBackupResult {
-        success: true,
-        message: format!("Backup saved locally at: {}", backup_path),
-        backup_path: Some(backup_path),
-        drive_url: None,
-        timestamp: chrono::Utc::now().to_rfc3339(),
-    })
+    write_local_backup("backups", &websites)
 }
 
 #[command]
@@ -296,64 +269,16 @@ pub async fn open_backup_folder() -> Result<(), String> {
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BackupInfo {
-    pub filename: String,
-    pub path: String,
-    pub timestamp: String,
-    pub size: u64,
-    pub is_local: bool,
-}
-
 /// List all available backups (both local and cloud)
 #[command]
 pub async fn list_cloud_backups() -> Result<Vec<BackupInfo>, String> {
-    let mut backups = Vec::new();
-    
-    // List local backups
-    let backup_dir = "backups";
-    if let Ok(entries) = fs::read_dir(backup_dir) {
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    let filename = entry.file_name().to_string_lossy().to_string();
-                    if filename.ends_with(".json") {
-                        let path = entry.path().to_string_lossy().to_string();
-                        
-                        // Extract timestamp from filename
-                        let timestamp = filename
-                            .replace("website_backup_", "")
-                            .replace(".json", "");
-                        
-                        backups.push(BackupInfo {
-                            filename,
-                            path: path.clone(),
-                            timestamp,
-                            size: metadata.len(),
-                            is_local: true,
-                        });
-                    }
-                }
-            }
-        }
-    }
-    
-    // Sort by timestamp (newest first)
-    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-    
-    Ok(backups)
+    Ok(list_local_backups("backups"))
 }
 
 /// Restore from a backup file
 #[command]
 pub async fn restore_from_backup(backup_path: String) -> Result<Vec<Website>, String> {
-    let backup_data = fs::read_to_string(&backup_path)
-        .map_err(|e| format!("Failed to read backup file: {}", e))?;
-    
-    let websites: Vec<Website> = serde_json::from_str(&backup_data)
-        .map_err(|e| format!("Failed to parse backup data: {}", e))?;
-    
-    Ok(websites)
+    read_backup(&backup_path)
 }
 
 /// Delete a backup file
@@ -0,0 +1,57 @@
+// controllers/job_controller.rs
+use crate::controllers::screenshot_controller::run_screenshot_job;
+use crate::controllers::website_controller::run_website_job;
+use crate::models::job::Job;
+use crate::services::job_service::JobService;
+use tauri::{State, Window, command};
+
+#[command]
+pub async fn list_jobs(jobs: State<'_, JobService>) -> Result<Vec<Job>, String> {
+    Ok(jobs.list_jobs())
+}
+
+#[command]
+pub async fn get_job(job_id: String, jobs: State<'_, JobService>) -> Result<Job, String> {
+    jobs.get_job(&job_id)
+        .ok_or_else(|| format!("Job {} not found", job_id))
+}
+
+#[command]
+pub async fn cancel_job(job_id: String, jobs: State<'_, JobService>) -> Result<(), String> {
+    println!("Cancelling job {}", job_id);
+    jobs.cancel_job(&job_id)
+}
+
+/// Resume an interrupted, cancelled or failed job from its last checkpoint.
+/// Scan jobs need the API key again since it is never persisted.
+#[command]
+pub async fn resume_job(
+    window: Window,
+    job_id: String,
+    api_key: Option<String>,
+    jobs: State<'_, JobService>,
+) -> Result<Job, String> {
+    let (job, cancel) = jobs.resume_job(&job_id)?;
+    println!(
+        "Resuming {} job {} with {} websites left",
+        job.kind,
+        job.id,
+        job.pending_ids.len()
+    );
+
+    match job.kind.as_str() {
+        "screenshot" => {
+            tauri::async_runtime::spawn(run_screenshot_job(window, job.clone(), cancel));
+        }
+        "status" | "wordpress" | "scan" | "technologies" => {
+            tauri::async_runtime::spawn(run_website_job(window, job.clone(), cancel, api_key));
+        }
+        other => {
+            let error = format!("Jobs of kind {} cannot be resumed", other);
+            jobs.finish_job(&job.id, Some(error.clone()))?;
+            return Err(error);
+        }
+    }
+
+    Ok(job)
+}
@@ -0,0 +1,86 @@
+// controllers/maintenance_controller.rs
+use crate::models::maintenance::{MaintenanceWindow, UptimeSummary};
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::maintenance_service::{
+    CronSchedule, MAX_DURATION_MINUTES, MaintenanceService, uptime_summary,
+};
+use chrono::{DateTime, Duration, Utc};
+use tauri::{State, command};
+
+const WINDOW_SCOPES: &[&str] = &["website", "tag"];
+
+#[command]
+pub async fn get_maintenance_windows(
+    maintenance: State<'_, MaintenanceService>,
+) -> Result<Vec<MaintenanceWindow>, String> {
+    Ok(maintenance.get_windows())
+}
+
+#[command]
+pub async fn save_maintenance_windows(
+    windows: Vec<MaintenanceWindow>,
+    maintenance: State<'_, MaintenanceService>,
+) -> Result<(), String> {
+    for (index, window) in windows.iter().enumerate() {
+        validate_window(window)?;
+        if windows[..index].iter().any(|other| other.id == window.id) {
+            return Err(format!("Duplicate maintenance window id: {}", window.id));
+        }
+    }
+
+    maintenance.save_windows(windows)
+}
+
+/// Uptime of a website over the last `days` days, without maintenance windows
+#[command]
+pub async fn get_uptime(
+    website_id: i64,
+    days: Option<u32>,
+    history: State<'_, CheckHistoryService>,
+) -> Result<UptimeSummary, String> {
+    let days = days.unwrap_or(30);
+    let since = Utc::now() - Duration::days(days as i64);
+    let checks = history.get_history(website_id, Some(since))?;
+    Ok(uptime_summary(website_id, days, &checks))
+}
+
+fn validate_window(window: &MaintenanceWindow) -> Result<(), String> {
+    if !WINDOW_SCOPES.contains(&window.scope.as_str()) {
+        return Err(format!("Unknown maintenance scope: {}", window.scope));
+    }
+    if window.target.as_deref().is_none_or(str::is_empty) {
+        return Err(format!(
+            "Maintenance window {} needs a {} to apply to",
+            window.name, window.scope
+        ));
+    }
+
+    match &window.cron {
+        Some(cron) => {
+            CronSchedule::parse(cron)?;
+            if !(1..=MAX_DURATION_MINUTES).contains(&window.duration_minutes) {
+                return Err(format!(
+                    "Maintenance window {} must last between 1 and {} minutes",
+                    window.name, MAX_DURATION_MINUTES
+                ));
+            }
+        }
+        None => {
+            let parse = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            };
+            match (parse(&window.starts_at), parse(&window.ends_at)) {
+                (Some(starts_at), Some(ends_at)) if starts_at < ends_at => {}
+                _ => {
+                    return Err(format!(
+                        "Maintenance window {} needs a cron expression or a start before its end",
+                        window.name
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -3,4 +3,11 @@ pub mod screenshot_controller;
 pub mod website_controller;
 pub mod settings_controller;
 pub mod system_controller;
-pub mod search_controller;
\ No newline at end of file
+pub mod search_controller;
+pub mod job_controller;
+pub mod alert_controller;
+pub mod maintenance_controller;
+pub mod agent_controller;
+pub mod api_controller;
+pub mod status_page_controller;
+pub mod report_controller;
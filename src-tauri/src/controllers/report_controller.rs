@@ -0,0 +1,252 @@
+// controllers/report_controller.rs
+use crate::models::report::{
+    GeneratedReport, ReportDelivery, ReportRequest, ReportSchedule, ReportSettings,
+};
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::report_schedule_service::{ReportScheduleService, run_schedule};
+use crate::services::report_service::{
+    self, ReportService, ReportSources, validate_period, validate_request,
+};
+use crate::services::scan_storage_service::ScanStorageService;
+use crate::services::screenshot_history_service::ScreenshotHistoryService;
+use crate::services::storage_service::StorageService;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State, command};
+
+const FREQUENCIES: &[&str] = &["weekly", "monthly"];
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[command]
+pub async fn get_report_settings(
+    reports: State<'_, ReportService>,
+) -> Result<ReportSettings, String> {
+    Ok(reports.get_settings())
+}
+
+#[command]
+pub async fn save_report_settings(
+    settings: ReportSettings,
+    reports: State<'_, ReportService>,
+) -> Result<(), String> {
+    let color = settings.accent_color.strip_prefix('#').unwrap_or_default();
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "The accent color must look like #2563eb, not {}",
+            settings.accent_color
+        ));
+    }
+    let missing_logo = settings
+        .logo_path
+        .as_deref()
+        .filter(|logo| !logo.is_empty() && !Path::new(logo).is_file());
+    if let Some(logo) = missing_logo {
+        return Err(format!("Logo file not found: {}", logo));
+    }
+    validate_period(settings.period_days)?;
+
+    reports.save_settings(settings)
+}
+
+#[command]
+pub async fn list_report_templates(
+    reports: State<'_, ReportService>,
+) -> Result<Vec<String>, String> {
+    Ok(reports.list_templates())
+}
+
+#[command]
+pub async fn get_report_template(
+    name: String,
+    reports: State<'_, ReportService>,
+) -> Result<String, String> {
+    reports.get_template(&name)
+}
+
+#[command]
+pub async fn save_report_template(
+    name: String,
+    content: String,
+    reports: State<'_, ReportService>,
+) -> Result<(), String> {
+    reports.save_template(&name, &content)
+}
+
+/// Generate a branded report for one website or a client group (tag)
+#[command]
+pub async fn generate_report(
+    request: ReportRequest,
+    reports: State<'_, ReportService>,
+    storage: State<'_, StorageService>,
+    history: State<'_, CheckHistoryService>,
+    scans: State<'_, ScanStorageService>,
+    screenshots: State<'_, ScreenshotHistoryService>,
+) -> Result<GeneratedReport, String> {
+    validate_request(&request)?;
+    let sources = ReportSources {
+        storage: &storage,
+        history: &history,
+        scans: &scans,
+        screenshots: &screenshots,
+    };
+
+    let report =
+        report_service::generate_report(&reports, &sources, &request, chrono::Utc::now()).await?;
+    println!("Generated {}: {}", report.title, report.files.join(", "));
+    Ok(report)
+}
+
+#[command]
+pub async fn get_report_schedules(
+    report_schedules: State<'_, ReportScheduleService>,
+) -> Result<Vec<ReportSchedule>, String> {
+    Ok(report_schedules.get_schedules())
+}
+
+#[command]
+pub async fn save_report_schedules(
+    schedules: Vec<ReportSchedule>,
+    report_schedules: State<'_, ReportScheduleService>,
+) -> Result<Vec<ReportSchedule>, String> {
+    for (index, schedule) in schedules.iter().enumerate() {
+        validate_schedule(schedule)?;
+        if schedules[..index]
+            .iter()
+            .any(|other| other.id == schedule.id)
+        {
+            return Err(format!("Duplicate report schedule id: {}", schedule.id));
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let schedules: Vec<ReportSchedule> = schedules
+        .into_iter()
+        .map(|schedule| ReportSchedule {
+            created_at: schedule.created_at.or_else(|| Some(now.clone())),
+            ..schedule
+        })
+        .collect();
+    report_schedules.save_schedules(schedules.clone())?;
+    Ok(schedules)
+}
+
+/// Delivery history, most recent first, of one schedule or all of them
+#[command]
+pub async fn get_report_deliveries(
+    schedule_id: Option<String>,
+    report_schedules: State<'_, ReportScheduleService>,
+) -> Result<Vec<ReportDelivery>, String> {
+    Ok(report_schedules.get_deliveries(schedule_id.as_deref()))
+}
+
+/// Run a schedule now, whether it is due or not
+#[command]
+pub async fn run_report_schedule(
+    app: AppHandle,
+    id: String,
+    report_schedules: State<'_, ReportScheduleService>,
+) -> Result<ReportDelivery, String> {
+    let schedule = report_schedules
+        .get_schedules()
+        .into_iter()
+        .find(|schedule| schedule.id == id)
+        .ok_or_else(|| format!("Report schedule {} not found", id))?;
+    Ok(deliver_scheduled_report(&app, &schedule).await)
+}
+
+/// Run due report schedules every minute, for as long as the app runs. Schedules run
+/// one after the other so a slow PDF cannot start the same report twice.
+pub fn start_report_timer(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULE_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let due = app
+                .state::<ReportScheduleService>()
+                .due_schedules(chrono::Utc::now());
+            for schedule in due {
+                deliver_scheduled_report(&app, &schedule).await;
+            }
+        }
+    });
+}
+
+async fn deliver_scheduled_report(app: &AppHandle, schedule: &ReportSchedule) -> ReportDelivery {
+    let sources = ReportSources {
+        storage: app.state::<StorageService>().inner(),
+        history: app.state::<CheckHistoryService>().inner(),
+        scans: app.state::<ScanStorageService>().inner(),
+        screenshots: app.state::<ScreenshotHistoryService>().inner(),
+    };
+    let reports = app.state::<ReportService>();
+    let delivery = run_schedule(&reports, &sources, schedule, chrono::Utc::now()).await;
+
+    match &delivery.error {
+        None => println!(
+            "Delivered report {} to {}",
+            schedule.name, delivery.destination
+        ),
+        Some(e) => eprintln!("Failed to deliver report {}: {}", schedule.name, e),
+    }
+    if let Err(e) = app
+        .state::<ReportScheduleService>()
+        .record_delivery(delivery.clone())
+    {
+        eprintln!("Failed to record report delivery: {}", e);
+    }
+    delivery
+}
+
+fn validate_schedule(schedule: &ReportSchedule) -> Result<(), String> {
+    validate_request(&ReportRequest {
+        scope: schedule.scope.clone(),
+        target: schedule.target.clone(),
+        template: schedule.template.clone(),
+        formats: schedule.formats.clone(),
+        period_days: None,
+        output_dir: None,
+    })?;
+
+    let days = match schedule.frequency.as_str() {
+        "weekly" => 0..=6,
+        // Every month has a 28th
+        "monthly" => 1..=28,
+        other => {
+            return Err(format!(
+                "Unknown report frequency: {}, use one of {}",
+                other,
+                FREQUENCIES.join(", ")
+            ));
+        }
+    };
+    if !days.contains(&schedule.day) {
+        return Err(format!(
+            "Day {} is not valid for a {} schedule",
+            schedule.day, schedule.frequency
+        ));
+    }
+    if schedule.hour > 23 {
+        return Err(format!("Invalid hour: {}", schedule.hour));
+    }
+
+    match schedule.delivery.as_str() {
+        "email" if schedule.smtp.as_ref().is_none_or(|smtp| smtp.to.is_empty()) => Err(format!(
+            "Schedule {} needs SMTP settings and at least one recipient",
+            schedule.name
+        )),
+        "folder"
+            if schedule
+                .folder
+                .as_deref()
+                .is_none_or(|f| f.trim().is_empty()) =>
+        {
+            Err(format!(
+                "Schedule {} needs a folder to deliver to",
+                schedule.name
+            ))
+        }
+        "email" | "folder" => Ok(()),
+        other => Err(format!("Unknown report delivery: {}", other)),
+    }
+}
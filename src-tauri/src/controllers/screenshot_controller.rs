@@ -1,131 +1,283 @@
 // controllers/screenshot_controller.rs
+use crate::models::job::Job;
+use crate::models::screenshot::{ScreenshotCapture, ScreenshotDiff, ScreenshotOptions};
+use crate::services::job_service::JobService;
+use crate::services::screenshot_history_service::ScreenshotHistoryService;
+use crate::services::screenshot_service::ScreenshotService;
+use crate::services::search_service::{BulkTarget, select_websites};
+use crate::services::storage_service::StorageService;
 use base64::encode;
-use headless_chrome::{Browser, protocol::page::ScreenshotFormat};
+use headless_chrome::Browser;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
-use tauri::{Emitter, State, Window, command};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager, State, Window, command};
+use tokio::task::JoinSet;
 
-static CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
+// Tabs captured in parallel during a bulk run
+const DEFAULT_CONCURRENCY: usize = 4;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ScreenshotProgress {
+    pub job_id: String,
     pub total: usize,
     pub completed: usize,
     pub current_website: String,
     pub current_id: i64,
     pub is_complete: bool,
     pub errors: Vec<String>,
+    pub changed_websites: Vec<i64>, // Sites whose capture differs significantly from the last one
+    pub last_id: i64,               // Website whose capture just finished
+    pub last_duration_ms: Option<u64>, // How long that capture took; None if its task panicked
+    pub average_ms: u64,            // Mean capture time so far
+    pub eta_seconds: u64,           // Estimated time left at the current pace
+}
+
+// Stored in `Job.params` so a resumed job captures the same way
+#[derive(Clone, Serialize, Deserialize)]
+struct ScreenshotJobParams {
+    options: ScreenshotOptions,
+    concurrency: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScreenshotDiffResult {
+    pub diff: ScreenshotDiff,
+    pub overlay: String, // PNG data URL
 }
 
 #[command]
-pub async fn take_screenshot(url: String) -> Result<String, String> {
-    if CANCEL_FLAG.load(Ordering::SeqCst) {
-        return Err("Screenshot cancelled".to_string());
-    }
+pub async fn take_screenshot(
+    url: String,
+    options: Option<ScreenshotOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    ScreenshotService::validate(&options)?;
 
     let browser = match Browser::default() {
         Ok(browser) => browser,
         Err(e) => return Err(format!("Failed to launch browser: {}", e)),
     };
 
-    let tab = match browser.new_tab() {
-        Ok(tab) => tab,
-        Err(e) => return Err(format!("Failed to create new tab: {}", e)),
-    };
-
-    // Set a navigation timeout
-    tab.set_default_timeout(Duration::from_secs(30));
-
-    // Navigate and handle potential errors
-    if let Err(e) = tab.navigate_to(&url) {
-        return Err(format!("Failed to navigate to URL: {}", e));
-    }
+    let capture_options = options.clone();
+    let screenshot_data = tokio::task::spawn_blocking(move || {
+        ScreenshotService::capture(&browser, &url, &capture_options)
+    })
+    .await
+    .map_err(|e| format!("Screenshot task failed: {}", e))??;
 
-    if let Err(e) = tab.wait_until_navigated() {
-        return Err(format!("Failed to wait for navigation: {}", e));
-    }
+    Ok(to_data_url(&screenshot_data, &options.format))
+}
 
-    // Wait for the page to be more stable
-    tokio::time::sleep(Duration::from_secs(3)).await;
+/// Start a background screenshot job over the targeted websites (all by default)
+/// and return it immediately. Progress is reported through `screenshot-progress`
+/// and `job-progress` events.
+#[command]
+pub async fn take_bulk_screenshots(
+    window: Window,
+    storage: State<'_, StorageService>,
+    jobs: State<'_, JobService>,
+    options: Option<ScreenshotOptions>,
+    concurrency: Option<usize>,
+    target: Option<BulkTarget>,
+) -> Result<Job, String> {
+    let options = options.unwrap_or_default();
+    ScreenshotService::validate(&options)?;
+
+    let websites = select_websites(
+        storage.get_websites().map_err(|e| e.to_string())?,
+        &target.unwrap_or_default(),
+    );
+    let params = serde_json::to_value(ScreenshotJobParams {
+        options,
+        concurrency: concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1),
+    })
+    .map_err(|e| format!("Failed to serialize job options: {}", e))?;
+
+    let (job, cancel) = jobs.create_job(
+        "screenshot",
+        websites.iter().map(|w| w.id).collect(),
+        params,
+    )?;
+
+    tauri::async_runtime::spawn(run_screenshot_job(window, job.clone(), cancel));
+
+    Ok(job)
+}
 
-    let screenshot_data = match tab.capture_screenshot(ScreenshotFormat::PNG, None, true) {
-        Ok(data) => data,
-        Err(e) => return Err(format!("Failed to capture screenshot: {}", e)),
-    };
+/// Run (or resume) a screenshot job until its pending websites are exhausted or it is cancelled
+pub async fn run_screenshot_job(window: Window, job: Job, cancel: Arc<AtomicBool>) {
+    let app = window.app_handle().clone();
+    let jobs = app.state::<JobService>();
 
-    let base64_screenshot = encode(&screenshot_data);
-    let data_url = format!("data:image/png;base64,{}", base64_screenshot);
+    let result = screenshot_websites(&window, &app, &job, &cancel).await;
+    if let Err(e) = &result {
+        eprintln!("Screenshot job {} failed: {}", job.id, e);
+    }
 
-    Ok(data_url)
+    match jobs.finish_job(&job.id, result.err()) {
+        Ok(job) => {
+            let _ = window.emit("job-progress", &job);
+        }
+        Err(e) => eprintln!("Failed to finish job {}: {}", job.id, e),
+    }
 }
 
-
-#[command]
-pub async fn take_bulk_screenshots(
-    window: Window,
-    storage: State<'_, crate::services::storage_service::StorageService>,
+async fn screenshot_websites(
+    window: &Window,
+    app: &tauri::AppHandle,
+    job: &Job,
+    cancel: &AtomicBool,
 ) -> Result<(), String> {
-    CANCEL_FLAG.store(false, Ordering::SeqCst);
+    let storage = app.state::<StorageService>();
+    let jobs = app.state::<JobService>();
 
-    let websites = storage.get_websites().map_err(|e| e.to_string())?;
-    let total_websites = websites.len();
+    let params: ScreenshotJobParams = serde_json::from_value(job.params.clone())
+        .map_err(|e| format!("Invalid screenshot job options: {}", e))?;
+    let options = params.options;
 
-    if total_websites == 0 {
-        return Ok(());
-    }
+    let websites: Vec<_> = storage
+        .get_websites()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|w| job.pending_ids.contains(&w.id))
+        .collect();
 
     let mut progress = ScreenshotProgress {
-        total: total_websites,
-        completed: 0,
+        job_id: job.id.clone(),
+        total: job.total,
+        completed: job.completed,
         current_website: String::new(),
         current_id: 0,
         is_complete: false,
         errors: Vec::new(),
+        changed_websites: Vec::new(),
+        last_id: 0,
+        last_duration_ms: None,
+        average_ms: 0,
+        eta_seconds: 0,
     };
+    let mut captured = 0u32;
+    let mut capture_time = Duration::ZERO;
+
+    // One browser shared by a bounded pool of tabs
+    let browser =
+        Arc::new(Browser::default().map_err(|e| format!("Failed to launch browser: {}", e))?);
+
+    let mut pending = websites.into_iter();
+    let mut running = JoinSet::new();
+    // Websites being captured, by task, and the order they were started in
+    let mut in_flight = HashMap::new();
+    let mut started_order = VecDeque::new();
+
+    loop {
+        while running.len() < params.concurrency && !cancel.load(Ordering::SeqCst) {
+            let Some(website) = pending.next() else {
+                break;
+            };
+
+            let browser = Arc::clone(&browser);
+            let options = options.clone();
+            let url = website.url.clone();
+            let app = app.clone();
+            let website_id = website.id;
+            let task = running.spawn_blocking(move || {
+                let started = Instant::now();
+                let result = ScreenshotService::capture(&browser, &url, &options);
+                let elapsed = started.elapsed();
+                // Diffing against the previous capture is image work too, so it stays
+                // off the async runtime
+                let result = result.map(|data| {
+                    let capture = app
+                        .state::<ScreenshotHistoryService>()
+                        .record_capture(website_id, &data);
+                    (data, capture)
+                });
+                (result, elapsed)
+            });
+            started_order.push_back(task.id());
+            in_flight.insert(task.id(), website);
+        }
 
-    // Create browser instance once for better performance
-    let browser = Browser::default().map_err(|e| format!("Failed to launch browser: {}", e))?;
-
-    for (index, website) in websites.iter().enumerate() {
-        if CANCEL_FLAG.load(Ordering::SeqCst) {
+        let Some(joined) = running.join_next_with_id().await else {
             break;
+        };
+        let (task_id, result, elapsed) = match joined {
+            Ok((task_id, (result, elapsed))) => {
+                captured += 1;
+                capture_time += elapsed;
+                (task_id, result, Some(elapsed))
+            }
+            // A panicking capture only fails its own website
+            Err(e) => (e.id(), Err(format!("Screenshot task failed: {}", e)), None),
+        };
+        let Some(website) = in_flight.remove(&task_id) else {
+            continue;
+        };
+        started_order.retain(|id| *id != task_id);
+
+        // The longest-running capture is the one the user is waiting on
+        let current = started_order.front().and_then(|id| in_flight.get(id));
+        progress.current_website = current.map(|w| w.name.clone()).unwrap_or_default();
+        progress.current_id = current.map(|w| w.id).unwrap_or_default();
+        progress.completed += 1;
+        progress.last_id = website.id;
+        progress.last_duration_ms = elapsed.map(|elapsed| elapsed.as_millis() as u64);
+        if captured > 0 {
+            let average = capture_time / captured;
+            let remaining = progress.total.saturating_sub(progress.completed) as u32;
+            progress.average_ms = average.as_millis() as u64;
+            progress.eta_seconds = (average * remaining / params.concurrency as u32).as_secs();
         }
 
-        progress.current_website = website.name.clone();
-        progress.current_id = website.id;
-        progress.completed = index;
-
-        window
-            .emit("screenshot-progress", &progress)
-            .map_err(|e| format!("Failed to emit progress: {}", e))?;
-
-        match take_screenshot_internal(&browser, &website.url).await {
-            Ok(screenshot_data) => {
-                let mut updated_website = website.clone();
-                updated_website.screenshot = Some(screenshot_data);
-                updated_website.last_checked = Some(chrono::Utc::now().to_rfc3339());
+        let mut site_errors = Vec::new();
+        match result {
+            Ok((screenshot_data, capture)) => {
+                match capture {
+                    Ok(capture) => {
+                        if capture.diff.as_ref().is_some_and(|d| d.significant) {
+                            println!("Visual change detected for {}", website.name);
+                            progress.changed_websites.push(website.id);
+                        }
+                    }
+                    Err(e) => site_errors.push(format!(
+                        "Failed to record screenshot history for {}: {}",
+                        website.name, e
+                    )),
+                }
 
-                if let Err(e) = storage.update_website(&updated_website) {
-                    progress.errors.push(format!(
+                let data_url = to_data_url(&screenshot_data, &options.format);
+                if let Err(e) = storage.modify_website(website.id, |w| {
+                    w.screenshot = Some(data_url);
+                    w.last_checked = Some(chrono::Utc::now().to_rfc3339());
+                }) {
+                    site_errors.push(format!(
                         "Failed to save screenshot for {}: {}",
                         website.name, e
                     ));
                 }
             }
-            Err(e) => {
-                progress
-                    .errors
-                    .push(format!("Failed to screenshot {}: {}", website.name, e));
-            }
+            Err(e) => site_errors.push(format!("Failed to screenshot {}: {}", website.name, e)),
         }
 
-        tokio::time::sleep(Duration::from_millis(1000)).await;
+        progress.errors.extend(site_errors.iter().cloned());
+        let site_error = (!site_errors.is_empty()).then(|| site_errors.join("; "));
+        let checkpoint = jobs.record_progress(&job.id, website.id, site_error)?;
+
+        window
+            .emit("screenshot-progress", &progress)
+            .map_err(|e| format!("Failed to emit progress: {}", e))?;
+        window
+            .emit("job-progress", &checkpoint)
+            .map_err(|e| format!("Failed to emit progress: {}", e))?;
     }
 
-    progress.completed = total_websites;
     progress.is_complete = true;
     progress.current_website = String::new();
+    progress.current_id = 0;
+    progress.eta_seconds = 0;
 
     window
         .emit("screenshot-progress", &progress)
@@ -134,33 +286,46 @@ pub async fn take_bulk_screenshots(
     Ok(())
 }
 
-async fn take_screenshot_internal(browser: &Browser, url: &str) -> Result<String, String> {
-    let tab = browser
-        .new_tab()
-        .map_err(|e| format!("Failed to create new tab: {}", e))?;
-
-    tab.set_default_timeout(Duration::from_secs(30));
-
-    tab.navigate_to(url)
-        .map_err(|e| format!("Failed to navigate to URL: {}", e))?;
-
-    tab.wait_until_navigated()
-        .map_err(|e| format!("Failed to wait for navigation: {}", e))?;
-
-    tokio::time::sleep(Duration::from_secs(3)).await;
-
-    let screenshot_data = tab
-        .capture_screenshot(ScreenshotFormat::PNG, None, true)
-        .map_err(|e| format!("Failed to capture screenshot: {}", e))?;
+fn to_data_url(image_data: &[u8], format: &str) -> String {
+    format!(
+        "data:{};base64,{}",
+        ScreenshotService::mime_type(format),
+        encode(image_data)
+    )
+}
 
-    let base64_screenshot = encode(&screenshot_data);
-    let data_url = format!("data:image/png;base64,{}", base64_screenshot);
+#[command]
+pub async fn get_screenshot_history(
+    website_id: i64,
+    history: State<'_, ScreenshotHistoryService>,
+) -> Result<Vec<ScreenshotCapture>, String> {
+    history.get_history(website_id)
+}
 
-    Ok(data_url)
+#[command]
+pub async fn get_screenshot_diff(
+    website_id: i64,
+    from_id: String,
+    to_id: String,
+    app: tauri::AppHandle,
+) -> Result<ScreenshotDiffResult, String> {
+    // Decoding and comparing two full-page captures takes a while
+    tokio::task::spawn_blocking(move || {
+        let history = app.state::<ScreenshotHistoryService>();
+        let (diff, overlay_png) = history.diff_overlay(website_id, &from_id, &to_id)?;
+
+        Ok(ScreenshotDiffResult {
+            diff,
+            overlay: to_data_url(&overlay_png, "png"),
+        })
+    })
+    .await
+    .map_err(|e| format!("Screenshot diff task failed: {}", e))?
 }
 
+/// Cancel every running screenshot job; use `cancel_job` to target a single one
 #[command]
-pub async fn cancel_bulk_screenshots() -> Result<(), String> {
-    CANCEL_FLAG.store(true, Ordering::SeqCst);
+pub async fn cancel_bulk_screenshots(jobs: State<'_, JobService>) -> Result<(), String> {
+    jobs.cancel_kind("screenshot");
     Ok(())
 }
@@ -0,0 +1,33 @@
+// controllers/status_page_controller.rs
+use crate::models::status_page::{StatusPage, StatusPageOptions};
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::search_service::{BulkTarget, select_websites};
+use crate::services::status_page::{publish_status_page, validate_options};
+use crate::services::storage_service::StorageService;
+use serde::Serialize;
+use tauri::{State, command};
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageOutput {
+    pub files: Vec<String>,
+    pub page: StatusPage,
+}
+
+/// Write a static status page and its JSON feed for the targeted websites
+#[command]
+pub async fn generate_status_page(
+    options: StatusPageOptions,
+    target: BulkTarget,
+    storage: State<'_, StorageService>,
+    history: State<'_, CheckHistoryService>,
+) -> Result<StatusPageOutput, String> {
+    validate_options(&options)?;
+    let websites = select_websites(storage.get_websites().map_err(|e| e.to_string())?, &target);
+    let (page, files) = publish_status_page(&websites, &history, &options, chrono::Utc::now())?;
+    println!(
+        "Status page for {} websites written to {}",
+        websites.len(),
+        options.output_dir
+    );
+    Ok(StatusPageOutput { files, page })
+}
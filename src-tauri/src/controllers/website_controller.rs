@@ -1,10 +1,35 @@
 // controllers/website_controller.rs
+use crate::controllers::agent_controller::confirmed_check;
+use crate::controllers::alert_controller::{record_check, send_alerts};
+use crate::services::search_service::{BulkTarget, select_websites};
+use crate::models::check::CheckResult;
+use crate::models::job::Job;
+use crate::models::technology::Technology;
 use crate::models::website::{WebVitals, Website};
-use crate::models::wpscan::WpscanResult;
+use crate::models::wpscan::{PortfolioScanReport, ScanRecord, WpscanOptions, WpscanResult};
+use crate::services::agent_service::{AgentService, Confirmation, confirm_check};
+use crate::services::alert_rule_service::AlertRuleService;
+use crate::services::alert_service::{certificate_event, vulnerability_event};
+use crate::services::certificate_service::certificate_expiry;
+use crate::services::check_service::probe_website;
+use crate::services::job_service::JobService;
+use crate::services::page_analysis::{FetchedPage, PageAnalysis, analyze_page};
+use crate::services::scan_report::build_scan_report;
+use crate::services::import_export_service::{
+    ExportOptions, ImportResult, export_data, import_data,
+};
+use crate::services::scan_storage_service::{ScanStorageService, apply_scan, scan_stored_website};
 use crate::services::storage_service::StorageService;
+use crate::services::technology_service::TechnologyService;
 use crate::services::wpscan_service::WpscanService;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{Emitter, Manager, State, Window};
+use tokio::task::JoinSet;
+
+// Websites processed in parallel by bulk status/WordPress/scan jobs
+const BULK_CONCURRENCY: usize = 8;
 
 // Define the validation result struct
 #[derive(Serialize, Deserialize)]
@@ -56,84 +81,45 @@ pub async fn save_websites(
 }
 
 
-#[derive(Serialize, Deserialize)]
-pub struct ExportOptions {
-    pub format: String, // "json", "csv", "full-backup"
-    pub include_notes: bool,
-    pub include_custom_statuses: bool,
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct FullBackupExport {
-    pub websites: Vec<Website>,
-    pub custom_statuses: Vec<CustomStatus>,
-    pub export_date: String,
-    pub version: String,
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct CustomStatus {
-    pub value: String,
-    pub label: String,
-    pub color: String,
-}
-
 #[tauri::command]
 pub async fn export_websites(
     storage: State<'_, StorageService>,
     options: ExportOptions,
 ) -> Result<String, String> {
-    match storage.get_websites() {
-        Ok(websites) => {
-            // Ensure all websites have proper structure before export
-            let websites: Vec<Website> = websites
-                .into_iter()
-                .map(|mut website| {
-                    if website.vitals.is_none() {
-                        website.vitals = Some(WebVitals::default());
-                    }
-                    if website.notes.is_none() {
-                        website.notes = Some(crate::models::website::WebsiteNotes::default());
-                    }
-                    website
-                })
-                .collect();
-
-            match options.format.as_str() {
-                "full-backup" => {
-                    // Create full backup structure
-                    let backup = FullBackupExport {
-                        websites,
-                        custom_statuses: vec![], // You'll need to get these from your storage
-                        export_date: chrono::Utc::now().to_rfc3339(),
-                        version: "1.0".to_string(),
-                    };
-                    serde_json::to_string_pretty(&backup)
-                        .map_err(|e| format!("Failed to serialize backup: {}", e))
-                }
-                "json" => serde_json::to_string_pretty(&websites)
-                    .map_err(|e| format!("Failed to serialize websites: {}", e)),
-                _ => Err("Unsupported export format".to_string()),
-            }
-        }
-        Err(e) => Err(format!("Failed to get websites: {}", e)),
-    }
+    export_data(&storage, &options)
 }
 
+/// Check a URL. When it belongs to a stored website, a failure is confirmed by the remote
+/// agents, and the check is recorded in its history and run through its alert rule.
 #[tauri::command]
-pub async fn check_website_status(url: String) -> Result<u16, String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    match client.get(&url).send().await {
-        Ok(response) => Ok(response.status().as_u16()),
-        Err(_) => Ok(0), // Return 0 for connection errors
+pub async fn check_website_status(
+    url: String,
+    app: tauri::AppHandle,
+    storage: State<'_, StorageService>,
+) -> Result<u16, String> {
+    let websites = storage.get_websites().map_err(|e| e.to_string())?;
+    match websites.iter().find(|w| w.url == url) {
+        Some(website) => Ok(check_stored_website(&app, website).await?.status),
+        None => Ok(probe_website(&url).await?.status),
     }
 }
 
+/// Check a stored website, have the remote agents confirm a failure and record the
+/// check. Returns the recorded check, marked unconfirmed when no agent answered.
+pub async fn check_stored_website(
+    app: &tauri::AppHandle,
+    website: &Website,
+) -> Result<CheckResult, String> {
+    let check = CheckResult {
+        website_id: website.id,
+        ..probe_website(&website.url).await?
+    };
+
+    let confirmed = confirmed_check(app, &website.url, check).await;
+    record_check(app, website, confirmed.clone());
+    Ok(confirmed)
+}
+
 #[tauri::command]
 pub async fn get_web_vitals(_url: String) -> Result<WebVitals, String> {
     // Return default vitals for now - you can implement actual web vitals collection later
@@ -141,72 +127,480 @@ pub async fn get_web_vitals(_url: String) -> Result<WebVitals, String> {
 }
 
 #[tauri::command]
-pub async fn scan_website(website: Website, api_key: String) -> Result<WpscanResult, String> {
-    let wpscan_service = WpscanService::new(api_key);
-
-    match wpscan_service.scan_website(&website.url).await {
-        Ok(mut result) => {
-            result.url = website.url.clone();
-            if result.wordpress_version.is_none() {
-                result.wordpress_version = Some("Unknown".to_string());
-            }
-            println!("WPScan completed successfully for {}", website.url);
-            Ok(result)
-        }
-        Err(e) => {
-            eprintln!("WPScan error for {}: {}", website.url, e);
-            Ok(WpscanResult {
-                url: website.url,
-                wordpress_version: Some("Unknown".to_string()),
-                vulnerabilities: vec![],
-                plugins: vec![],
-                themes: vec![],
-                users: vec![],
-                scan_date: chrono::Utc::now().to_rfc3339(),
-                is_wordpress: false,
-            })
-        }
+pub async fn scan_website(
+    website: Website,
+    api_key: Option<String>,
+    options: Option<WpscanOptions>,
+    aggressive_consent: Option<bool>,
+    app: tauri::AppHandle,
+    storage: State<'_, StorageService>,
+    scans: State<'_, ScanStorageService>,
+) -> Result<WpscanResult, String> {
+    let options = options.unwrap_or_default();
+    check_aggressive_consent(&options, aggressive_consent)?;
+    let wpscan_service = WpscanService::with_options(api_key, &options)?;
+
+    let (result, stored) = scan_stored_website(&wpscan_service, &storage, &scans, &website)
+        .await
+        .inspect_err(|e| eprintln!("WPScan error for {}: {}", website.url, e))?;
+    println!("WPScan completed successfully for {}", website.url);
+
+    if let Some((website, record)) = stored {
+        send_scan_alerts(&app, &website, &record);
+    }
+    Ok(result)
+}
+
+/// Aggressive enumeration sends around a hundred extra requests to each client server,
+/// so the caller has to confirm it explicitly. Resumed jobs keep the original consent.
+fn check_aggressive_consent(options: &WpscanOptions, consent: Option<bool>) -> Result<(), String> {
+    if options.aggressive && consent != Some(true) {
+        return Err(
+            "Aggressive plugin enumeration probes about a hundred URLs on the target server; \
+             pass aggressiveConsent: true to confirm you are allowed to do this"
+                .to_string(),
+        );
     }
+    Ok(())
+}
+
+/// Persist a scan and mirror its vulnerabilities into the website's security notes.
+/// Returns the updated website, or `None` if it is not stored.
+fn store_scan(
+    app: &tauri::AppHandle,
+    storage: &StorageService,
+    scans: &ScanStorageService,
+    website_id: i64,
+    result: &mut WpscanResult,
+) -> Result<Option<Website>, String> {
+    let Some((website, record)) = apply_scan(storage, scans, website_id, result)? else {
+        return Ok(None);
+    };
+
+    send_scan_alerts(app, &website, &record);
+    Ok(Some(website))
+}
+
+fn send_scan_alerts(app: &tauri::AppHandle, website: &Website, record: &ScanRecord) {
+    let events = vulnerability_event(website, record).into_iter().collect();
+    send_alerts(app, app.state::<AlertRuleService>().route(website, events));
+}
+
+#[tauri::command]
+pub async fn get_scan_history(
+    website_id: i64,
+    scans: State<'_, ScanStorageService>,
+) -> Result<Vec<ScanRecord>, String> {
+    scans.get_history(website_id)
 }
 
 #[tauri::command]
-pub async fn detect_wordpress(url: String) -> Result<bool, String> {
+pub async fn get_latest_scan(
+    website_id: i64,
+    scans: State<'_, ScanStorageService>,
+) -> Result<Option<ScanRecord>, String> {
+    scans.get_latest(website_id)
+}
+
+/// Aggregate the latest scans of the targeted websites (all by default): where each
+/// plugin is installed, vulnerable versions shared between sites and a fix list
+#[tauri::command]
+pub async fn get_scan_report(
+    target: Option<BulkTarget>,
+    storage: State<'_, StorageService>,
+    scans: State<'_, ScanStorageService>,
+) -> Result<PortfolioScanReport, String> {
+    let websites = select_websites(
+        storage.get_websites().map_err(|e| e.to_string())?,
+        &target.unwrap_or_default(),
+    );
+    scan_report(websites, &scans)
+}
+
+fn scan_report(
+    websites: Vec<Website>,
+    scans: &ScanStorageService,
+) -> Result<PortfolioScanReport, String> {
+    let sites = websites
+        .into_iter()
+        .map(|website| {
+            let latest = scans.get_latest(website.id)?;
+            Ok((website, latest))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(build_scan_report(&sites))
+}
+
+/// Decide whether a site runs WordPress. When `website_id` is given the verdict
+/// (and the technologies found on the way) is stored on the website.
+#[tauri::command]
+pub async fn detect_wordpress(
+    url: String,
+    website_id: Option<i64>,
+    storage: State<'_, StorageService>,
+    technology: State<'_, TechnologyService>,
+) -> Result<bool, String> {
     if url.trim().is_empty() {
         return Err("URL is required".to_string());
     }
 
     println!("Detecting WordPress for: {}", url);
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    match client.get(&url).send().await {
-        Ok(response) => {
-            if let Ok(text) = response.text().await {
-                let is_wordpress = text.contains("wp-content")
-                    || text.contains("wp-includes")
-                    || text.contains("WordPress")
-                    || text.contains("wp-json")
-                    || text.contains("/wp-admin/")
-                    || text.contains("wp-embed.min.js");
-
-                println!("WordPress detection for {}: {}", url, is_wordpress);
-                Ok(is_wordpress)
-            } else {
-                println!("Failed to read response text for {}", url);
-                Ok(false)
+    match analyze_website(&reqwest::Client::new(), &url, &technology).await {
+        Ok(analysis) => {
+            println!("WordPress detection for {}: {}", url, analysis.is_wordpress);
+            if let Some(id) = website_id {
+                store_analysis(&storage, id, &analysis)?;
             }
+            Ok(analysis.is_wordpress)
         }
         Err(e) => {
+            // An unreachable site says nothing about its CMS, so nothing is stored
             println!("Error detecting WordPress for {}: {}", url, e);
             Ok(false)
         }
     }
 }
 
+/// Detect the CMS, frameworks, analytics, CDN and server software of a site.
+/// When `website_id` is given the result is stored on the website.
+#[tauri::command]
+pub async fn detect_technologies(
+    url: String,
+    website_id: Option<i64>,
+    storage: State<'_, StorageService>,
+    technology: State<'_, TechnologyService>,
+) -> Result<Vec<Technology>, String> {
+    if url.trim().is_empty() {
+        return Err("URL is required".to_string());
+    }
+
+    let analysis = analyze_website(&reqwest::Client::new(), &url, &technology).await?;
+    if let Some(id) = website_id {
+        store_analysis(&storage, id, &analysis)?;
+    }
+    Ok(analysis.technologies)
+}
+
+/// Fetch a homepage once and run every detector on it
+async fn analyze_website(
+    client: &reqwest::Client,
+    url: &str,
+    technology: &TechnologyService,
+) -> Result<PageAnalysis, String> {
+    let page = FetchedPage::fetch(client, url).await?;
+    Ok(analyze_page(&page, Some(technology)))
+}
+
+fn store_analysis(
+    storage: &StorageService,
+    website_id: i64,
+    analysis: &PageAnalysis,
+) -> Result<Option<Website>, String> {
+    storage
+        .modify_website(website_id, |w| {
+            w.is_wordpress = Some(analysis.is_wordpress);
+            w.technologies = Some(analysis.technologies.clone());
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Detect technologies for the targeted websites as a background job and store them
+#[tauri::command]
+pub async fn detect_technologies_bulk(
+    window: Window,
+    target: Option<BulkTarget>,
+    storage: State<'_, StorageService>,
+    jobs: State<'_, JobService>,
+) -> Result<Job, String> {
+    start_website_job(
+        window,
+        "technologies",
+        target,
+        None,
+        serde_json::Value::Null,
+        &storage,
+        &jobs,
+    )
+}
+
+/// Check HTTP status for the targeted websites (all by default) as a background job
+#[tauri::command]
+pub async fn check_websites_status(
+    window: Window,
+    target: Option<BulkTarget>,
+    storage: State<'_, StorageService>,
+    jobs: State<'_, JobService>,
+) -> Result<Job, String> {
+    start_website_job(
+        window,
+        "status",
+        target,
+        None,
+        serde_json::Value::Null,
+        &storage,
+        &jobs,
+    )
+}
+
+/// Run WordPress detection for the targeted websites as a background job
+#[tauri::command]
+pub async fn detect_wordpress_bulk(
+    window: Window,
+    target: Option<BulkTarget>,
+    storage: State<'_, StorageService>,
+    jobs: State<'_, JobService>,
+) -> Result<Job, String> {
+    start_website_job(
+        window,
+        "wordpress",
+        target,
+        None,
+        serde_json::Value::Null,
+        &storage,
+        &jobs,
+    )
+}
+
+/// Run a WPScan for the targeted websites as a background job.
+/// Each result is emitted as a `scan-result` event, and the aggregated report over
+/// the job's websites as a `scan-report` event once the job ends.
+#[tauri::command]
+pub async fn scan_websites(
+    window: Window,
+    target: Option<BulkTarget>,
+    api_key: Option<String>,
+    options: Option<WpscanOptions>,
+    aggressive_consent: Option<bool>,
+    storage: State<'_, StorageService>,
+    jobs: State<'_, JobService>,
+) -> Result<Job, String> {
+    let options = options.unwrap_or_default();
+    check_aggressive_consent(&options, aggressive_consent)?;
+    // Fail fast on a missing key or unreadable offline database
+    WpscanService::with_options(api_key.clone(), &options)?;
+
+    let params = serde_json::to_value(&options)
+        .map_err(|e| format!("Failed to serialize scan options: {}", e))?;
+    start_website_job(window, "scan", target, api_key, params, &storage, &jobs)
+}
+
+fn start_website_job(
+    window: Window,
+    kind: &str,
+    target: Option<BulkTarget>,
+    api_key: Option<String>,
+    params: serde_json::Value,
+    storage: &StorageService,
+    jobs: &JobService,
+) -> Result<Job, String> {
+    let websites = select_websites(
+        storage.get_websites().map_err(|e| e.to_string())?,
+        &target.unwrap_or_default(),
+    );
+    println!("Starting {} job for {} websites", kind, websites.len());
+
+    // The API key is deliberately not persisted with the job; it is passed again on resume
+    let (job, cancel) = jobs.create_job(kind, websites.iter().map(|w| w.id).collect(), params)?;
+
+    tauri::async_runtime::spawn(run_website_job(window, job.clone(), cancel, api_key));
+
+    Ok(job)
+}
+
+enum BulkOutcome {
+    // The check to record once agents had their say, and the certificate expiry of
+    // HTTPS sites
+    Status(Box<Confirmation>, Option<chrono::DateTime<chrono::Utc>>),
+    Page(Box<PageAnalysis>), // "wordpress" and "technologies" share one fetch
+    Scan(Box<WpscanResult>),
+}
+
+/// Run (or resume) a "status", "wordpress", "scan" or "technologies" job
+pub async fn run_website_job(
+    window: Window,
+    job: Job,
+    cancel: Arc<AtomicBool>,
+    api_key: Option<String>,
+) {
+    let app = window.app_handle().clone();
+    let jobs = app.state::<JobService>();
+
+    let result = process_websites(&window, &app, &job, &cancel, api_key).await;
+    if let Err(e) = &result {
+        eprintln!("{} job {} failed: {}", job.kind, job.id, e);
+    }
+
+    match jobs.finish_job(&job.id, result.err()) {
+        Ok(job) => {
+            let _ = window.emit("job-progress", &job);
+        }
+        Err(e) => eprintln!("Failed to finish job {}: {}", job.id, e),
+    }
+
+    if job.kind == "scan" {
+        let storage = app.state::<StorageService>();
+        let scans = app.state::<ScanStorageService>();
+        let report = storage
+            .get_websites()
+            .map_err(|e| e.to_string())
+            .and_then(|websites| {
+                let websites = websites
+                    .into_iter()
+                    .filter(|w| job.pending_ids.contains(&w.id))
+                    .collect();
+                scan_report(websites, &scans)
+            });
+        match report {
+            Ok(report) => {
+                let _ = window.emit("scan-report", &report);
+            }
+            Err(e) => eprintln!("Failed to build scan report for job {}: {}", job.id, e),
+        }
+    }
+}
+
+async fn process_websites(
+    window: &Window,
+    app: &tauri::AppHandle,
+    job: &Job,
+    cancel: &AtomicBool,
+    api_key: Option<String>,
+) -> Result<(), String> {
+    let storage = app.state::<StorageService>();
+    let scans = app.state::<ScanStorageService>();
+    let jobs = app.state::<JobService>();
+    let alert_rules = app.state::<AlertRuleService>();
+
+    let wpscan = if job.kind == "scan" {
+        let options: WpscanOptions =
+            serde_json::from_value(job.params.clone()).unwrap_or_default();
+        Some(Arc::new(WpscanService::with_options(api_key, &options)?))
+    } else {
+        None
+    };
+    let technology = if job.kind == "wordpress" || job.kind == "technologies" {
+        Some(app.state::<TechnologyService>().inner().clone())
+    } else {
+        None
+    };
+    let client = reqwest::Client::new();
+    let agents = Arc::new(app.state::<AgentService>().enabled_agents());
+
+    let mut pending = storage
+        .get_websites()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|w| job.pending_ids.contains(&w.id));
+    let mut running = JoinSet::new();
+
+    loop {
+        while running.len() < BULK_CONCURRENCY && !cancel.load(Ordering::SeqCst) {
+            let Some(website) = pending.next() else {
+                break;
+            };
+
+            let kind = job.kind.clone();
+            let wpscan = wpscan.clone();
+            let technology = technology.clone();
+            let client = client.clone();
+            let agents = agents.clone();
+            running.spawn(async move {
+                let outcome = match kind.as_str() {
+                    "status" => {
+                        let check = match probe_website(&website.url).await {
+                            Ok(check) => check,
+                            Err(e) => return (website, Err(e)),
+                        };
+                        let check = CheckResult {
+                            website_id: website.id,
+                            ..check
+                        };
+                        let confirmed =
+                            confirm_check(&client, &agents, &website.url, check).await;
+                        // A failed handshake leaves the stored expiry untouched
+                        let certificate = certificate_expiry(&website.url).await.ok().flatten();
+                        Ok(BulkOutcome::Status(Box::new(confirmed), certificate))
+                    }
+                    "wordpress" | "technologies" => match &technology {
+                        Some(technology) => analyze_website(&client, &website.url, technology)
+                            .await
+                            .map(|analysis| BulkOutcome::Page(Box::new(analysis))),
+                        None => Err("Technology detection is not configured".to_string()),
+                    },
+                    "scan" => match &wpscan {
+                        Some(wpscan) => wpscan
+                            .scan_website(&website.url)
+                            .await
+                            .map(|result| BulkOutcome::Scan(Box::new(result))),
+                        None => Err("WPScan is not configured".to_string()),
+                    },
+                    other => Err(format!("Unknown bulk operation: {}", other)),
+                };
+                (website, outcome)
+            });
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let (website, outcome) = joined.map_err(|e| format!("Bulk task failed: {}", e))?;
+
+        let error = match outcome {
+            Ok(outcome) => {
+                let saved = match outcome {
+                    BulkOutcome::Status(confirmation, certificate) => {
+                        let now = chrono::Utc::now();
+                        let status = confirmation.check.status;
+                        app.state::<AgentService>().note_confirmation(&confirmation);
+                        record_check(app, &website, confirmation.check);
+                        let expiring = certificate
+                            .and_then(|expires_at| certificate_event(&website, expires_at, now));
+                        send_alerts(
+                            app,
+                            alert_rules.route(&website, expiring.into_iter().collect()),
+                        );
+
+                        storage
+                            .modify_website(website.id, |w| {
+                                w.status = Some(status);
+                                w.last_checked = Some(now.to_rfc3339());
+                                if let Some(expires_at) = certificate {
+                                    w.certificate_expires_at = Some(expires_at.to_rfc3339());
+                                    w.certificate_checked_at = Some(now.to_rfc3339());
+                                }
+                            })
+                            .map_err(|e| e.to_string())
+                    }
+                    BulkOutcome::Page(analysis) => store_analysis(&storage, website.id, &analysis),
+                    BulkOutcome::Scan(mut result) => {
+                        let saved = store_scan(app, &storage, &scans, website.id, &mut result);
+                        let _ = window.emit("scan-result", result.as_ref());
+                        saved
+                    }
+                };
+
+                match saved {
+                    Ok(Some(updated)) => {
+                        let _ = window.emit("website-updated", &updated);
+                        None
+                    }
+                    Ok(None) => None,
+                    Err(e) => Some(format!("Failed to save {}: {}", website.name, e)),
+                }
+            }
+            Err(e) => Some(format!("{} failed for {}: {}", job.kind, website.name, e)),
+        };
+
+        let checkpoint = jobs.record_progress(&job.id, website.id, error)?;
+        window
+            .emit("job-progress", &checkpoint)
+            .map_err(|e| format!("Failed to emit progress: {}", e))?;
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn save_to_cloud(website: Website, provider: String) -> Result<(), String> {
     println!("Saving to {}: {:?}", provider, website);
@@ -220,13 +614,11 @@ pub async fn update_website_industry(
     industry: String,
     storage: State<'_, StorageService>,
 ) -> Result<(), String> {
-    let mut websites = storage.get_websites().map_err(|e| e.to_string())?;
+    let updated = storage
+        .modify_website(id, |website| website.industry = industry.clone())
+        .map_err(|e| e.to_string())?;
 
-    if let Some(website) = websites.iter_mut().find(|w| w.id == id) {
-        website.industry = industry.clone();
-        storage
-            .save_websites(&websites)
-            .map_err(|e| e.to_string())?;
+    if updated.is_some() {
         println!("Updated industry for website {} to {}", id, industry);
         Ok(())
     } else {
@@ -240,17 +632,13 @@ pub async fn update_website_project_status(
     project_status: String,
     storage: State<'_, StorageService>,
 ) -> Result<(), String> {
-    let mut websites = storage.get_websites().map_err(|e| e.to_string())?;
-
-    if let Some(website) = websites.iter_mut().find(|w| w.id == id) {
-        // Actually update the project_status field
-        website.project_status = Some(project_status.clone());
-
-        // Save the updated websites
-        storage
-            .save_websites(&websites)
-            .map_err(|e| e.to_string())?;
+    let updated = storage
+        .modify_website(id, |website| {
+            website.project_status = Some(project_status.clone())
+        })
+        .map_err(|e| e.to_string())?;
 
+    if updated.is_some() {
         println!(
             "Successfully updated project status for website {} to: {}",
             id, project_status
@@ -266,141 +654,7 @@ pub async fn import_websites(
     storage: State<'_, StorageService>,
     merge: bool,
 ) -> Result<ImportResult, String> {
-    println!("Importing websites, merge mode: {}", merge);
-
-    // Try to parse as full backup first
-    let backup_parsed: Result<FullBackupExport, _> = serde_json::from_str(&json_data);
-
-    if let Ok(backup) = backup_parsed {
-        println!(
-            "Detected full backup file with {} websites",
-            backup.websites.len()
-        );
-        return import_full_backup(backup, storage, merge).await;
-    }
-
-    // Fall back to websites-only import
-    let imported_websites: Vec<Website> = match serde_json::from_str(&json_data) {
-        Ok(websites) => websites,
-        Err(e) => return Err(format!("Failed to parse JSON: {}", e)),
-    };
-
-    import_websites_only(imported_websites, storage, merge).await
-}
-
-async fn import_full_backup(
-    backup: FullBackupExport,
-    storage: State<'_, StorageService>,
-    merge: bool,
-) -> Result<ImportResult, String> {
-    let imported_websites = import_websites_only(backup.websites, storage, merge).await?;
-
-    Ok(ImportResult {
-        websites: imported_websites.websites,
-        custom_statuses: backup.custom_statuses,
-        imported_count: imported_websites.imported_count,
-        skipped_count: imported_websites.skipped_count,
-    })
-}
-
-async fn import_websites_only(
-    imported_websites: Vec<Website>,
-    storage: State<'_, StorageService>,
-    merge: bool,
-) -> Result<ImportResult, String> {
-    // Validate the imported websites
-    if imported_websites.is_empty() {
-        return Err("No websites found in import file".to_string());
-    }
-
-    // Ensure all websites have proper defaults
-    let imported_websites: Vec<Website> = imported_websites
-        .into_iter()
-        .map(|mut website| {
-            if website.vitals.is_none() {
-                website.vitals = Some(WebVitals::default());
-            }
-
-            if website.project_status.is_none() {
-                website.project_status = Some("wip".to_string());
-            }
-
-            if website.notes.is_none() {
-                website.notes = Some(crate::models::website::WebsiteNotes::default());
-            }
-
-            if website.industry.is_empty() {
-                website.industry = "general".to_string();
-            }
-
-            website
-        })
-        .collect();
-
-    let mut imported_count = 0;
-    let mut skipped_count = 0;
-
-    if merge {
-        println!("Merging with existing websites...");
-        let mut existing_websites = storage.get_websites().map_err(|e| e.to_string())?;
-        let existing_urls: std::collections::HashSet<String> =
-            existing_websites.iter().map(|w| w.url.clone()).collect();
-
-        let max_id = existing_websites.iter().map(|w| w.id).max().unwrap_or(0);
-        let mut next_id = max_id + 1;
-
-        for mut website in imported_websites {
-            if existing_urls.contains(&website.url) {
-                println!("Skipping duplicate URL: {}", website.url);
-                skipped_count += 1;
-                continue;
-            }
-
-            website.id = next_id;
-            next_id += 1;
-            existing_websites.push(website);
-            imported_count += 1;
-        }
-
-        storage
-            .save_websites(&existing_websites)
-            .map_err(|e| e.to_string())?;
-        Ok(ImportResult {
-            websites: existing_websites,
-            custom_statuses: vec![],
-            imported_count,
-            skipped_count,
-        })
-    } else {
-        println!("Replacing all websites with imported data...");
-        let imported_websites: Vec<Website> = imported_websites
-            .into_iter()
-            .enumerate()
-            .map(|(index, mut website)| {
-                website.id = (index + 1) as i64;
-                website
-            })
-            .collect();
-
-        imported_count = imported_websites.len();
-        storage
-            .save_websites(&imported_websites)
-            .map_err(|e| e.to_string())?;
-        Ok(ImportResult {
-            websites: imported_websites,
-            custom_statuses: vec![],
-            imported_count,
-            skipped_count,
-        })
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct ImportResult {
-    pub websites: Vec<Website>,
-    pub custom_statuses: Vec<CustomStatus>,
-    pub imported_count: usize,
-    pub skipped_count: usize,
+    import_data(&json_data, &storage, merge).await
 }
 
 #[tauri::command]
@@ -1,14 +1,166 @@
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
-}
+// src-tauri/src/lib.rs
+// The desktop app, shared with the headless binaries in src/bin
+#[cfg(feature = "desktop")]
+pub mod controllers;
+pub mod models;
+pub mod services;
+
+// Everything below is the Tauri app, left out of headless builds
+#[cfg(feature = "desktop")]
+use crate::{
+    controllers::api_controller::ApiServer,
+    controllers::search_controller::{
+        get_search_stats, get_search_suggestions, quick_search, search_websites,
+    },
+    services::{
+        agent_service::AgentService, alert_rule_service::AlertRuleService,
+        alert_service::AlertService, check_history_service::CheckHistoryService,
+        job_service::JobService, maintenance_service::MaintenanceService,
+        metrics_service::MetricsService, report_schedule_service::ReportScheduleService,
+        report_service::ReportService, scan_storage_service::ScanStorageService,
+        screenshot_history_service::ScreenshotHistoryService, storage_service::StorageService,
+        technology_service::TechnologyService,
+    },
+};
+#[cfg(feature = "desktop")]
+use tauri::Manager;
 
+#[cfg(feature = "desktop")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            let storage = StorageService::new("../websites.json".to_string());
+            app.manage(storage);
+            app.manage(ScreenshotHistoryService::new("../screenshots".to_string()));
+            app.manage(JobService::new("../jobs.json".to_string()));
+            app.manage(ScanStorageService::new("../scans".to_string()));
+            app.manage(AlertService::new("../alerts.json".to_string()));
+            app.manage(CheckHistoryService::new("../checks".to_string()));
+            app.manage(MaintenanceService::new("../maintenance.json".to_string()));
+            app.manage(AgentService::new("../agents.json".to_string()));
+            app.manage(ApiServer::new("../api.json".to_string()));
+            app.manage(MetricsService::new());
+            app.manage(TechnologyService::new());
+            app.manage(ReportService::new(
+                "../reports.json".to_string(),
+                "../report_templates".to_string(),
+            ));
+            app.manage(ReportScheduleService::new(
+                "../report_schedules.json".to_string(),
+                "../report_deliveries.json".to_string(),
+            ));
+            app.manage(AlertRuleService::new(
+                "../alert_rules.json".to_string(),
+                "../alert_state.json".to_string(),
+            ));
+            controllers::alert_controller::start_alert_timers(app.handle().clone());
+            controllers::report_controller::start_report_timer(app.handle().clone());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = controllers::api_controller::start_api_server(handle).await {
+                    eprintln!("Local API could not start: {}", e);
+                }
+            });
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            // Website Controllers
+            controllers::website_controller::get_websites,
+            controllers::website_controller::save_websites,
+            controllers::website_controller::check_website_status,
+            controllers::website_controller::get_web_vitals,
+            controllers::website_controller::export_websites,
+            controllers::website_controller::import_websites,
+            controllers::website_controller::validate_import_data,
+            controllers::website_controller::save_to_cloud,
+            controllers::website_controller::scan_website,
+            controllers::website_controller::detect_wordpress,
+            controllers::website_controller::check_websites_status,
+            controllers::website_controller::detect_wordpress_bulk,
+            controllers::website_controller::scan_websites,
+            controllers::website_controller::detect_technologies,
+            controllers::website_controller::detect_technologies_bulk,
+            controllers::website_controller::get_scan_history,
+            controllers::website_controller::get_latest_scan,
+            controllers::website_controller::get_scan_report,
+            controllers::website_controller::update_website_industry,
+            controllers::website_controller::update_website_project_status,
+            // Screenshot Controllers
+            controllers::screenshot_controller::take_screenshot,
+            controllers::screenshot_controller::take_bulk_screenshots,
+            controllers::screenshot_controller::cancel_bulk_screenshots,
+            controllers::screenshot_controller::get_screenshot_history,
+            controllers::screenshot_controller::get_screenshot_diff,
+            // Job Controllers
+            controllers::job_controller::list_jobs,
+            controllers::job_controller::get_job,
+            controllers::job_controller::cancel_job,
+            controllers::job_controller::resume_job,
+            // Alert Controllers
+            controllers::alert_controller::get_alert_channels,
+            controllers::alert_controller::save_alert_channels,
+            controllers::alert_controller::test_alert_channel,
+            controllers::alert_controller::get_alert_rules,
+            controllers::alert_controller::save_alert_rules,
+            controllers::alert_controller::get_alert_states,
+            // Maintenance Controllers
+            controllers::maintenance_controller::get_maintenance_windows,
+            controllers::maintenance_controller::save_maintenance_windows,
+            controllers::maintenance_controller::get_uptime,
+            // Agent Controllers
+            controllers::agent_controller::get_remote_agents,
+            controllers::agent_controller::save_remote_agents,
+            controllers::agent_controller::test_remote_agent,
+            controllers::agent_controller::get_agent_failures,
+            // API Controllers
+            controllers::api_controller::get_api_settings,
+            controllers::api_controller::save_api_settings,
+            // Status Page Controllers
+            controllers::status_page_controller::generate_status_page,
+            // Report Controllers
+            controllers::report_controller::get_report_settings,
+            controllers::report_controller::save_report_settings,
+            controllers::report_controller::list_report_templates,
+            controllers::report_controller::get_report_template,
+            controllers::report_controller::save_report_template,
+            controllers::report_controller::generate_report,
+            controllers::report_controller::get_report_schedules,
+            controllers::report_controller::save_report_schedules,
+            controllers::report_controller::get_report_deliveries,
+            controllers::report_controller::run_report_schedule,
+            // Cloud Controllers
+            controllers::cloud_controller::start_google_drive_auth,
+            controllers::cloud_controller::complete_google_drive_auth,
+            controllers::cloud_controller::backup_to_google_drive,
+            controllers::cloud_controller::is_google_drive_authenticated,
+            controllers::cloud_controller::disconnect_google_drive,
+            controllers::cloud_controller::backup_local,
+            controllers::cloud_controller::open_backup_folder,
+            // NEW Cloud Backup Commands
+            controllers::cloud_controller::list_cloud_backups,
+            controllers::cloud_controller::restore_from_backup,
+            controllers::cloud_controller::delete_backup,
+            controllers::cloud_controller::get_backup_stats,
+            // Settings Controllers
+            controllers::settings_controller::save_settings,
+            controllers::settings_controller::load_settings,
+            controllers::settings_controller::get_api_key,
+            controllers::settings_controller::delete_all_settings,
+            controllers::settings_controller::export_settings_unencrypted,
+            // System Controllers
+            controllers::system_controller::open_containing_folder,
+            controllers::system_controller::open_downloads_folder,
+            controllers::system_controller::get_downloads_path,
+            //
+            // Search Controllers
+            search_websites,
+            quick_search,
+            get_search_suggestions,
+            get_search_stats,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+// Bumped when requests or responses change incompatibly
+pub const AGENT_PROTOCOL_VERSION: u32 = 1;
+
+/// A remote check agent the app asks for a second opinion before raising an incident
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteAgent {
+    pub id: String,
+    pub name: String,
+    pub url: String, // Base URL, e.g. "http://203.0.113.7:7171"
+    pub token: String,
+    pub enabled: bool,
+}
+
+/// The last time a remote agent could not be asked to confirm a failure
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgentFailure {
+    pub agent_id: String,
+    pub agent_name: String,
+    pub url: String, // The URL the agent was asked about
+    pub error: String,
+    pub failed_at: String,
+}
+
+/// `GET /v1/info`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgentInfo {
+    pub name: String,
+    pub region: Option<String>,
+    pub version: String,
+    pub protocol: u32,
+}
+
+/// `POST /v1/check`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AgentCheckRequest {
+    pub urls: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgentCheckResponse {
+    pub agent: AgentInfo,
+    pub results: Vec<AgentCheck>,
+}
+
+/// One URL as seen from the agent
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgentCheck {
+    pub url: String,
+    pub checked_at: String,
+    pub status: u16,                   // 0 when the site did not answer
+    pub response_time_ms: Option<u64>, // None when the site did not answer
+}
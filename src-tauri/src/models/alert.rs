@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// Something worth telling the user about a website
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub kind: String, // "downtime", "recovery", "certificate_expiry", "vulnerability", "test"
+    pub website_id: i64,
+    pub website_name: String,
+    pub url: String,
+    pub title: String,
+    pub message: String,
+    pub severity: String, // "critical", "warning", "info"
+    pub occurred_at: String,
+}
+
+/// Where alerts are delivered. Only the settings of its `channel_type` are used.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertChannel {
+    pub id: String,
+    pub name: String,
+    pub channel_type: String, // "desktop", "email", "webhook", "slack", "discord"
+    pub enabled: bool,
+    #[serde(default)]
+    pub triggers: Vec<String>, // Event kinds to deliver; empty means all
+    #[serde(default)]
+    pub webhook_url: Option<String>, // "webhook", "slack" and "discord"
+    #[serde(default)]
+    pub smtp: Option<SmtpSettings>, // "email"
+}
+
+impl AlertChannel {
+    pub fn accepts(&self, event: &AlertEvent) -> bool {
+        self.enabled
+            && (event.kind == "test"
+                || self.triggers.is_empty()
+                || self.triggers.contains(&event.kind))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub security: String, // "tls" (implicit, usually 465), "starttls" (587), "none"
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// Outcome of sending one event to one channel
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertDelivery {
+    pub channel_id: String,
+    pub channel_type: String,
+    pub event_kind: String,
+    pub website_id: i64,
+    pub success: bool,
+    pub error: Option<String>,
+    pub sent_at: String,
+}
+
+/// When and where alerts about a website go. The most specific enabled rule wins:
+/// website, then tag, then industry, then "all".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub scope: String,          // "all", "website", "tag", "industry"
+    pub target: Option<String>, // Website id, tag or industry, depending on the scope
+    pub failure_threshold: u32, // Consecutive failed checks before a downtime alert
+    pub latency_threshold_ms: Option<u64>,
+    pub latency_minutes: u32, // How long latency must stay above the threshold
+    pub renotify_minutes: Option<u32>, // Repeat downtime alerts while the site stays down
+    pub escalation_minutes: Option<u32>, // Downtime after which escalation channels are alerted
+    pub escalation_channel_ids: Vec<String>,
+    pub channel_ids: Vec<String>, // Empty means every channel subscribed to the event kind
+    pub suppress_in_maintenance: bool,
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            enabled: true,
+            scope: "all".to_string(),
+            target: None,
+            failure_threshold: 1,
+            latency_threshold_ms: None,
+            latency_minutes: 0,
+            renotify_minutes: None,
+            escalation_minutes: None,
+            escalation_channel_ids: Vec::new(),
+            channel_ids: Vec::new(),
+            suppress_in_maintenance: true,
+        }
+    }
+}
+
+/// Where a website stands with respect to its alert rule, kept between checks
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AlertState {
+    pub website_id: i64,
+    pub consecutive_failures: u32,
+    pub last_status: Option<u16>,
+    pub down_since: Option<String>,
+    pub down_alerted: bool,
+    pub last_notified_at: Option<String>,
+    pub escalated: bool,
+    pub slow_since: Option<String>,
+    pub latency_alerted: bool,
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// The optional local REST API and Prometheus `/metrics` endpoint. Both are served on
+/// the same port and need the same token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub metrics_enabled: bool,
+    pub host: String, // 127.0.0.1 by default; 0.0.0.0 lets a remote Prometheus scrape
+    pub port: u16,
+    pub token: String, // Sent as `Authorization: Bearer <token>`; generated when left empty
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            metrics_enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 7170,
+            token: String::new(),
+        }
+    }
+}
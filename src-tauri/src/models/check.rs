@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// One availability check of a website
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CheckResult {
+    pub website_id: i64,
+    pub checked_at: String,
+    pub status: u16,                   // 0 when the site did not answer
+    pub response_time_ms: Option<u64>, // None when the site did not answer
+    #[serde(default)]
+    pub maintenance: bool, // Made during a maintenance window, so left out of uptime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vantage: Option<String>, // Remote agent that made the check; None for this computer
+    #[serde(default)]
+    pub unconfirmed: bool, // A failure no remote agent answered about, counted as seen from here
+}
+
+impl CheckResult {
+    /// Online means HTTP 200, as on the dashboard
+    pub fn is_up(&self) -> bool {
+        self.status == 200
+    }
+}
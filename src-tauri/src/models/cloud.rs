@@ -5,5 +5,15 @@ pub struct CloudBackupResult {
     pub success: bool,
     pub message: String,
     pub backup_path: Option<String>,
+    pub drive_url: Option<String>,
     pub timestamp: String,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub filename: String,
+    pub path: String,
+    pub timestamp: String,
+    pub size: u64,
+    pub is_local: bool,
+}
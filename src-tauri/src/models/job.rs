@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub id: String,
+    pub kind: String,   // "screenshot", "scan", "crawl", ...
+    pub status: String, // "running", "completed", "cancelled", "failed", "interrupted"
+    pub total: usize,
+    pub completed: usize,
+    pub pending_ids: Vec<i64>, // Checkpoint: websites not processed yet
+    pub errors: Vec<String>,
+    pub params: serde_json::Value, // Job-specific options, replayed on resume
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Job {
+    pub fn is_active(&self) -> bool {
+        self.status == "running"
+    }
+
+    pub fn is_resumable(&self) -> bool {
+        matches!(self.status.as_str(), "interrupted" | "cancelled" | "failed")
+            && !self.pending_ids.is_empty()
+    }
+}
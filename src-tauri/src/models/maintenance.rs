@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// A period during which a website is expected to be down. Checks keep running, but
+/// incidents stay silent and the checks do not count towards uptime.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MaintenanceWindow {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub scope: String,             // "website" or "tag"
+    pub target: Option<String>,    // Website id or tag, depending on the scope
+    pub starts_at: Option<String>, // One-off windows: RFC 3339 start
+    pub ends_at: Option<String>,   // One-off windows: RFC 3339 end
+    pub cron: Option<String>, // Recurring windows: "minute hour day-of-month month day-of-week" in local time
+    pub duration_minutes: u32, // Recurring windows: how long each occurrence lasts
+}
+
+impl Default for MaintenanceWindow {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            enabled: true,
+            scope: "website".to_string(),
+            target: None,
+            starts_at: None,
+            ends_at: None,
+            cron: None,
+            duration_minutes: 60,
+        }
+    }
+}
+
+/// Uptime of a website over a period, leaving out checks made during maintenance
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UptimeSummary {
+    pub website_id: i64,
+    pub days: u32,
+    pub checks: usize, // Checks that count towards uptime
+    pub up_checks: usize,
+    pub maintenance_checks: usize, // Checks left out because of maintenance
+    pub uptime_percent: Option<f64>, // None without any counted check
+}
@@ -1,4 +1,14 @@
 pub mod web_vitals;
 pub mod website;
 pub mod wpscan;
-pub mod cloud;
\ No newline at end of file
+pub mod cloud;
+pub mod screenshot;
+pub mod job;
+pub mod technology;
+pub mod alert;
+pub mod check;
+pub mod maintenance;
+pub mod agent;
+pub mod api;
+pub mod status_page;
+pub mod report;
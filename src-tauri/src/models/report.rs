@@ -0,0 +1,119 @@
+use crate::models::alert::SmtpSettings;
+use serde::{Deserialize, Serialize};
+
+/// Branding and defaults shared by every generated report
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ReportSettings {
+    pub company_name: String,
+    pub logo_path: Option<String>, // PNG, JPEG or SVG, embedded into each report
+    pub accent_color: String,      // #rrggbb
+    pub footer: String,
+    pub contact_email: String,
+    pub output_dir: String,
+    pub period_days: u32,
+}
+
+impl Default for ReportSettings {
+    fn default() -> Self {
+        Self {
+            company_name: String::new(),
+            logo_path: None,
+            accent_color: "#2563eb".to_string(),
+            footer: String::new(),
+            contact_email: String::new(),
+            output_dir: "../reports".to_string(),
+            period_days: 30,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ReportRequest {
+    pub scope: String,  // 'website' or 'tag'; a tag stands for a client group
+    pub target: String, // Website id or tag
+    pub template: String,
+    pub formats: Vec<String>,     // 'html' and/or 'pdf'
+    pub period_days: Option<u32>, // From the settings when None
+    pub output_dir: Option<String>,
+}
+
+impl Default for ReportRequest {
+    fn default() -> Self {
+        Self {
+            scope: "website".to_string(),
+            target: String::new(),
+            template: "default".to_string(),
+            formats: vec!["html".to_string(), "pdf".to_string()],
+            period_days: None,
+            output_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GeneratedReport {
+    pub title: String,
+    pub generated_at: String,
+    pub period_days: u32,
+    pub website_ids: Vec<i64>,
+    pub files: Vec<String>,
+}
+
+/// A report generated for a website or client group every week or month and mailed
+/// or dropped into a folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReportSchedule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub scope: String,  // 'website' or 'tag', as in ReportRequest
+    pub target: String, // Website id or tag
+    pub template: String,
+    pub formats: Vec<String>,
+    pub frequency: String,          // 'weekly' or 'monthly'
+    pub day: u32,                   // Day of the week (0 is Sunday) or day of the month (1-28)
+    pub hour: u32,                  // Local time
+    pub delivery: String,           // 'email' or 'folder'
+    pub smtp: Option<SmtpSettings>, // 'email'; same settings as e-mail alert channels
+    pub folder: Option<String>,     // 'folder'
+    pub created_at: Option<String>, // Set when first saved; nothing is due before it
+}
+
+impl Default for ReportSchedule {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            enabled: true,
+            scope: "tag".to_string(),
+            target: String::new(),
+            template: "default".to_string(),
+            formats: vec!["pdf".to_string()],
+            frequency: "monthly".to_string(),
+            day: 1,
+            hour: 8,
+            delivery: "email".to_string(),
+            smtp: None,
+            folder: None,
+            created_at: None,
+        }
+    }
+}
+
+/// Outcome of one scheduled (or manually triggered) report run
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportDelivery {
+    pub schedule_id: String,
+    pub schedule_name: String,
+    pub title: Option<String>, // None when the report could not be generated
+    pub delivered_at: String,
+    pub period_days: u32,
+    pub delivery: String,
+    pub destination: String, // Recipients or folder
+    pub files: Vec<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
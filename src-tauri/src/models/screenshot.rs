@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenshotCapture {
+    pub id: String,
+    pub website_id: i64,
+    pub captured_at: String,
+    pub file_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub diff: Option<ScreenshotDiff>, // Diff against the previous capture, if any
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenshotDiff {
+    pub previous_id: String,
+    pub changed_ratio: f64, // Fraction of pixels that changed (0.0 - 1.0)
+    pub perceptual_distance: u32, // Hamming distance between dHashes (0 - 64)
+    pub significant: bool,  // True when the change exceeds the threshold
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScreenshotOptions {
+    pub full_page: bool, // Scroll-capture the whole document, not just the viewport
+    pub selector: Option<String>, // Capture only this element's bounding box
+    pub hide_selectors: Vec<String>, // Hidden before capture (cookie banners, chat widgets)
+    pub format: String,  // "png", "jpeg", "webp"
+    pub quality: Option<u8>, // 0-100, ignored for PNG
+    pub settle_timeout_ms: u64, // Max wait for load + network idle before capturing
+}
+
+impl Default for ScreenshotOptions {
+    fn default() -> Self {
+        Self {
+            full_page: false,
+            selector: None,
+            hide_selectors: Vec::new(),
+            format: "png".to_string(),
+            quality: None,
+            settle_timeout_ms: 10_000,
+        }
+    }
+}
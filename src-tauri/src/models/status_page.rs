@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct StatusPageOptions {
+    pub title: String,
+    pub output_dir: String, // Gets index.html and status.json
+    pub group_by: String,   // 'tag', 'industry' or 'none'
+    pub days: u32,          // Length of the uptime bars, at most 90
+}
+
+impl Default for StatusPageOptions {
+    fn default() -> Self {
+        Self {
+            title: "Service status".to_string(),
+            output_dir: String::new(),
+            group_by: "none".to_string(),
+            days: 90,
+        }
+    }
+}
+
+/// Everything on a status page; also written as its JSON feed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusPage {
+    pub title: String,
+    pub generated_at: String,
+    pub days: u32,
+    pub overall_status: String, // 'operational', 'partial_outage', 'major_outage' or 'unknown'
+    pub summary: StatusSummary,
+    pub groups: Vec<StatusGroup>,
+    pub incidents: Vec<Incident>, // Most recent first
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusSummary {
+    pub total_websites: usize,
+    pub online_count: usize,
+    pub offline_count: usize,
+    pub unknown_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusGroup {
+    pub name: String,
+    pub websites: Vec<StatusPageWebsite>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusPageWebsite {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub status: String, // 'up', 'down', 'maintenance' or 'unknown'
+    pub last_checked: Option<String>,
+    pub uptime_percent: Option<f64>, // Over the whole page period, without maintenance
+    pub uptime_days: Vec<UptimeDay>, // Oldest first, one per UTC day
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UptimeDay {
+    pub date: String, // YYYY-MM-DD
+    pub checks: usize,
+    pub up_checks: usize,
+    pub maintenance_checks: usize,
+    pub uptime_percent: Option<f64>, // None without checks outside maintenance
+}
+
+/// A run of failed checks outside maintenance
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Incident {
+    pub website_id: i64,
+    pub website_name: String,
+    pub started_at: String,
+    pub resolved_at: Option<String>, // First successful check afterwards; None while ongoing
+    pub duration_minutes: Option<i64>,
+    pub status: u16, // Status of the last failed check, 0 when the site did not answer
+}
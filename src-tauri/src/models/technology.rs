@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Technology {
+    pub name: String,
+    pub category: String, // "cms", "framework", "javascript", "analytics", "cdn", "server", "language"
+    pub version: Option<String>,
+}
@@ -1,3 +1,4 @@
+use crate::models::technology::Technology;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -56,6 +57,9 @@ pub struct SecurityVulnerability {
     pub description: String,
     pub status: String, // 'open', 'fixed', 'in-progress'
     pub discovered: String,
+    // Set for entries maintained by WPScan; manual entries leave it empty
+    #[serde(rename = "scanId", default, skip_serializing_if = "Option::is_none")]
+    pub scan_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -74,13 +78,13 @@ pub struct SecurityNotes {
     pub security_scan_results: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct WebsiteReport {
     pub summary: String,
     pub performance: String,
     pub security: String,
     pub recommendations: String,
-    pub generated_date: String,
+    pub generated_date: String, // Empty until a report has been generated
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -117,6 +121,23 @@ pub struct Website {
     pub description: Option<String>,
     pub notes: Option<WebsiteNotes>, // Use the WebsiteNotes struct defined above
     pub tags: Option<Vec<String>>,
+    // Detected by TechnologyService: CMS, frameworks, analytics, CDN, server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub technologies: Option<Vec<Technology>>,
+    // Expiry of the TLS certificate, refreshed by status checks
+    #[serde(
+        rename = "certificateExpiresAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub certificate_expires_at: Option<String>,
+    // When the certificate was last read, which only status checks do
+    #[serde(
+        rename = "certificateCheckedAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub certificate_checked_at: Option<String>,
 }
 
 // Implement Default for Website and the notes structs if needed
@@ -137,6 +158,9 @@ impl Default for Website {
             description: None,
             notes: Some(WebsiteNotes::default()),
             tags: None,
+            technologies: None,
+            certificate_expires_at: None,
+            certificate_checked_at: None,
         }
     }
 }
@@ -175,15 +199,3 @@ impl Default for SecurityNotes {
         }
     }
 }
-
-impl Default for WebsiteReport {
-    fn default() -> Self {
-        Self {
-            summary: String::new(),
-            performance: String::new(),
-            security: String::new(),
-            recommendations: String::new(),
-            generated_date: chrono::Utc::now().to_rfc3339(),
-        }
-    }
-}
@@ -11,6 +11,12 @@ pub struct WpscanResult {
     pub users: Vec<User>,
     pub scan_date: String,
     pub is_wordpress: bool,
+    #[serde(default)]
+    pub api_requests_remaining: Option<u32>, // WPScan API quota left after this scan
+    #[serde(default)]
+    pub findings: Vec<Finding>, // Hardening issues that are not tied to a component version
+    #[serde(default)]
+    pub aggressive: bool, // Wordlist plugins were probed too, so only compare with aggressive scans
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -47,3 +53,116 @@ pub struct Vulnerability {
     pub references: Vec<String>,
     pub cve: Option<String>, // Add this field
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Finding {
+    // "xmlrpc_enabled", "uploads_listing", "debug_log_exposed", "config_backup_exposed",
+    // "author_user_enumeration", "rest_user_enumeration", "login_page_exposed"; response
+    // header checks add "missing_hsts", "missing_csp", "clickjacking", "missing_nosniff",
+    // "missing_referrer_policy" and "software_disclosure"
+    pub id: String,
+    pub title: String,
+    pub severity: String, // 'low', 'medium', 'high', 'critical'
+    pub description: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanRecord {
+    pub website_id: i64,
+    pub result: WpscanResult,
+    pub changes: Vec<ScanChange>, // Compared to the previous scan of the same website
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScanChange {
+    // "core_updated", "plugin_added", "plugin_removed", "plugin_updated", "theme_added",
+    // "theme_removed", "theme_updated", "vulnerability_new", "vulnerability_fixed"
+    pub change_type: String,
+    pub component: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub description: String,
+}
+
+pub const DEFAULT_WPSCAN_API_URL: &str = "https://wpscan.com/api/v3";
+// Upper bounds for aggressive enumeration, which runs against client servers
+pub const MAX_PROBE_CONCURRENCY: usize = 16;
+pub const MAX_PROBES_PER_SECOND: u32 = 20;
+
+/// Where vulnerability data comes from. Non-secret, so it is kept with scan jobs for resuming.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WpscanOptions {
+    pub api_base_url: String,
+    // Local JSON dump used instead of the API; no API key or network access to WPScan needed
+    pub offline_db_path: Option<String>,
+    // Probe readme.txt of every plugin in the bundled wordlist. Noisy, needs explicit consent.
+    pub aggressive: bool,
+    pub probe_concurrency: usize, // 1 to MAX_PROBE_CONCURRENCY
+    pub probes_per_second: u32,   // 1 to MAX_PROBES_PER_SECOND
+}
+
+impl Default for WpscanOptions {
+    fn default() -> Self {
+        Self {
+            api_base_url: DEFAULT_WPSCAN_API_URL.to_string(),
+            offline_db_path: None,
+            aggressive: false,
+            probe_concurrency: 4,
+            probes_per_second: 5,
+        }
+    }
+}
+
+/// Latest scans of many websites rolled up into one picture
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortfolioScanReport {
+    pub generated_at: String,
+    pub sites_scanned: usize,
+    pub wordpress_sites: usize,
+    pub sites_without_scan: Vec<AffectedSite>,
+    pub plugins: Vec<PluginUsage>, // Most installed first
+    pub shared_vulnerable_versions: Vec<SharedVulnerableVersion>,
+    pub fix_list: Vec<FixItem>, // Most urgent first
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AffectedSite {
+    pub website_id: i64,
+    pub name: String,
+    pub version: Option<String>, // Installed version of the component in question
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginUsage {
+    pub slug: String,
+    pub name: String,
+    pub installs: Vec<AffectedSite>,
+    pub vulnerable_installs: usize,
+}
+
+/// The same vulnerable component version installed on several websites
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedVulnerableVersion {
+    pub component_type: String, // "wordpress", "plugin", "theme"
+    pub slug: String,
+    pub version: String,
+    pub vulnerabilities: Vec<String>, // Titles
+    pub highest_severity: String,
+    pub sites: Vec<AffectedSite>,
+}
+
+/// One vulnerability to fix, with every website it affects
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FixItem {
+    pub priority: usize, // 1 is the most urgent
+    pub component_type: String,
+    pub slug: String,
+    pub vulnerability_id: String,
+    pub title: String,
+    pub severity: String,
+    pub cve: Option<String>,
+    pub fixed_in: Option<String>,
+    pub sites: Vec<AffectedSite>,
+}
@@ -0,0 +1,521 @@
+// services/agent_service.rs
+use crate::models::agent::{
+    AGENT_PROTOCOL_VERSION, AgentCheck, AgentCheckRequest, AgentCheckResponse, AgentFailure,
+    AgentInfo, RemoteAgent,
+};
+use crate::models::check::CheckResult;
+use crate::services::bearer_token::authorized;
+use crate::services::check_service::probe_website;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use reqwest::Client;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+// Room for the agent's own 10 second check timeout
+const AGENT_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_URLS_PER_REQUEST: usize = 50;
+
+/// The remote agents configured in the app, asked to confirm failures before they count
+#[derive(Debug)]
+pub struct AgentService {
+    file_path: String,
+    agents: Mutex<Vec<RemoteAgent>>,
+    failures: Mutex<HashMap<String, AgentFailure>>, // By agent id, until it answers again
+    client: Client,
+}
+
+impl AgentService {
+    pub fn new(file_path: String) -> Self {
+        let agents = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            file_path,
+            agents: Mutex::new(agents),
+            failures: Mutex::new(HashMap::new()),
+            client: Client::new(),
+        }
+    }
+
+    pub fn get_agents(&self) -> Vec<RemoteAgent> {
+        self.agents.lock().unwrap().clone()
+    }
+
+    pub fn save_agents(&self, agents: Vec<RemoteAgent>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&agents)
+            .map_err(|e| format!("Failed to serialize agents: {}", e))?;
+        fs::write(&self.file_path, json).map_err(|e| format!("Failed to save agents: {}", e))?;
+
+        *self.agents.lock().unwrap() = agents;
+        Ok(())
+    }
+
+    pub fn enabled_agents(&self) -> Vec<RemoteAgent> {
+        self.get_agents()
+            .into_iter()
+            .filter(|agent| agent.enabled)
+            .collect()
+    }
+
+    pub async fn info(&self, agent: &RemoteAgent) -> Result<AgentInfo, String> {
+        agent_info(&self.client, agent).await
+    }
+
+    pub async fn confirm(&self, url: &str, check: CheckResult) -> CheckResult {
+        let confirmation = confirm_check(&self.client, &self.enabled_agents(), url, check).await;
+        self.note_confirmation(&confirmation);
+        confirmation.check
+    }
+
+    /// Remember which agents failed to answer, and forget those that answered again
+    pub fn note_confirmation(&self, confirmation: &Confirmation) {
+        let mut failures = self.failures.lock().unwrap();
+        for agent_id in &confirmation.answered {
+            failures.remove(agent_id);
+        }
+        for failure in &confirmation.failures {
+            failures.insert(failure.agent_id.clone(), failure.clone());
+        }
+    }
+
+    /// Enabled agents whose last confirmation failed
+    pub fn get_failures(&self) -> Vec<AgentFailure> {
+        let enabled = self.enabled_agents();
+        let mut failures: Vec<AgentFailure> = self
+            .failures
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|failure| enabled.iter().any(|agent| agent.id == failure.agent_id))
+            .cloned()
+            .collect();
+        failures.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+        failures
+    }
+}
+
+/// A check after the agents had their say
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirmation {
+    pub check: CheckResult,
+    pub answered: Vec<String>, // Ids of the agents that answered
+    pub failures: Vec<AgentFailure>,
+}
+
+pub async fn agent_info(client: &Client, agent: &RemoteAgent) -> Result<AgentInfo, String> {
+    let response = client
+        .get(agent_endpoint(agent, "info"))
+        .bearer_auth(&agent.token)
+        .timeout(AGENT_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach agent {}: {}", agent.name, e))?;
+    let info: AgentInfo = agent_json(agent, response).await?;
+    check_protocol(agent, &info)?;
+    Ok(info)
+}
+
+pub async fn request_checks(
+    client: &Client,
+    agent: &RemoteAgent,
+    urls: &[String],
+) -> Result<AgentCheckResponse, String> {
+    let response = client
+        .post(agent_endpoint(agent, "check"))
+        .bearer_auth(&agent.token)
+        .timeout(AGENT_TIMEOUT)
+        .json(&AgentCheckRequest {
+            urls: urls.to_vec(),
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach agent {}: {}", agent.name, e))?;
+    let checks: AgentCheckResponse = agent_json(agent, response).await?;
+    check_protocol(agent, &checks.agent)?;
+    Ok(checks)
+}
+
+/// Ask every agent about a URL that failed from here. The failure stands when at least
+/// one agent sees it too. When the agents reach the site, their check replaces ours;
+/// when no agent answers, the failure still counts but is marked unconfirmed, so an
+/// offline agent never hides an outage. Agents that could not be asked are returned
+/// as failures. Successful checks and setups without agents are returned as they are.
+pub async fn confirm_check(
+    client: &Client,
+    agents: &[RemoteAgent],
+    url: &str,
+    check: CheckResult,
+) -> Confirmation {
+    let mut confirmation = Confirmation {
+        check,
+        answered: Vec::new(),
+        failures: Vec::new(),
+    };
+    if confirmation.check.is_up() || agents.is_empty() {
+        return confirmation;
+    }
+
+    let mut running = JoinSet::new();
+    for agent in agents.iter().cloned() {
+        let client = client.clone();
+        let urls = vec![url.to_string()];
+        running.spawn(async move {
+            let response = request_checks(&client, &agent, &urls).await;
+            (agent, response)
+        });
+    }
+
+    let mut answers = Vec::new();
+    while let Some(joined) = running.join_next().await {
+        match joined {
+            Ok((agent, Ok(response))) => {
+                confirmation.answered.push(agent.id);
+                answers.extend(
+                    response
+                        .results
+                        .into_iter()
+                        .next()
+                        .map(|seen| (agent.name, seen)),
+                )
+            }
+            Ok((agent, Err(e))) => {
+                eprintln!("Agent {} could not check {}: {}", agent.name, url, e);
+                confirmation.failures.push(AgentFailure {
+                    agent_id: agent.id,
+                    agent_name: agent.name,
+                    url: url.to_string(),
+                    error: e,
+                    failed_at: chrono::Utc::now().to_rfc3339(),
+                });
+            }
+            Err(e) => eprintln!("Agent check of {} failed: {}", url, e),
+        }
+    }
+    answers.sort_by(|a, b| a.0.cmp(&b.0));
+    confirmation
+        .failures
+        .sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+
+    confirmation.check = settle_check(confirmation.check, answers);
+    if let Some(vantage) = &confirmation.check.vantage {
+        eprintln!(
+            "{} failed from here but answered from agent {}",
+            url, vantage
+        );
+    } else if confirmation.check.unconfirmed {
+        eprintln!(
+            "No agent answered, so the failure of {} counts unconfirmed",
+            url
+        );
+    }
+    confirmation
+}
+
+fn settle_check(check: CheckResult, answers: Vec<(String, AgentCheck)>) -> CheckResult {
+    if answers.iter().any(|(_, seen)| seen.status != 200) {
+        return check;
+    }
+
+    match answers.into_iter().next() {
+        Some((name, seen)) => CheckResult {
+            checked_at: seen.checked_at,
+            status: seen.status,
+            response_time_ms: seen.response_time_ms,
+            vantage: Some(name),
+            ..check
+        },
+        None => CheckResult {
+            unconfirmed: true,
+            ..check
+        },
+    }
+}
+
+fn agent_endpoint(agent: &RemoteAgent, path: &str) -> String {
+    format!("{}/v1/{}", agent.url.trim_end_matches('/'), path)
+}
+
+async fn agent_json<T: serde::de::DeserializeOwned>(
+    agent: &RemoteAgent,
+    response: reqwest::Response,
+) -> Result<T, String> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "Agent {} answered HTTP {}: {}",
+            agent.name,
+            status.as_u16(),
+            body
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse answer of agent {}: {}", agent.name, e))
+}
+
+fn check_protocol(agent: &RemoteAgent, info: &AgentInfo) -> Result<(), String> {
+    if info.protocol != AGENT_PROTOCOL_VERSION {
+        return Err(format!(
+            "Agent {} speaks protocol {}, expected {}",
+            agent.name, info.protocol, AGENT_PROTOCOL_VERSION
+        ));
+    }
+    Ok(())
+}
+
+struct AgentContext {
+    info: AgentInfo,
+    token: String,
+}
+
+type AgentError = (StatusCode, String);
+
+/// The agent side of the protocol. Every request needs `Authorization: Bearer <token>`.
+///
+/// - `GET /v1/info` returns an `AgentInfo`
+/// - `POST /v1/check` takes an `AgentCheckRequest` and returns an `AgentCheckResponse`
+///   with one result per URL, in request order
+pub fn agent_router(info: AgentInfo, token: String) -> Router {
+    Router::new()
+        .route("/v1/info", get(get_info))
+        .route("/v1/check", post(post_check))
+        .with_state(Arc::new(AgentContext { info, token }))
+}
+
+/// Run an agent until the process is stopped
+pub async fn serve_agent(listen: &str, info: AgentInfo, token: String) -> Result<(), String> {
+    let listener = tokio::net::TcpListener::bind(listen)
+        .await
+        .map_err(|e| format!("Failed to listen on {}: {}", listen, e))?;
+    println!("Check agent {} listening on {}", info.name, listen);
+
+    axum::serve(listener, agent_router(info, token))
+        .await
+        .map_err(|e| format!("Agent server stopped: {}", e))
+}
+
+async fn get_info(
+    State(context): State<Arc<AgentContext>>,
+    headers: HeaderMap,
+) -> Result<Json<AgentInfo>, AgentError> {
+    authorize(&context, &headers)?;
+    Ok(Json(context.info.clone()))
+}
+
+async fn post_check(
+    State(context): State<Arc<AgentContext>>,
+    headers: HeaderMap,
+    Json(request): Json<AgentCheckRequest>,
+) -> Result<Json<AgentCheckResponse>, AgentError> {
+    authorize(&context, &headers)?;
+    if request.urls.len() > MAX_URLS_PER_REQUEST {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("At most {} URLs per request", MAX_URLS_PER_REQUEST),
+        ));
+    }
+    if let Some(url) = request
+        .urls
+        .iter()
+        .find(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Not an http(s) URL: {}", url),
+        ));
+    }
+
+    let mut running = JoinSet::new();
+    for (index, url) in request.urls.into_iter().enumerate() {
+        running.spawn(async move {
+            let check = probe_website(&url).await;
+            (index, url, check)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = running.join_next().await {
+        let (index, url, check) =
+            joined.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let check = check.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        results.push((
+            index,
+            AgentCheck {
+                url,
+                checked_at: check.checked_at,
+                status: check.status,
+                response_time_ms: check.response_time_ms,
+            },
+        ));
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    Ok(Json(AgentCheckResponse {
+        agent: context.info.clone(),
+        results: results.into_iter().map(|(_, check)| check).collect(),
+    }))
+}
+
+fn authorize(context: &AgentContext, headers: &HeaderMap) -> Result<(), AgentError> {
+    if !authorized(headers, &context.token) {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid agent token".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_failure() -> CheckResult {
+        CheckResult {
+            website_id: 2,
+            checked_at: "2026-10-18T09:00:00+00:00".to_string(),
+            status: 0,
+            response_time_ms: None,
+            maintenance: false,
+            vantage: None,
+            unconfirmed: false,
+        }
+    }
+
+    fn seen(status: u16) -> AgentCheck {
+        AgentCheck {
+            url: "https://shop.example".to_string(),
+            checked_at: "2026-10-18T09:00:02+00:00".to_string(),
+            status,
+            response_time_ms: (status == 200).then_some(310),
+        }
+    }
+
+    #[test]
+    fn failures_need_a_second_vantage_point() {
+        // Down from here and from one agent: confirmed
+        let confirmed = settle_check(
+            local_failure(),
+            vec![("eu".to_string(), seen(200)), ("us".to_string(), seen(0))],
+        );
+        assert_eq!(confirmed, local_failure());
+
+        // Up from every agent that answered: the agent's check is recorded instead
+        let overruled = settle_check(local_failure(), vec![("eu".to_string(), seen(200))]);
+        assert_eq!(overruled.status, 200);
+        assert_eq!(overruled.response_time_ms, Some(310));
+        assert_eq!(overruled.vantage.as_deref(), Some("eu"));
+        assert_eq!(overruled.website_id, 2);
+
+        // No agent answered: the failure from here still counts
+        let unanswered = settle_check(local_failure(), Vec::new());
+        assert_eq!(unanswered.status, 0);
+        assert!(unanswered.unconfirmed);
+        assert_eq!(unanswered.vantage, None);
+    }
+
+    #[tokio::test]
+    async fn agent_protocol_round_trip() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let info = AgentInfo {
+            name: "eu-west".to_string(),
+            region: Some("Frankfurt".to_string()),
+            version: "test".to_string(),
+            protocol: AGENT_PROTOCOL_VERSION,
+        };
+        let router = agent_router(info.clone(), "secret".to_string());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = Client::new();
+        let mut agent = RemoteAgent {
+            id: "eu".to_string(),
+            name: "eu-west".to_string(),
+            url: format!("{}/", url),
+            token: "wrong".to_string(),
+            enabled: true,
+        };
+        let rejected = agent_info(&client, &agent).await.unwrap_err();
+        assert!(rejected.contains("HTTP 401"), "{}", rejected);
+
+        agent.token = "secret".to_string();
+        assert_eq!(agent_info(&client, &agent).await.unwrap(), info);
+
+        // Nothing listens on port 1, so the agent sees the site as down
+        let unreachable = "http://127.0.0.1:1/".to_string();
+        let response = request_checks(&client, &agent, std::slice::from_ref(&unreachable))
+            .await
+            .unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].url, unreachable);
+        assert_eq!(response.results[0].status, 0);
+
+        let invalid = request_checks(&client, &agent, &["file:///etc/passwd".to_string()])
+            .await
+            .unwrap_err();
+        assert!(invalid.contains("HTTP 400"), "{}", invalid);
+
+        let confirmed = confirm_check(
+            &client,
+            std::slice::from_ref(&agent),
+            &unreachable,
+            local_failure(),
+        )
+        .await;
+        assert_eq!(confirmed.check, local_failure());
+        assert_eq!(confirmed.answered, vec!["eu".to_string()]);
+        assert!(confirmed.failures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unreachable_agents_do_not_hide_failures() {
+        let path = std::env::temp_dir().join(format!(
+            "whb-agents-{}.json",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let service = AgentService::new(path.to_string_lossy().to_string());
+        // Nothing listens on port 1, so the agent cannot be asked
+        let agent = RemoteAgent {
+            id: "eu".to_string(),
+            name: "eu-west".to_string(),
+            url: "http://127.0.0.1:1".to_string(),
+            token: "secret".to_string(),
+            enabled: true,
+        };
+        service.save_agents(vec![agent]).unwrap();
+
+        let check = service
+            .confirm("https://shop.example", local_failure())
+            .await;
+        assert_eq!(check.status, 0);
+        assert!(check.unconfirmed);
+
+        let failures = service.get_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].agent_id, "eu");
+        assert_eq!(failures[0].url, "https://shop.example");
+        assert!(
+            failures[0].error.contains("eu-west"),
+            "{}",
+            failures[0].error
+        );
+
+        // Once the site is up again no agent is asked, and the failure stays visible
+        let mut up = local_failure();
+        up.status = 200;
+        assert_eq!(
+            service.confirm("https://shop.example", up.clone()).await,
+            up
+        );
+        assert_eq!(service.get_failures().len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,211 @@
+// services/alert_rule_service.rs
+use crate::models::alert::{AlertEvent, AlertRule, AlertState};
+use crate::models::check::CheckResult;
+use crate::models::website::Website;
+use crate::services::alert_rules::{RuleAlert, evaluate_check, evaluate_timers, rule_for};
+use crate::services::file_lock::lock_file;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::sync::Mutex;
+
+/// Alert rules and the per-website state they are evaluated against.
+/// Websites without a matching rule alert on the first failure, like the default rule.
+#[derive(Debug)]
+pub struct AlertRuleService {
+    rules_path: String,
+    state_path: String,
+    rules: Mutex<Vec<AlertRule>>,
+    states: Mutex<HashMap<i64, AlertState>>,
+}
+
+impl AlertRuleService {
+    pub fn new(rules_path: String, state_path: String) -> Self {
+        let rules = fs::read_to_string(&rules_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        let states = read_states(&state_path).unwrap_or_default();
+
+        Self {
+            rules_path,
+            state_path,
+            rules: Mutex::new(rules),
+            states: Mutex::new(states),
+        }
+    }
+
+    pub fn get_rules(&self) -> Vec<AlertRule> {
+        self.rules.lock().unwrap().clone()
+    }
+
+    pub fn save_rules(&self, rules: Vec<AlertRule>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&rules)
+            .map_err(|e| format!("Failed to serialize alert rules: {}", e))?;
+        fs::write(&self.rules_path, json)
+            .map_err(|e| format!("Failed to save alert rules: {}", e))?;
+
+        *self.rules.lock().unwrap() = rules;
+        Ok(())
+    }
+
+    pub fn get_states(&self) -> Vec<AlertState> {
+        let mut states: Vec<AlertState> = self.states.lock().unwrap().values().cloned().collect();
+        states.sort_by_key(|s| s.website_id);
+        states
+    }
+
+    /// Feed a check into the website's rule
+    pub fn process_check(
+        &self,
+        website: &Website,
+        check: &CheckResult,
+        now: DateTime<Utc>,
+        in_maintenance: bool,
+    ) -> Result<Vec<RuleAlert>, String> {
+        let rule = self.rule_for(website);
+        let mut states = self.states.lock().unwrap();
+        let _lock = self.reload_states(&mut states)?;
+        let state = states.entry(website.id).or_insert_with(|| AlertState {
+            website_id: website.id,
+            ..Default::default()
+        });
+
+        let alerts = evaluate_check(&rule, state, website, check, now, in_maintenance);
+        self.persist(&states)?;
+        Ok(alerts)
+    }
+
+    /// Reminders, escalations and alerts held back by maintenance for sites that are down.
+    /// `in_maintenance` tells whether a website is currently in a maintenance window.
+    pub fn process_timers(
+        &self,
+        websites: &[Website],
+        now: DateTime<Utc>,
+        in_maintenance: impl Fn(&Website) -> bool,
+    ) -> Result<Vec<RuleAlert>, String> {
+        let mut states = self.states.lock().unwrap();
+        let _lock = self.reload_states(&mut states)?;
+        let mut alerts = Vec::new();
+
+        for website in websites {
+            let Some(state) = states
+                .get_mut(&website.id)
+                .filter(|state| state.consecutive_failures > 0)
+            else {
+                continue;
+            };
+            let rule = self.rule_for(website);
+            alerts.extend(evaluate_timers(
+                &rule,
+                state,
+                website,
+                now,
+                in_maintenance(website),
+            ));
+        }
+
+        if !alerts.is_empty() {
+            self.persist(&states)?;
+        }
+        Ok(alerts)
+    }
+
+    /// Send events that are not rule-driven (certificates, vulnerabilities) to the
+    /// channels of the website's rule
+    pub fn route(&self, website: &Website, events: Vec<AlertEvent>) -> Vec<RuleAlert> {
+        let channel_ids = self.rule_for(website).channel_ids;
+        events
+            .into_iter()
+            .map(|event| RuleAlert {
+                event,
+                channel_ids: channel_ids.clone(),
+            })
+            .collect()
+    }
+
+    fn rule_for(&self, website: &Website) -> AlertRule {
+        rule_for(&self.rules.lock().unwrap(), website)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Lock the state file and take over what another process saved since it was read,
+    /// e.g. whb check runs while the app is open. Persist before dropping the lock.
+    fn reload_states(&self, states: &mut HashMap<i64, AlertState>) -> Result<File, String> {
+        let lock = lock_file(&self.state_path)
+            .map_err(|e| format!("Failed to lock alert state: {}", e))?;
+        if let Some(saved) = read_states(&self.state_path) {
+            *states = saved;
+        }
+        Ok(lock)
+    }
+
+    fn persist(&self, states: &HashMap<i64, AlertState>) -> Result<(), String> {
+        let mut states: Vec<&AlertState> = states.values().collect();
+        states.sort_by_key(|s| s.website_id);
+        let json = serde_json::to_string_pretty(&states)
+            .map_err(|e| format!("Failed to serialize alert state: {}", e))?;
+        fs::write(&self.state_path, json).map_err(|e| format!("Failed to save alert state: {}", e))
+    }
+}
+
+fn read_states(path: &str) -> Option<HashMap<i64, AlertState>> {
+    let states: Vec<AlertState> = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())?;
+    Some(states.into_iter().map(|s| (s.website_id, s)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_sharing_the_state_file_keep_each_others_state() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-alert-state-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let service = || {
+            AlertRuleService::new(
+                dir.join("alert_rules.json").to_string_lossy().to_string(),
+                dir.join("alert_state.json").to_string_lossy().to_string(),
+            )
+        };
+        let now = Utc::now();
+        let website = |id| Website {
+            id,
+            ..Website::default()
+        };
+        let failure = |website_id| CheckResult {
+            website_id,
+            checked_at: now.to_rfc3339(),
+            status: 0,
+            response_time_ms: None,
+            maintenance: false,
+            vantage: None,
+            unconfirmed: false,
+        };
+
+        // The app and a whb check run, both started before either saved anything
+        let app = service();
+        let cli = service();
+        app.process_check(&website(1), &failure(1), now, false)
+            .unwrap();
+        cli.process_check(&website(2), &failure(2), now, false)
+            .unwrap();
+        app.process_check(&website(1), &failure(1), now, false)
+            .unwrap();
+
+        let failures: Vec<(i64, u32)> = service()
+            .get_states()
+            .iter()
+            .map(|s| (s.website_id, s.consecutive_failures))
+            .collect();
+        assert_eq!(failures, vec![(1, 2), (2, 1)]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
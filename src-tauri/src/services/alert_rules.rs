@@ -0,0 +1,487 @@
+// services/alert_rules.rs
+use crate::models::alert::{AlertEvent, AlertRule, AlertState};
+use crate::models::check::CheckResult;
+use crate::models::website::Website;
+use crate::services::alert_service::alert_event;
+use chrono::{DateTime, Utc};
+
+/// An alert and the channels it goes to; no channel ids means the default routing
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleAlert {
+    pub event: AlertEvent,
+    pub channel_ids: Vec<String>,
+}
+
+/// The most specific enabled rule for a website: website, tag, industry, then "all"
+pub fn rule_for<'a>(rules: &'a [AlertRule], website: &Website) -> Option<&'a AlertRule> {
+    let website_id = website.id.to_string();
+    let specificity = |rule: &AlertRule| {
+        let target = rule.target.as_deref().unwrap_or_default();
+        match rule.scope.as_str() {
+            "website" if target == website_id => Some(3),
+            "tag"
+                if website.tags.as_ref().is_some_and(|tags| {
+                    tags.iter().any(|tag| tag.eq_ignore_ascii_case(target))
+                }) =>
+            {
+                Some(2)
+            }
+            "industry" if website.industry.eq_ignore_ascii_case(target) => Some(1),
+            "all" => Some(0),
+            _ => None,
+        }
+    };
+
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| specificity(rule).map(|rank| (rank, rule)))
+        // The first rule wins among equally specific ones
+        .fold(
+            None,
+            |best: Option<(u8, &AlertRule)>, (rank, rule)| match best {
+                Some((best_rank, _)) if best_rank >= rank => best,
+                _ => Some((rank, rule)),
+            },
+        )
+        .map(|(_, rule)| rule)
+}
+
+/// Update a website's state with a new check and return the alerts it triggers.
+/// During maintenance the state keeps counting, but new incidents stay silent; recoveries
+/// of incidents that were already reported are always sent.
+pub fn evaluate_check(
+    rule: &AlertRule,
+    state: &mut AlertState,
+    website: &Website,
+    check: &CheckResult,
+    now: DateTime<Utc>,
+    in_maintenance: bool,
+) -> Vec<RuleAlert> {
+    let mut alerts = Vec::new();
+    let suppressed = in_maintenance && rule.suppress_in_maintenance;
+    let previous_status = state.last_status.replace(check.status);
+
+    if !check.is_up() {
+        state.consecutive_failures += 1;
+        state
+            .down_since
+            .get_or_insert_with(|| check.checked_at.clone());
+        // Slowness of a site that is down is not worth its own alert
+        state.slow_since = None;
+        state.latency_alerted = false;
+        alerts.extend(evaluate_timers(rule, state, website, now, in_maintenance));
+        return alerts;
+    }
+
+    if state.down_alerted {
+        let minutes = elapsed_minutes(state.down_since.as_deref(), now);
+        alerts.push(RuleAlert {
+            event: alert_event(
+                website,
+                "recovery",
+                "info",
+                format!("{} is back up", website.name),
+                format!(
+                    "{} is responding again after {} minute{} (last failure: {}).",
+                    website.url,
+                    minutes,
+                    if minutes == 1 { "" } else { "s" },
+                    describe_status(previous_status.unwrap_or_default())
+                ),
+            ),
+            channel_ids: recipients(rule, state.escalated),
+        });
+    }
+    state.consecutive_failures = 0;
+    state.down_since = None;
+    state.down_alerted = false;
+    state.last_notified_at = None;
+    state.escalated = false;
+
+    let slow = rule
+        .latency_threshold_ms
+        .zip(check.response_time_ms)
+        .is_some_and(|(limit, elapsed)| elapsed > limit);
+    if slow {
+        state
+            .slow_since
+            .get_or_insert_with(|| check.checked_at.clone());
+        let minutes = elapsed_minutes(state.slow_since.as_deref(), now);
+        if !state.latency_alerted && !suppressed && minutes >= i64::from(rule.latency_minutes) {
+            state.latency_alerted = true;
+            alerts.push(RuleAlert {
+                event: alert_event(
+                    website,
+                    "latency",
+                    "warning",
+                    format!("{} is slow", website.name),
+                    format!(
+                        "{} took {} ms to respond, above the {} ms limit for {} minutes.",
+                        website.url,
+                        check.response_time_ms.unwrap_or_default(),
+                        rule.latency_threshold_ms.unwrap_or_default(),
+                        minutes
+                    ),
+                ),
+                channel_ids: rule.channel_ids.clone(),
+            });
+        }
+    } else {
+        if state.latency_alerted {
+            alerts.push(RuleAlert {
+                event: alert_event(
+                    website,
+                    "recovery",
+                    "info",
+                    format!("{} responds quickly again", website.name),
+                    format!(
+                        "{} answered in {} ms.",
+                        website.url,
+                        check.response_time_ms.unwrap_or_default()
+                    ),
+                ),
+                channel_ids: rule.channel_ids.clone(),
+            });
+        }
+        state.slow_since = None;
+        state.latency_alerted = false;
+    }
+
+    alerts
+}
+
+/// Alerts that depend on time rather than a new check: the first downtime alert once
+/// the failure threshold is reached, reminders while down, and escalation.
+pub fn evaluate_timers(
+    rule: &AlertRule,
+    state: &mut AlertState,
+    website: &Website,
+    now: DateTime<Utc>,
+    in_maintenance: bool,
+) -> Vec<RuleAlert> {
+    let mut alerts = Vec::new();
+    if state.consecutive_failures < rule.failure_threshold.max(1)
+        || (in_maintenance && rule.suppress_in_maintenance)
+    {
+        return alerts;
+    }
+
+    let status = describe_status(state.last_status.unwrap_or_default());
+    let down_minutes = elapsed_minutes(state.down_since.as_deref(), now);
+
+    let remind = rule.renotify_minutes.is_some_and(|every| {
+        elapsed_minutes(state.last_notified_at.as_deref(), now) >= i64::from(every.max(1))
+    });
+    if !state.down_alerted || remind {
+        let title = if state.down_alerted {
+            format!(
+                "{} is still down after {} minutes",
+                website.name, down_minutes
+            )
+        } else {
+            format!("{} is down", website.name)
+        };
+        alerts.push(RuleAlert {
+            event: alert_event(
+                website,
+                "downtime",
+                "critical",
+                title,
+                format!(
+                    "{} returned {} ({} failed check{} in a row).",
+                    website.url,
+                    status,
+                    state.consecutive_failures,
+                    if state.consecutive_failures == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                ),
+            ),
+            channel_ids: recipients(rule, state.escalated),
+        });
+        state.down_alerted = true;
+        state.last_notified_at = Some(now.to_rfc3339());
+    }
+
+    let escalate = !state.escalated
+        && !rule.escalation_channel_ids.is_empty()
+        && rule
+            .escalation_minutes
+            .is_some_and(|after| down_minutes >= i64::from(after));
+    if escalate {
+        state.escalated = true;
+        alerts.push(RuleAlert {
+            event: alert_event(
+                website,
+                "downtime",
+                "critical",
+                format!(
+                    "Escalation: {} has been down for {} minutes",
+                    website.name, down_minutes
+                ),
+                format!("{} still returns {}.", website.url, status),
+            ),
+            channel_ids: rule.escalation_channel_ids.clone(),
+        });
+    }
+
+    alerts
+}
+
+/// Once escalated, follow-ups also go to the escalation channels
+fn recipients(rule: &AlertRule, escalated: bool) -> Vec<String> {
+    let mut channel_ids = rule.channel_ids.clone();
+    if escalated && !channel_ids.is_empty() {
+        for id in &rule.escalation_channel_ids {
+            if !channel_ids.contains(id) {
+                channel_ids.push(id.clone());
+            }
+        }
+    }
+    channel_ids
+}
+
+fn describe_status(status: u16) -> String {
+    match status {
+        0 => "no response".to_string(),
+        status => format!("HTTP {}", status),
+    }
+}
+
+fn elapsed_minutes(since: Option<&str>, now: DateTime<Utc>) -> i64 {
+    since
+        .and_then(|since| DateTime::parse_from_rfc3339(since).ok())
+        .map(|since| (now - since.with_timezone(&Utc)).num_minutes())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn website() -> Website {
+        Website {
+            id: 3,
+            name: "Blog".to_string(),
+            url: "https://blog.example".to_string(),
+            industry: "media".to_string(),
+            tags: Some(vec!["client-a".to_string()]),
+            ..Default::default()
+        }
+    }
+
+    fn check(at: DateTime<Utc>, status: u16, response_time_ms: Option<u64>) -> CheckResult {
+        CheckResult {
+            website_id: 3,
+            checked_at: at.to_rfc3339(),
+            status,
+            response_time_ms,
+            maintenance: false,
+            vantage: None,
+            unconfirmed: false,
+        }
+    }
+
+    fn kinds(alerts: &[RuleAlert]) -> Vec<&str> {
+        alerts.iter().map(|a| a.event.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let rule = |id: &str, scope: &str, target: Option<&str>| AlertRule {
+            id: id.to_string(),
+            scope: scope.to_string(),
+            target: target.map(str::to_string),
+            ..Default::default()
+        };
+        let rules = vec![
+            rule("all", "all", None),
+            rule("industry", "industry", Some("Media")),
+            rule("tag", "tag", Some("client-a")),
+            rule("other-site", "website", Some("4")),
+        ];
+
+        assert_eq!(rule_for(&rules, &website()).unwrap().id, "tag");
+        assert_eq!(rule_for(&rules[..2], &website()).unwrap().id, "industry");
+        assert!(rule_for(&rules[3..], &website()).is_none());
+    }
+
+    #[test]
+    fn debounces_renotifies_escalates_and_recovers() {
+        let rule = AlertRule {
+            failure_threshold: 3,
+            renotify_minutes: Some(30),
+            escalation_minutes: Some(60),
+            channel_ids: vec!["slack".to_string()],
+            escalation_channel_ids: vec!["oncall".to_string()],
+            ..Default::default()
+        };
+        let mut state = AlertState::default();
+        let site = website();
+        let start = Utc::now();
+        let at = |minutes: i64| start + Duration::minutes(minutes);
+
+        // A single blip is not reported
+        assert!(
+            evaluate_check(
+                &rule,
+                &mut state,
+                &site,
+                &check(at(0), 0, None),
+                at(0),
+                false
+            )
+            .is_empty()
+        );
+        assert!(
+            evaluate_check(
+                &rule,
+                &mut state,
+                &site,
+                &check(at(1), 200, Some(80)),
+                at(1),
+                false
+            )
+            .is_empty()
+        );
+
+        // Three failures in a row are
+        for minute in 2..4 {
+            assert!(
+                evaluate_check(
+                    &rule,
+                    &mut state,
+                    &site,
+                    &check(at(minute), 503, None),
+                    at(minute),
+                    false
+                )
+                .is_empty()
+            );
+        }
+        let alerts = evaluate_check(
+            &rule,
+            &mut state,
+            &site,
+            &check(at(4), 503, None),
+            at(4),
+            false,
+        );
+        assert_eq!(kinds(&alerts), vec!["downtime"]);
+        assert_eq!(alerts[0].channel_ids, vec!["slack"]);
+
+        // Nothing new until the reminder is due
+        assert!(evaluate_timers(&rule, &mut state, &site, at(20), false).is_empty());
+        let reminder = evaluate_timers(&rule, &mut state, &site, at(35), false);
+        assert!(reminder[0].event.title.contains("still down"));
+
+        // After an hour the escalation channel is alerted once
+        let escalation = evaluate_timers(&rule, &mut state, &site, at(62), false);
+        assert_eq!(escalation.len(), 1);
+        assert_eq!(escalation[0].channel_ids, vec!["oncall"]);
+        assert!(evaluate_timers(&rule, &mut state, &site, at(63), false).is_empty());
+
+        // The recovery reaches everyone who heard about the incident
+        let recovery = evaluate_check(
+            &rule,
+            &mut state,
+            &site,
+            &check(at(70), 200, Some(90)),
+            at(70),
+            false,
+        );
+        assert_eq!(kinds(&recovery), vec!["recovery"]);
+        assert_eq!(recovery[0].channel_ids, vec!["slack", "oncall"]);
+        assert_eq!(state.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn latency_must_stay_high_for_the_configured_time() {
+        let rule = AlertRule {
+            latency_threshold_ms: Some(2000),
+            latency_minutes: 10,
+            ..Default::default()
+        };
+        let mut state = AlertState::default();
+        let site = website();
+        let start = Utc::now();
+        let at = |minutes: i64| start + Duration::minutes(minutes);
+
+        assert!(
+            evaluate_check(
+                &rule,
+                &mut state,
+                &site,
+                &check(at(0), 200, Some(3500)),
+                at(0),
+                false
+            )
+            .is_empty()
+        );
+        assert!(
+            evaluate_check(
+                &rule,
+                &mut state,
+                &site,
+                &check(at(5), 200, Some(3100)),
+                at(5),
+                false
+            )
+            .is_empty()
+        );
+        let slow = evaluate_check(
+            &rule,
+            &mut state,
+            &site,
+            &check(at(10), 200, Some(2900)),
+            at(10),
+            false,
+        );
+        assert_eq!(kinds(&slow), vec!["latency"]);
+        assert!(
+            evaluate_check(
+                &rule,
+                &mut state,
+                &site,
+                &check(at(15), 200, Some(2900)),
+                at(15),
+                false
+            )
+            .is_empty()
+        );
+
+        let fast = evaluate_check(
+            &rule,
+            &mut state,
+            &site,
+            &check(at(20), 200, Some(300)),
+            at(20),
+            false,
+        );
+        assert_eq!(kinds(&fast), vec!["recovery"]);
+    }
+
+    #[test]
+    fn maintenance_holds_alerts_until_it_ends() {
+        let rule = AlertRule::default();
+        let mut state = AlertState::default();
+        let site = website();
+        let now = Utc::now();
+
+        assert!(
+            evaluate_check(&rule, &mut state, &site, &check(now, 0, None), now, true).is_empty()
+        );
+        assert_eq!(state.consecutive_failures, 1);
+
+        // Still down once the window is over: alert on the next tick
+        let later = now + Duration::minutes(1);
+        assert_eq!(
+            kinds(&evaluate_timers(&rule, &mut state, &site, later, false)),
+            vec!["downtime"]
+        );
+    }
+}
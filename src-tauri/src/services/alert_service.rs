@@ -0,0 +1,654 @@
+// services/alert_service.rs
+use crate::models::alert::{AlertChannel, AlertDelivery, AlertEvent, SmtpSettings};
+use crate::models::website::Website;
+use crate::models::wpscan::ScanRecord;
+use chrono::{DateTime, Utc};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use reqwest::Client;
+use serde_json::json;
+use std::fs;
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(feature = "desktop")]
+use tauri_plugin_notification::NotificationExt;
+
+// Days before expiry at which a certificate alert goes out, once per threshold
+const CERTIFICATE_THRESHOLDS: &[i64] = &[14, 7, 3, 1, 0];
+const SEND_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// What desktop notifications are shown through. Headless builds have no app, so
+/// there is no value of it to pass and desktop channels always fail there.
+#[cfg(feature = "desktop")]
+pub type NotificationHandle = tauri::AppHandle;
+#[cfg(not(feature = "desktop"))]
+pub type NotificationHandle = std::convert::Infallible;
+
+/// Keeps the configured notification channels and delivers alert events to them
+#[derive(Debug)]
+pub struct AlertService {
+    file_path: String,
+    channels: Mutex<Vec<AlertChannel>>,
+    client: Client,
+}
+
+impl AlertService {
+    pub fn new(file_path: String) -> Self {
+        let channels = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            file_path,
+            channels: Mutex::new(channels),
+            client: Client::new(),
+        }
+    }
+
+    pub fn get_channels(&self) -> Vec<AlertChannel> {
+        self.channels.lock().unwrap().clone()
+    }
+
+    pub fn save_channels(&self, channels: Vec<AlertChannel>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&channels)
+            .map_err(|e| format!("Failed to serialize alert channels: {}", e))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| format!("Failed to save alert channels: {}", e))?;
+
+        *self.channels.lock().unwrap() = channels;
+        Ok(())
+    }
+
+    /// Send an event to the given enabled channels, or when none are given, to every
+    /// enabled channel that subscribes to its kind. Desktop notifications need the app
+    /// handle and fail without one.
+    pub async fn dispatch(
+        &self,
+        event: &AlertEvent,
+        channel_ids: &[String],
+        app: Option<&NotificationHandle>,
+    ) -> Vec<AlertDelivery> {
+        let channels: Vec<AlertChannel> = self
+            .get_channels()
+            .into_iter()
+            .filter(|channel| {
+                if channel_ids.is_empty() {
+                    channel.accepts(event)
+                } else {
+                    channel.enabled && channel_ids.contains(&channel.id)
+                }
+            })
+            .collect();
+
+        let mut deliveries = Vec::new();
+        for channel in &channels {
+            deliveries.push(self.deliver(channel, event, app).await);
+        }
+        deliveries
+    }
+
+    /// Send an event to one channel regardless of its triggers, e.g. to test it
+    pub async fn deliver(
+        &self,
+        channel: &AlertChannel,
+        event: &AlertEvent,
+        app: Option<&NotificationHandle>,
+    ) -> AlertDelivery {
+        let result = self.send(channel, event, app).await;
+        if let Err(e) = &result {
+            eprintln!(
+                "Failed to send {} alert to {} channel {}: {}",
+                event.kind, channel.channel_type, channel.name, e
+            );
+        }
+
+        AlertDelivery {
+            channel_id: channel.id.clone(),
+            channel_type: channel.channel_type.clone(),
+            event_kind: event.kind.clone(),
+            website_id: event.website_id,
+            success: result.is_ok(),
+            error: result.err(),
+            sent_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    async fn send(
+        &self,
+        channel: &AlertChannel,
+        event: &AlertEvent,
+        app: Option<&NotificationHandle>,
+    ) -> Result<(), String> {
+        match channel.channel_type.as_str() {
+            "desktop" => {
+                let app = app.ok_or("Desktop notifications need the app window")?;
+                show_notification(app, event)
+            }
+            "email" => {
+                let smtp = channel.smtp.as_ref().ok_or("SMTP settings are missing")?;
+                send_email(smtp, event).await
+            }
+            "webhook" => self.post_json(channel, &json!(event)).await,
+            "slack" => self.post_json(channel, &slack_payload(event)).await,
+            "discord" => self.post_json(channel, &discord_payload(event)).await,
+            other => Err(format!("Unknown channel type: {}", other)),
+        }
+    }
+
+    async fn post_json(
+        &self,
+        channel: &AlertChannel,
+        payload: &serde_json::Value,
+    ) -> Result<(), String> {
+        let url = channel
+            .webhook_url
+            .as_deref()
+            .ok_or("Webhook URL is missing")?;
+
+        let response = self
+            .client
+            .post(url)
+            .timeout(SEND_TIMEOUT)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call webhook: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Webhook answered with status {}",
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn show_notification(app: &NotificationHandle, event: &AlertEvent) -> Result<(), String> {
+    app.notification()
+        .builder()
+        .title(&event.title)
+        .body(&event.message)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))
+}
+
+#[cfg(not(feature = "desktop"))]
+fn show_notification(app: &NotificationHandle, _event: &AlertEvent) -> Result<(), String> {
+    match *app {}
+}
+
+async fn send_email(smtp: &SmtpSettings, event: &AlertEvent) -> Result<(), String> {
+    if smtp.to.is_empty() {
+        return Err("No e-mail recipients configured".to_string());
+    }
+
+    let mut builder = Message::builder()
+        .from(
+            smtp.from
+                .parse()
+                .map_err(|e| format!("Invalid sender {}: {}", smtp.from, e))?,
+        )
+        .subject(format!(
+            "[{}] {}",
+            event.severity.to_uppercase(),
+            event.title
+        ));
+    for recipient in &smtp.to {
+        builder = builder.to(recipient
+            .parse()
+            .map_err(|e| format!("Invalid recipient {}: {}", recipient, e))?);
+    }
+    let message = builder
+        .header(ContentType::TEXT_PLAIN)
+        .body(email_body(event))
+        .map_err(|e| format!("Failed to build e-mail: {}", e))?;
+
+    smtp_transport(smtp)?
+        .send(message)
+        .await
+        .map_err(|e| format!("Failed to send e-mail: {}", e))?;
+    Ok(())
+}
+
+/// Transport for the configured SMTP server; also used to mail scheduled reports
+pub fn smtp_transport(smtp: &SmtpSettings) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+    let mut transport = match smtp.security.as_str() {
+        "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host),
+        "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host),
+        _ => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+            &smtp.host,
+        )),
+    }
+    .map_err(|e| format!("Failed to configure SMTP: {}", e))?
+    .port(smtp.port)
+    .timeout(Some(SEND_TIMEOUT));
+    if let Some(username) = &smtp.username {
+        transport = transport.credentials(Credentials::new(
+            username.clone(),
+            smtp.password.clone().unwrap_or_default(),
+        ));
+    }
+    Ok(transport.build())
+}
+
+fn email_body(event: &AlertEvent) -> String {
+    format!(
+        "{}\n\nWebsite: {}\nURL: {}\nTime: {}\n",
+        event.message, event.website_name, event.url, event.occurred_at
+    )
+}
+
+fn severity_color(severity: &str) -> u32 {
+    match severity {
+        "critical" => 0xD92D20,
+        "warning" => 0xF79009,
+        _ => 0x12B76A,
+    }
+}
+
+/// Incoming-webhook message for Slack and Slack-compatible tools (Mattermost, Rocket.Chat)
+fn slack_payload(event: &AlertEvent) -> serde_json::Value {
+    json!({
+        "text": event.title,
+        "attachments": [{
+            "color": format!("#{:06X}", severity_color(&event.severity)),
+            "title": event.website_name,
+            "title_link": event.url,
+            "text": event.message,
+            "footer": event.kind,
+            "ts": DateTime::parse_from_rfc3339(&event.occurred_at)
+                .map(|t| t.timestamp())
+                .unwrap_or_default(),
+        }],
+    })
+}
+
+/// Discord webhook message with one embed
+fn discord_payload(event: &AlertEvent) -> serde_json::Value {
+    json!({
+        "username": "WebHealth Monitor",
+        "embeds": [{
+            "title": event.title,
+            "description": event.message,
+            "url": event.url,
+            "color": severity_color(&event.severity),
+            "timestamp": event.occurred_at,
+            "footer": { "text": event.website_name },
+        }],
+    })
+}
+
+pub fn alert_event(
+    website: &Website,
+    kind: &str,
+    severity: &str,
+    title: String,
+    message: String,
+) -> AlertEvent {
+    AlertEvent {
+        kind: kind.to_string(),
+        website_id: website.id,
+        website_name: website.name.clone(),
+        url: website.url.clone(),
+        title,
+        message,
+        severity: severity.to_string(),
+        occurred_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Alert once each time a certificate crosses one of the expiry thresholds.
+/// The previous position comes from the stored expiry date and the time it was read,
+/// not `last_checked`, which screenshots and API checks move without reading it.
+pub fn certificate_event(
+    website: &Website,
+    expires_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<AlertEvent> {
+    let crossed = |days: i64| {
+        CERTIFICATE_THRESHOLDS
+            .iter()
+            .filter(|threshold| days <= **threshold)
+            .count()
+    };
+
+    let days_left = (expires_at - now).num_days();
+    let previous_days = website
+        .certificate_expires_at
+        .as_deref()
+        .zip(website.certificate_checked_at.as_deref())
+        .and_then(|(expiry, checked)| {
+            let expiry = DateTime::parse_from_rfc3339(expiry).ok()?;
+            let checked = DateTime::parse_from_rfc3339(checked).ok()?;
+            Some((expiry - checked).num_days())
+        });
+
+    if crossed(days_left) <= previous_days.map_or(0, crossed) {
+        return None;
+    }
+
+    let (severity, title) = if expires_at <= now {
+        (
+            "critical",
+            format!("Certificate of {} has expired", website.name),
+        )
+    } else {
+        (
+            if days_left <= 3 {
+                "critical"
+            } else {
+                "warning"
+            },
+            format!(
+                "Certificate of {} expires in {} day{}",
+                website.name,
+                days_left,
+                if days_left == 1 { "" } else { "s" }
+            ),
+        )
+    };
+    Some(alert_event(
+        website,
+        "certificate_expiry",
+        severity,
+        title,
+        format!(
+            "The TLS certificate of {} is valid until {}.",
+            website.url,
+            expires_at.format("%Y-%m-%d %H:%M UTC")
+        ),
+    ))
+}
+
+/// Vulnerabilities a scan found that the previous scan did not
+pub fn vulnerability_event(website: &Website, record: &ScanRecord) -> Option<AlertEvent> {
+    let new: Vec<&str> = record
+        .changes
+        .iter()
+        .filter(|c| c.change_type == "vulnerability_new")
+        .map(|c| c.description.as_str())
+        .collect();
+    if new.is_empty() {
+        return None;
+    }
+
+    Some(alert_event(
+        website,
+        "vulnerability",
+        "warning",
+        format!(
+            "{} new vulnerabilit{} on {}",
+            new.len(),
+            if new.len() == 1 { "y" } else { "ies" },
+            website.name
+        ),
+        new.join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wpscan::{ScanChange, WpscanResult};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    fn website(status: Option<u16>) -> Website {
+        Website {
+            id: 7,
+            name: "Shop".to_string(),
+            url: "https://shop.example".to_string(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    fn downtime(message: &str) -> AlertEvent {
+        alert_event(
+            &website(Some(200)),
+            "downtime",
+            "critical",
+            "Shop is down".to_string(),
+            message.to_string(),
+        )
+    }
+
+    fn channel(id: &str, channel_type: &str, triggers: &[&str]) -> AlertChannel {
+        AlertChannel {
+            id: id.to_string(),
+            name: id.to_string(),
+            channel_type: channel_type.to_string(),
+            enabled: true,
+            triggers: triggers.iter().map(|t| t.to_string()).collect(),
+            webhook_url: None,
+            smtp: None,
+        }
+    }
+
+    fn service(channels: Vec<AlertChannel>) -> AlertService {
+        let path = std::env::temp_dir().join(format!(
+            "whb-alerts-{}.json",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let service = AlertService::new(path.to_string_lossy().to_string());
+        service.save_channels(channels).unwrap();
+        let _ = fs::remove_file(path);
+        service
+    }
+
+    /// Local HTTP stand-in: answers 200 to every request and forwards the bodies
+    async fn serve_http() -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut reader = BufReader::new(stream);
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).await.unwrap();
+                        if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                            length = value.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).await.unwrap();
+                    let _ = sender.send(String::from_utf8(body).unwrap());
+                    reader
+                        .into_inner()
+                        .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+
+        (url, receiver)
+    }
+
+    /// Local SMTP stand-in that accepts one message and forwards its DATA section
+    async fn serve_smtp() -> (u16, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut reader = BufReader::new(read);
+            write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+            let mut data: Option<String> = None;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                if let Some(message) = data.as_mut() {
+                    if line == ".\r\n" {
+                        let _ = sender.send(data.take().unwrap());
+                        write.write_all(b"250 Queued\r\n").await.unwrap();
+                    } else {
+                        message.push_str(&line);
+                    }
+                    continue;
+                }
+
+                let command = line.to_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("DATA") {
+                    data = Some(String::new());
+                    b"354 End data with <CR><LF>.<CR><LF>\r\n"
+                } else if command.starts_with("QUIT") {
+                    write.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                write.write_all(reply).await.unwrap();
+            }
+        });
+
+        (port, receiver)
+    }
+
+    #[test]
+    fn certificate_alerts_once_per_threshold() {
+        let now = Utc::now();
+        let mut site = website(Some(200));
+
+        assert!(certificate_event(&site, now + chrono::Duration::days(40), now).is_none());
+        let first = certificate_event(&site, now + chrono::Duration::days(10), now).unwrap();
+        assert_eq!(first.severity, "warning");
+
+        // Stored after that check: still 10 days left, nothing new
+        site.certificate_expires_at = Some((now + chrono::Duration::days(10)).to_rfc3339());
+        site.certificate_checked_at = Some(now.to_rfc3339());
+        let tomorrow = now + chrono::Duration::days(1);
+        assert!(certificate_event(&site, now + chrono::Duration::days(10), tomorrow).is_none());
+
+        // A few days later the 7-day threshold is crossed
+        let later = now + chrono::Duration::days(4);
+        let second = certificate_event(&site, now + chrono::Duration::days(10), later).unwrap();
+        assert!(second.title.contains("6 days"));
+
+        // Other jobs moving last_checked do not count as reading the certificate
+        site.last_checked = Some(later.to_rfc3339());
+        assert!(certificate_event(&site, now + chrono::Duration::days(10), later).is_some());
+    }
+
+    #[test]
+    fn new_vulnerabilities_raise_one_event() {
+        let record = ScanRecord {
+            website_id: 7,
+            result: WpscanResult {
+                url: String::new(),
+                wordpress_version: None,
+                vulnerabilities: Vec::new(),
+                plugins: Vec::new(),
+                themes: Vec::new(),
+                users: Vec::new(),
+                scan_date: String::new(),
+                is_wordpress: true,
+                api_requests_remaining: None,
+                findings: Vec::new(),
+                aggressive: false,
+            },
+            changes: vec![ScanChange {
+                change_type: "vulnerability_new".to_string(),
+                component: "akismet".to_string(),
+                from: None,
+                to: None,
+                description: "akismet: XSS".to_string(),
+            }],
+        };
+
+        let event = vulnerability_event(&website(Some(200)), &record).unwrap();
+        assert_eq!(event.title, "1 new vulnerability on Shop");
+        assert_eq!(event.message, "akismet: XSS");
+    }
+
+    #[tokio::test]
+    async fn delivers_to_webhooks_filtered_by_trigger() {
+        let (url, mut bodies) = serve_http().await;
+
+        let mut webhook = channel("hook", "webhook", &[]);
+        webhook.webhook_url = Some(format!("{}/alerts", url));
+        let mut slack = channel("slack", "slack", &["downtime"]);
+        slack.webhook_url = Some(format!("{}/slack", url));
+        let mut discord = channel("discord", "discord", &["recovery"]);
+        discord.webhook_url = Some(format!("{}/discord", url));
+        let service = service(vec![
+            webhook,
+            slack,
+            discord,
+            channel("desk", "desktop", &[]),
+        ]);
+
+        let event = downtime("https://shop.example returned HTTP 503.");
+        let deliveries = service.dispatch(&event, &[], None).await;
+
+        let sent: Vec<(&str, bool)> = deliveries
+            .iter()
+            .map(|d| (d.channel_id.as_str(), d.success))
+            .collect();
+        assert_eq!(sent, vec![("hook", true), ("slack", true), ("desk", false)]);
+
+        let mut received: Vec<serde_json::Value> = vec![
+            serde_json::from_str(&bodies.recv().await.unwrap()).unwrap(),
+            serde_json::from_str(&bodies.recv().await.unwrap()).unwrap(),
+        ];
+        received.sort_by_key(|body| body.get("text").is_some());
+        assert_eq!(received[0]["kind"], "downtime");
+        assert_eq!(received[1]["text"], "Shop is down");
+        assert_eq!(received[1]["attachments"][0]["color"], "#D92D20");
+
+        // Channels picked by an alert rule are used whatever their triggers
+        let routed = service
+            .dispatch(&event, &["discord".to_string()], None)
+            .await;
+        assert_eq!(routed.len(), 1);
+        assert!(routed[0].success);
+        let body: serde_json::Value = serde_json::from_str(&bodies.recv().await.unwrap()).unwrap();
+        assert_eq!(body["embeds"][0]["title"], "Shop is down");
+    }
+
+    #[tokio::test]
+    async fn delivers_email_over_smtp() {
+        let (port, mut messages) = serve_smtp().await;
+
+        let mut email = channel("mail", "email", &[]);
+        email.smtp = Some(SmtpSettings {
+            host: "127.0.0.1".to_string(),
+            port,
+            security: "none".to_string(),
+            username: None,
+            password: None,
+            from: "monitor@example.com".to_string(),
+            to: vec!["ops@example.com".to_string()],
+        });
+        let service = service(vec![email]);
+
+        let event = downtime("https://shop.example returned no response.");
+        let deliveries = service.dispatch(&event, &[], None).await;
+        assert!(deliveries[0].success, "{:?}", deliveries[0].error);
+
+        let message = messages.recv().await.unwrap();
+        assert!(message.contains("Subject: [CRITICAL] Shop is down"));
+        assert!(message.contains("To: ops@example.com"));
+        assert!(message.contains("https://shop.example returned no response."));
+    }
+}
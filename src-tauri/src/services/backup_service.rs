@@ -0,0 +1,77 @@
+// services/backup_service.rs
+use crate::models::cloud::{BackupInfo, CloudBackupResult};
+use crate::models::website::Website;
+use std::fs;
+
+/// Write a backup of the websites into a local backup directory
+pub fn write_local_backup(
+    backup_dir: &str,
+    websites: &[Website],
+) -> Result<CloudBackupResult, String> {
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let filename = format!("website_backup_{}.json", timestamp);
+
+    fs::create_dir_all(backup_dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    let backup_path = format!("{}/{}", backup_dir, filename);
+
+    let backup_data = serde_json::to_string_pretty(&websites)
+        .map_err(|e| format!("Failed to serialize backup: {}", e))?;
+
+    fs::write(&backup_path, backup_data).map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    Ok(CloudBackupResult {
+        success: true,
+        message: format!("Backup saved locally at: {}", backup_path),
+        backup_path: Some(backup_path),
+        drive_url: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Backups in a local backup directory, newest first
+pub fn list_local_backups(backup_dir: &str) -> Vec<BackupInfo> {
+    let mut backups = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(backup_dir) {
+        for entry in entries.flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_file() {
+                    let filename = entry.file_name().to_string_lossy().to_string();
+                    if filename.ends_with(".json") {
+                        let path = entry.path().to_string_lossy().to_string();
+
+                        // Extract timestamp from filename
+                        let timestamp =
+                            filename.replace("website_backup_", "").replace(".json", "");
+
+                        backups.push(BackupInfo {
+                            filename,
+                            path: path.clone(),
+                            timestamp,
+                            size: metadata.len(),
+                            is_local: true,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // Sort by timestamp (newest first)
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    backups
+}
+
+/// The websites saved in a backup file
+pub fn read_backup(backup_path: &str) -> Result<Vec<Website>, String> {
+    let backup_data = fs::read_to_string(backup_path)
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+    let websites: Vec<Website> = serde_json::from_str(&backup_data)
+        .map_err(|e| format!("Failed to parse backup data: {}", e))?;
+
+    Ok(websites)
+}
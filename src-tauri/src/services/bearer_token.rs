@@ -0,0 +1,46 @@
+// services/bearer_token.rs
+use axum::http::{HeaderMap, header};
+
+/// Whether a request carries `Authorization: Bearer <token>` with the expected token.
+/// An empty expected token authorizes nothing, and tokens are compared in constant
+/// time so the response time does not tell how much of a guess was right.
+pub fn authorized(headers: &HeaderMap, token: &str) -> bool {
+    let given = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match given {
+        Some(given) if !token.is_empty() => constant_time_eq(given.as_bytes(), token.as_bytes()),
+        _ => false,
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn only_the_exact_token_is_accepted() {
+        assert!(authorized(&headers("Bearer secret"), "secret"));
+        assert!(!authorized(&headers("Bearer secreT"), "secret"));
+        assert!(!authorized(&headers("Bearer secret2"), "secret"));
+        assert!(!authorized(&headers("Basic secret"), "secret"));
+        assert!(!authorized(&HeaderMap::new(), "secret"));
+
+        // An unset token must not let an empty one through
+        assert!(!authorized(&headers("Bearer "), ""));
+    }
+}
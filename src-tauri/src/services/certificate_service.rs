@@ -0,0 +1,56 @@
+// services/certificate_service.rs
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use url::Url;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Expiry date of the certificate a site presents, `None` for plain HTTP sites.
+/// Invalid and expired certificates are accepted so their dates can still be read.
+pub async fn certificate_expiry(url: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    if parsed.scheme() != "https" {
+        return Ok(None);
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("URL {} has no host", url))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| format!("Timed out connecting to {}", host))?
+        .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .map_err(|e| format!("Failed to create TLS connector: {}", e))?;
+    let tls = tokio::time::timeout(
+        CONNECT_TIMEOUT,
+        tokio_native_tls::TlsConnector::from(connector).connect(host, stream),
+    )
+    .await
+    .map_err(|_| format!("Timed out during TLS handshake with {}", host))?
+    .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+
+    let certificate = tls
+        .get_ref()
+        .peer_certificate()
+        .map_err(|e| format!("Failed to read certificate of {}: {}", host, e))?
+        .ok_or_else(|| format!("{} presented no certificate", host))?;
+    let der = certificate
+        .to_der()
+        .map_err(|e| format!("Failed to encode certificate of {}: {}", host, e))?;
+
+    not_after(&der).map(Some)
+}
+
+fn not_after(der: &[u8]) -> Result<DateTime<Utc>, String> {
+    let (_, certificate) = x509_parser::parse_x509_certificate(der)
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+    DateTime::from_timestamp(certificate.validity().not_after.timestamp(), 0)
+        .ok_or_else(|| "Certificate expiry date is out of range".to_string())
+}
@@ -0,0 +1,143 @@
+// services/check_history_service.rs
+use crate::models::check::CheckResult;
+use chrono::{DateTime, Duration, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+// Long enough for 90-day uptime figures
+const RETENTION_DAYS: i64 = 92;
+
+/// Every availability check per website, one JSON object per line so recording a
+/// check is an append rather than a rewrite of the whole history
+#[derive(Debug)]
+pub struct CheckHistoryService {
+    base_dir: PathBuf,
+}
+
+impl CheckHistoryService {
+    pub fn new(base_dir: String) -> Self {
+        Self {
+            base_dir: PathBuf::from(base_dir),
+        }
+    }
+
+    pub fn record(&self, check: &CheckResult) -> Result<(), String> {
+        fs::create_dir_all(&self.base_dir)
+            .map_err(|e| format!("Failed to create check history directory: {}", e))?;
+        let line = serde_json::to_string(check)
+            .map_err(|e| format!("Failed to serialize check: {}", e))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.history_path(check.website_id))
+            .map_err(|e| format!("Failed to open check history: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to save check: {}", e))?;
+
+        self.prune_if_needed(check.website_id)
+    }
+
+    /// Checks of a website, oldest first, optionally only those since a point in time
+    pub fn get_history(
+        &self,
+        website_id: i64,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CheckResult>, String> {
+        let path = self.history_path(website_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read check history: {}", e))?;
+        Ok(contents
+            .lines()
+            // A line cut short by a crash is skipped rather than failing the whole history
+            .filter_map(|line| serde_json::from_str::<CheckResult>(line).ok())
+            .filter(|check| since.is_none_or(|since| checked_at(check) >= Some(since)))
+            .collect())
+    }
+
+    /// Drop checks past the retention period once the oldest one is a day overdue.
+    /// Only the oldest check is read unless a rewrite is due, so pruning stays cheap.
+    fn prune_if_needed(&self, website_id: i64) -> Result<(), String> {
+        let cutoff = Utc::now() - Duration::days(RETENTION_DAYS);
+        let file = File::open(self.history_path(website_id))
+            .map_err(|e| format!("Failed to read check history: {}", e))?;
+        let overdue = BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .find_map(|line| serde_json::from_str::<CheckResult>(&line).ok())
+            .and_then(|oldest| checked_at(&oldest))
+            .is_some_and(|oldest| oldest < cutoff - Duration::days(1));
+        if !overdue {
+            return Ok(());
+        }
+
+        let history = self.get_history(website_id, None)?;
+        let kept: Vec<String> = history
+            .iter()
+            .filter(|check| checked_at(check).is_some_and(|at| at >= cutoff))
+            .filter_map(|check| serde_json::to_string(check).ok())
+            .collect();
+        fs::write(
+            self.history_path(website_id),
+            kept.join("\n") + if kept.is_empty() { "" } else { "\n" },
+        )
+        .map_err(|e| format!("Failed to prune check history: {}", e))
+    }
+
+    fn history_path(&self, website_id: i64) -> PathBuf {
+        self.base_dir.join(format!("{}.jsonl", website_id))
+    }
+}
+
+fn checked_at(check: &CheckResult) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&check.checked_at)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_filters_and_prunes() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-checks-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let service = CheckHistoryService::new(dir.to_string_lossy().to_string());
+        let check = |days_ago: i64, status: u16| CheckResult {
+            website_id: 1,
+            checked_at: (Utc::now() - Duration::days(days_ago)).to_rfc3339(),
+            status,
+            response_time_ms: Some(120),
+            maintenance: false,
+            vantage: None,
+            unconfirmed: false,
+        };
+
+        service.record(&check(2, 200)).unwrap();
+        service.record(&check(1, 503)).unwrap();
+        assert_eq!(service.get_history(1, None).unwrap().len(), 2);
+        let recent = service
+            .get_history(1, Some(Utc::now() - Duration::hours(36)))
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].status, 503);
+
+        // An entry far past the retention period triggers a rewrite without it
+        fs::write(
+            service.history_path(1),
+            serde_json::to_string(&check(200, 200)).unwrap() + "\n",
+        )
+        .unwrap();
+        service.record(&check(0, 200)).unwrap();
+        assert_eq!(service.get_history(1, None).unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,35 @@
+// services/check_service.rs
+use crate::models::check::CheckResult;
+use std::time::{Duration, Instant};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Status and response time of a URL as seen from this computer; connection errors are
+/// reported as status 0. The website id is left for the caller to fill in.
+pub async fn probe_website(url: &str) -> Result<CheckResult, String> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let checked_at = chrono::Utc::now().to_rfc3339();
+    let started = Instant::now();
+    let (status, response_time_ms) = match client.get(url).send().await {
+        Ok(response) => (
+            response.status().as_u16(),
+            Some(started.elapsed().as_millis() as u64),
+        ),
+        Err(_) => (0, None),
+    };
+
+    Ok(CheckResult {
+        website_id: 0,
+        checked_at,
+        status,
+        response_time_ms,
+        maintenance: false,
+        vantage: None,
+        unconfirmed: false,
+    })
+}
@@ -0,0 +1,20 @@
+// services/file_lock.rs
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+/// Lock `<path>.lock` until the returned file is dropped. The desktop app and the whb
+/// CLI both take it around read-modify-write cycles of a data file, so neither process
+/// saves over what the other wrote in between. The lock is advisory: plain reads
+/// ignore it.
+pub fn lock_file(path: impl AsRef<Path>) -> Result<File, std::io::Error> {
+    let mut lock_path = path.as_ref().as_os_str().to_owned();
+    lock_path.push(".lock");
+
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+    lock.lock()?;
+    Ok(lock)
+}
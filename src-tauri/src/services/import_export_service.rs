@@ -0,0 +1,209 @@
+// services/import_export_service.rs
+use crate::models::website::{WebVitals, Website};
+use crate::services::storage_service::StorageService;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportOptions {
+    pub format: String, // "json", "csv", "full-backup"
+    pub include_notes: bool,
+    pub include_custom_statuses: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FullBackupExport {
+    pub websites: Vec<Website>,
+    pub custom_statuses: Vec<CustomStatus>,
+    pub export_date: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CustomStatus {
+    pub value: String,
+    pub label: String,
+    pub color: String,
+}
+
+/// Serialize the stored websites in one of the export formats
+pub fn export_data(storage: &StorageService, options: &ExportOptions) -> Result<String, String> {
+    match storage.get_websites() {
+        Ok(websites) => {
+            // Ensure all websites have proper structure before export
+            let websites: Vec<Website> = websites
+                .into_iter()
+                .map(|mut website| {
+                    if website.vitals.is_none() {
+                        website.vitals = Some(WebVitals::default());
+                    }
+                    if website.notes.is_none() {
+                        website.notes = Some(crate::models::website::WebsiteNotes::default());
+                    }
+                    website
+                })
+                .collect();
+
+            match options.format.as_str() {
+                "full-backup" => {
+                    // Create full backup structure
+                    let backup = FullBackupExport {
+                        websites,
+                        custom_statuses: vec![], // You'll need to get these from your storage
+                        export_date: chrono::Utc::now().to_rfc3339(),
+                        version: "1.0".to_string(),
+                    };
+                    serde_json::to_string_pretty(&backup)
+                        .map_err(|e| format!("Failed to serialize backup: {}", e))
+                }
+                "json" => serde_json::to_string_pretty(&websites)
+                    .map_err(|e| format!("Failed to serialize websites: {}", e)),
+                _ => Err("Unsupported export format".to_string()),
+            }
+        }
+        Err(e) => Err(format!("Failed to get websites: {}", e)),
+    }
+}
+
+/// Import a websites export or full backup, merged with or replacing the stored websites
+pub async fn import_data(
+    json_data: &str,
+    storage: &StorageService,
+    merge: bool,
+) -> Result<ImportResult, String> {
+    eprintln!("Importing websites, merge mode: {}", merge);
+
+    // Try to parse as full backup first
+    let backup_parsed: Result<FullBackupExport, _> = serde_json::from_str(json_data);
+
+    if let Ok(backup) = backup_parsed {
+        eprintln!(
+            "Detected full backup file with {} websites",
+            backup.websites.len()
+        );
+        return import_full_backup(backup, storage, merge).await;
+    }
+
+    // Fall back to websites-only import
+    let imported_websites: Vec<Website> = match serde_json::from_str(json_data) {
+        Ok(websites) => websites,
+        Err(e) => return Err(format!("Failed to parse JSON: {}", e)),
+    };
+
+    import_websites_only(imported_websites, storage, merge).await
+}
+
+async fn import_full_backup(
+    backup: FullBackupExport,
+    storage: &StorageService,
+    merge: bool,
+) -> Result<ImportResult, String> {
+    let imported_websites = import_websites_only(backup.websites, storage, merge).await?;
+
+    Ok(ImportResult {
+        websites: imported_websites.websites,
+        custom_statuses: backup.custom_statuses,
+        imported_count: imported_websites.imported_count,
+        skipped_count: imported_websites.skipped_count,
+    })
+}
+
+async fn import_websites_only(
+    imported_websites: Vec<Website>,
+    storage: &StorageService,
+    merge: bool,
+) -> Result<ImportResult, String> {
+    // Validate the imported websites
+    if imported_websites.is_empty() {
+        return Err("No websites found in import file".to_string());
+    }
+
+    // Ensure all websites have proper defaults
+    let imported_websites: Vec<Website> = imported_websites
+        .into_iter()
+        .map(|mut website| {
+            if website.vitals.is_none() {
+                website.vitals = Some(WebVitals::default());
+            }
+
+            if website.project_status.is_none() {
+                website.project_status = Some("wip".to_string());
+            }
+
+            if website.notes.is_none() {
+                website.notes = Some(crate::models::website::WebsiteNotes::default());
+            }
+
+            if website.industry.is_empty() {
+                website.industry = "general".to_string();
+            }
+
+            website
+        })
+        .collect();
+
+    let mut imported_count = 0;
+    let mut skipped_count = 0;
+
+    if merge {
+        eprintln!("Merging with existing websites...");
+        let existing_websites = storage
+            .modify_websites(|existing_websites| {
+                let existing_urls: std::collections::HashSet<String> =
+                    existing_websites.iter().map(|w| w.url.clone()).collect();
+
+                let max_id = existing_websites.iter().map(|w| w.id).max().unwrap_or(0);
+                let mut next_id = max_id + 1;
+
+                for mut website in imported_websites {
+                    if existing_urls.contains(&website.url) {
+                        eprintln!("Skipping duplicate URL: {}", website.url);
+                        skipped_count += 1;
+                        continue;
+                    }
+
+                    website.id = next_id;
+                    next_id += 1;
+                    existing_websites.push(website);
+                    imported_count += 1;
+                }
+
+                existing_websites.clone()
+            })
+            .map_err(|e| e.to_string())?;
+        Ok(ImportResult {
+            websites: existing_websites,
+            custom_statuses: vec![],
+            imported_count,
+            skipped_count,
+        })
+    } else {
+        eprintln!("Replacing all websites with imported data...");
+        let imported_websites: Vec<Website> = imported_websites
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut website)| {
+                website.id = (index + 1) as i64;
+                website
+            })
+            .collect();
+
+        imported_count = imported_websites.len();
+        storage
+            .save_websites(&imported_websites)
+            .map_err(|e| e.to_string())?;
+        Ok(ImportResult {
+            websites: imported_websites,
+            custom_statuses: vec![],
+            imported_count,
+            skipped_count,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImportResult {
+    pub websites: Vec<Website>,
+    pub custom_statuses: Vec<CustomStatus>,
+    pub imported_count: usize,
+    pub skipped_count: usize,
+}
@@ -0,0 +1,329 @@
+// services/job_service.rs
+use crate::models::job::Job;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+// Finished jobs kept around for the UI once a new job is created
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Tracks background jobs, their cancellation flags and checkpoints.
+/// Jobs are persisted after every step so they can be resumed after a restart.
+#[derive(Debug)]
+pub struct JobService {
+    file_path: String,
+    jobs: Mutex<Vec<Job>>,
+    cancel_flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl JobService {
+    pub fn new(file_path: String) -> Self {
+        let mut jobs: Vec<Job> = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        // Anything still running belonged to a previous app session
+        for job in jobs.iter_mut().filter(|j| j.is_active()) {
+            job.status = "interrupted".to_string();
+        }
+
+        let service = Self {
+            file_path,
+            jobs: Mutex::new(jobs),
+            cancel_flags: Mutex::new(HashMap::new()),
+        };
+        let _ = service.persist(&service.jobs.lock().unwrap());
+        service
+    }
+
+    pub fn create_job(
+        &self,
+        kind: &str,
+        website_ids: Vec<i64>,
+        params: serde_json::Value,
+    ) -> Result<(Job, Arc<AtomicBool>), String> {
+        let suffix: String = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(6)
+            .map(char::from)
+            .collect();
+        let now = chrono::Utc::now();
+
+        let job = Job {
+            id: format!("{}-{}-{}", kind, now.format("%Y%m%d%H%M%S"), suffix),
+            kind: kind.to_string(),
+            status: "running".to_string(),
+            total: website_ids.len(),
+            completed: 0,
+            pending_ids: website_ids,
+            errors: Vec::new(),
+            params,
+            created_at: now.to_rfc3339(),
+            updated_at: now.to_rfc3339(),
+        };
+
+        let mut jobs = self.jobs.lock().unwrap();
+        Self::prune_finished(&mut jobs);
+        jobs.push(job.clone());
+        self.persist(&jobs)?;
+
+        Ok((job.clone(), self.register_flag(&job.id)))
+    }
+
+    /// Restart an interrupted, cancelled or failed job from its checkpoint
+    pub fn resume_job(&self, job_id: &str) -> Result<(Job, Arc<AtomicBool>), String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| format!("Job {} not found", job_id))?;
+
+        if !job.is_resumable() {
+            return Err(format!("Job {} cannot be resumed ({})", job_id, job.status));
+        }
+
+        job.status = "running".to_string();
+        job.updated_at = chrono::Utc::now().to_rfc3339();
+        let job = job.clone();
+        self.persist(&jobs)?;
+
+        Ok((job.clone(), self.register_flag(&job.id)))
+    }
+
+    /// Checkpoint one processed website
+    pub fn record_progress(
+        &self,
+        job_id: &str,
+        website_id: i64,
+        error: Option<String>,
+    ) -> Result<Job, String> {
+        self.update(job_id, |job| {
+            if let Some(index) = job.pending_ids.iter().position(|id| *id == website_id) {
+                job.pending_ids.remove(index);
+                job.completed += 1;
+            }
+            if let Some(error) = error {
+                job.errors.push(error);
+            }
+        })
+    }
+
+    /// Mark a job as done; the status is "cancelled" if it was cancelled meanwhile
+    pub fn finish_job(&self, job_id: &str, error: Option<String>) -> Result<Job, String> {
+        let cancelled = self
+            .cancel_flags
+            .lock()
+            .unwrap()
+            .remove(job_id)
+            .is_some_and(|flag| flag.load(Ordering::SeqCst));
+
+        self.update(job_id, |job| {
+            job.status = match (&error, cancelled) {
+                (Some(_), _) => "failed",
+                (None, true) => "cancelled",
+                (None, false) => "completed",
+            }
+            .to_string();
+            if let Some(error) = error {
+                job.errors.push(error);
+            }
+        })
+    }
+
+    pub fn cancel_job(&self, job_id: &str) -> Result<(), String> {
+        match self.cancel_flags.lock().unwrap().get(job_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+            None => Err(format!("Job {} is not running", job_id)),
+        }
+    }
+
+    /// Cancel every running job of a kind, returns how many were signalled
+    pub fn cancel_kind(&self, kind: &str) -> usize {
+        let running: Vec<String> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|j| j.kind == kind && j.is_active())
+            .map(|j| j.id.clone())
+            .collect();
+
+        running
+            .iter()
+            .filter(|id| self.cancel_job(id).is_ok())
+            .count()
+    }
+
+    pub fn get_job(&self, job_id: &str) -> Option<Job> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|j| j.id == job_id)
+            .cloned()
+    }
+
+    pub fn list_jobs(&self) -> Vec<Job> {
+        let mut jobs = self.jobs.lock().unwrap().clone();
+        jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        jobs
+    }
+
+    fn register_flag(&self, job_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.cancel_flags
+            .lock()
+            .unwrap()
+            .insert(job_id.to_string(), Arc::clone(&flag));
+        flag
+    }
+
+    fn update(&self, job_id: &str, apply: impl FnOnce(&mut Job)) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| format!("Job {} not found", job_id))?;
+
+        apply(job);
+        job.updated_at = chrono::Utc::now().to_rfc3339();
+        let job = job.clone();
+        self.persist(&jobs)?;
+
+        Ok(job)
+    }
+
+    fn prune_finished(jobs: &mut Vec<Job>) {
+        // Any job that is no longer running is finished, whether it completed or not
+        let finished = jobs.iter().filter(|j| !j.is_active()).count();
+        if finished <= MAX_FINISHED_JOBS {
+            return;
+        }
+
+        let mut to_remove = finished - MAX_FINISHED_JOBS;
+        // Oldest jobs come first since they are appended on creation
+        jobs.retain(|j| {
+            if to_remove > 0 && !j.is_active() {
+                to_remove -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn persist(&self, jobs: &[Job]) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(jobs)
+            .map_err(|e| format!("Failed to serialize jobs: {}", e))?;
+        fs::write(&self.file_path, json).map_err(|e| format!("Failed to save jobs: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_jobs_file(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!(
+                "whb-jobs-{}-{}.json",
+                name,
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+            ))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn checkpoints_and_resumes_after_restart() {
+        let path = temp_jobs_file("resume");
+        let service = JobService::new(path.clone());
+
+        let (job, _flag) = service
+            .create_job("screenshot", vec![1, 2, 3], serde_json::Value::Null)
+            .unwrap();
+        service.record_progress(&job.id, 2, None).unwrap();
+
+        // Simulate an app restart while the job is running
+        let restarted = JobService::new(path.clone());
+        let job = restarted.get_job(&job.id).unwrap();
+        assert_eq!(job.status, "interrupted");
+        assert_eq!(job.pending_ids, vec![1, 3]);
+        assert_eq!(job.completed, 1);
+
+        let (resumed, _flag) = restarted.resume_job(&job.id).unwrap();
+        assert_eq!(resumed.status, "running");
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn cancelling_one_job_leaves_others_running() {
+        let path = temp_jobs_file("cancel");
+        let service = JobService::new(path.clone());
+
+        let (first, first_flag) = service
+            .create_job("screenshot", vec![1], serde_json::Value::Null)
+            .unwrap();
+        let (second, second_flag) = service
+            .create_job("scan", vec![1], serde_json::Value::Null)
+            .unwrap();
+
+        service.cancel_job(&first.id).unwrap();
+        assert!(first_flag.load(Ordering::SeqCst));
+        assert!(!second_flag.load(Ordering::SeqCst));
+
+        assert_eq!(
+            service.finish_job(&first.id, None).unwrap().status,
+            "cancelled"
+        );
+        assert_eq!(
+            service.finish_job(&second.id, None).unwrap().status,
+            "completed"
+        );
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn prunes_every_finished_status() {
+        let path = temp_jobs_file("prune");
+        let service = JobService::new(path.clone());
+
+        let (running, _flag) = service
+            .create_job("scan", vec![1], serde_json::Value::Null)
+            .unwrap();
+        for (index, error) in [None, Some("boom".to_string())]
+            .into_iter()
+            .cycle()
+            .take(MAX_FINISHED_JOBS + 10)
+            .enumerate()
+        {
+            let (job, flag) = service
+                .create_job("screenshot", vec![1], serde_json::Value::Null)
+                .unwrap();
+            if index % 3 == 0 {
+                flag.store(true, Ordering::SeqCst);
+            }
+            service.finish_job(&job.id, error).unwrap();
+        }
+        service
+            .create_job("screenshot", vec![1], serde_json::Value::Null)
+            .unwrap();
+
+        let jobs = service.list_jobs();
+        assert_eq!(
+            jobs.iter().filter(|j| !j.is_active()).count(),
+            MAX_FINISHED_JOBS
+        );
+        assert!(jobs.iter().any(|j| j.id == running.id));
+
+        let _ = fs::remove_file(path);
+    }
+}
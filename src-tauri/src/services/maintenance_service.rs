@@ -0,0 +1,316 @@
+// services/maintenance_service.rs
+use crate::models::check::CheckResult;
+use crate::models::maintenance::{MaintenanceWindow, UptimeSummary};
+use crate::models::website::Website;
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+use std::fs;
+use std::sync::Mutex;
+
+// Recurring windows longer than a week would overlap with their next occurrence
+pub const MAX_DURATION_MINUTES: u32 = 7 * 24 * 60;
+
+/// Maintenance windows, stored as one JSON file like the alert channels
+#[derive(Debug)]
+pub struct MaintenanceService {
+    file_path: String,
+    windows: Mutex<Vec<MaintenanceWindow>>,
+}
+
+impl MaintenanceService {
+    pub fn new(file_path: String) -> Self {
+        let windows = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            file_path,
+            windows: Mutex::new(windows),
+        }
+    }
+
+    pub fn get_windows(&self) -> Vec<MaintenanceWindow> {
+        self.windows.lock().unwrap().clone()
+    }
+
+    pub fn save_windows(&self, windows: Vec<MaintenanceWindow>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&windows)
+            .map_err(|e| format!("Failed to serialize maintenance windows: {}", e))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| format!("Failed to save maintenance windows: {}", e))?;
+
+        *self.windows.lock().unwrap() = windows;
+        Ok(())
+    }
+
+    /// Whether any enabled window covering the website is active; recurring windows
+    /// follow the local clock
+    pub fn in_maintenance(&self, website: &Website, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&Local);
+        self.windows
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|window| applies_to(window, website) && is_active(window, &local))
+    }
+}
+
+pub fn applies_to(window: &MaintenanceWindow, website: &Website) -> bool {
+    let target = window.target.as_deref().unwrap_or_default();
+    window.enabled
+        && match window.scope.as_str() {
+            "website" => target == website.id.to_string(),
+            "tag" => website
+                .tags
+                .as_ref()
+                .is_some_and(|tags| tags.iter().any(|tag| tag.eq_ignore_ascii_case(target))),
+            _ => false,
+        }
+}
+
+/// Whether a window is active at a point in time. One-off windows include their start
+/// and exclude their end; a recurring occurrence lasts `duration_minutes` from each
+/// minute its cron expression matches.
+pub fn is_active<Tz: TimeZone>(window: &MaintenanceWindow, at: &DateTime<Tz>) -> bool {
+    if let Some(cron) = &window.cron {
+        let Ok(schedule) = CronSchedule::parse(cron) else {
+            return false;
+        };
+        let minute = at.with_second(0).and_then(|t| t.with_nanosecond(0));
+        return minute.is_some_and(|minute| {
+            (0..window.duration_minutes.min(MAX_DURATION_MINUTES))
+                .any(|ago| schedule.matches(&(minute.clone() - Duration::minutes(ago as i64))))
+        });
+    }
+
+    let parse = |value: &Option<String>| {
+        value
+            .as_deref()
+            .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|v| v.with_timezone(&Utc))
+    };
+    let at = at.with_timezone(&Utc);
+    match (parse(&window.starts_at), parse(&window.ends_at)) {
+        (Some(starts_at), Some(ends_at)) => starts_at <= at && at < ends_at,
+        _ => false,
+    }
+}
+
+/// Uptime of a set of checks; checks made during maintenance are counted separately
+pub fn uptime_summary(website_id: i64, days: u32, checks: &[CheckResult]) -> UptimeSummary {
+    let maintenance_checks = checks.iter().filter(|check| check.maintenance).count();
+    let counted = checks.len() - maintenance_checks;
+    let up_checks = checks
+        .iter()
+        .filter(|check| !check.maintenance && check.is_up())
+        .count();
+
+    UptimeSummary {
+        website_id,
+        days,
+        checks: counted,
+        up_checks,
+        maintenance_checks,
+        uptime_percent: (counted > 0).then(|| up_checks as f64 * 100.0 / counted as f64),
+    }
+}
+
+/// A five-field cron expression: minute, hour, day of month, month and day of week
+/// (0 or 7 is Sunday). Fields accept `*`, numbers, ranges, lists and `/step`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    // Standard cron matches either day field when both are restricted
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Cron expression \"{}\" needs 5 fields, found {}",
+                expression,
+                fields.len()
+            ));
+        }
+
+        let mut days_of_week: Vec<u32> = parse_field(fields[4], 0, 7)?
+            .into_iter()
+            .map(|day| day % 7)
+            .collect();
+        days_of_week.sort_unstable();
+        days_of_week.dedup();
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            any_day_of_month: fields[2] == "*",
+            any_day_of_week: fields[4] == "*",
+        })
+    }
+
+    pub fn matches<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        let day_of_month = self.days_of_month.contains(&at.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&at.weekday().num_days_from_sunday());
+        let day = match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+
+        day && self.minutes.contains(&at.minute())
+            && self.hours.contains(&at.hour())
+            && self.months.contains(&at.month())
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("Invalid cron step: {}", part))?,
+            ),
+            None => (part, 1),
+        };
+
+        let number = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(|| format!("Cron value {} is outside {}-{}", value, min, max))
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (number(start)?, number(end)?),
+                // "5/15" runs from 5 to the end of the range
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if start > end {
+            return Err(format!("Invalid cron range: {}", range));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_cron_fields() {
+        let schedule = CronSchedule::parse("*/15 22-23 * * 5,7").unwrap();
+        assert_eq!(schedule.minutes, vec![0, 15, 30, 45]);
+        assert_eq!(schedule.hours, vec![22, 23]);
+        assert_eq!(schedule.days_of_week, vec![0, 5]);
+
+        assert!(CronSchedule::parse("0 22 * *").is_err());
+        assert!(CronSchedule::parse("60 22 * * *").is_err());
+        assert!(CronSchedule::parse("0 23-22 * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn recurring_and_one_off_windows() {
+        // Friday nights from 22:00 for three hours; 2026-10-16 is a Friday
+        let friday_deploys = MaintenanceWindow {
+            cron: Some("0 22 * * 5".to_string()),
+            duration_minutes: 180,
+            ..Default::default()
+        };
+        assert!(!is_active(&friday_deploys, &at("2026-10-16T21:59:00Z")));
+        assert!(is_active(&friday_deploys, &at("2026-10-16T22:00:00Z")));
+        // Runs past midnight into Saturday
+        assert!(is_active(&friday_deploys, &at("2026-10-17T00:59:30Z")));
+        assert!(!is_active(&friday_deploys, &at("2026-10-17T01:00:00Z")));
+        assert!(!is_active(&friday_deploys, &at("2026-10-15T22:30:00Z")));
+
+        let migration = MaintenanceWindow {
+            starts_at: Some("2026-10-18T08:00:00+02:00".to_string()),
+            ends_at: Some("2026-10-18T10:00:00+02:00".to_string()),
+            ..Default::default()
+        };
+        assert!(is_active(&migration, &at("2026-10-18T06:00:00Z")));
+        assert!(!is_active(&migration, &at("2026-10-18T08:00:00Z")));
+    }
+
+    #[test]
+    fn windows_apply_by_website_or_tag() {
+        let website = Website {
+            id: 4,
+            tags: Some(vec!["Client-A".to_string()]),
+            ..Default::default()
+        };
+        let window = |scope: &str, target: &str| MaintenanceWindow {
+            scope: scope.to_string(),
+            target: Some(target.to_string()),
+            ..Default::default()
+        };
+
+        assert!(applies_to(&window("website", "4"), &website));
+        assert!(applies_to(&window("tag", "client-a"), &website));
+        assert!(!applies_to(&window("website", "5"), &website));
+        assert!(!applies_to(
+            &MaintenanceWindow {
+                enabled: false,
+                ..window("website", "4")
+            },
+            &website
+        ));
+    }
+
+    #[test]
+    fn uptime_leaves_out_maintenance_checks() {
+        let check = |status: u16, maintenance: bool| CheckResult {
+            website_id: 1,
+            checked_at: Utc::now().to_rfc3339(),
+            status,
+            response_time_ms: None,
+            maintenance,
+            vantage: None,
+            unconfirmed: false,
+        };
+        let checks = [
+            check(200, false),
+            check(200, false),
+            check(200, false),
+            check(503, false),
+            check(0, true),
+            check(0, true),
+        ];
+
+        let summary = uptime_summary(1, 90, &checks);
+        assert_eq!(summary.checks, 4);
+        assert_eq!(summary.up_checks, 3);
+        assert_eq!(summary.maintenance_checks, 2);
+        assert_eq!(summary.uptime_percent, Some(75.0));
+        assert_eq!(uptime_summary(1, 90, &[]).uptime_percent, None);
+    }
+}
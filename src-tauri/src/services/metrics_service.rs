@@ -0,0 +1,389 @@
+// services/metrics_service.rs
+use crate::models::check::CheckResult;
+use crate::models::website::Website;
+use crate::models::wpscan::ScanRecord;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::Mutex;
+
+// Upper bounds of the response time histogram, in seconds
+const RESPONSE_TIME_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Histogram {
+    buckets: Vec<u64>, // Cumulative, one per entry of RESPONSE_TIME_BUCKETS
+    count: u64,
+    sum: f64,
+}
+
+/// Counters of the checks made since the app started, and rendering of everything in
+/// the Prometheus text format. Gauges come from the stored websites and scans.
+#[derive(Debug, Default)]
+pub struct MetricsService {
+    response_times: Mutex<HashMap<i64, Histogram>>,
+    checks: Mutex<HashMap<(i64, bool), u64>>, // By website and whether it was up
+}
+
+impl MetricsService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&self, check: &CheckResult) {
+        *self
+            .checks
+            .lock()
+            .unwrap()
+            .entry((check.website_id, check.is_up()))
+            .or_default() += 1;
+
+        let Some(response_time_ms) = check.response_time_ms else {
+            return;
+        };
+        let seconds = response_time_ms as f64 / 1000.0;
+        let mut response_times = self.response_times.lock().unwrap();
+        let histogram = response_times
+            .entry(check.website_id)
+            .or_insert_with(|| Histogram {
+                buckets: vec![0; RESPONSE_TIME_BUCKETS.len()],
+                ..Default::default()
+            });
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(RESPONSE_TIME_BUCKETS) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        histogram.count += 1;
+        histogram.sum += seconds;
+    }
+
+    /// All metrics for the given websites and their latest scans
+    pub fn render(
+        &self,
+        websites: &[Website],
+        latest_scans: &HashMap<i64, ScanRecord>,
+        now: DateTime<Utc>,
+    ) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "whb_website_up",
+            "gauge",
+            "1 when the last check answered HTTP 200",
+        );
+        for website in websites {
+            if let Some(status) = website.status {
+                sample(
+                    &mut out,
+                    "whb_website_up",
+                    &labels(website, &[]),
+                    (status == 200) as u8,
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "whb_website_status_code",
+            "gauge",
+            "HTTP status of the last check, 0 when the site did not answer",
+        );
+        for website in websites {
+            if let Some(status) = website.status {
+                sample(
+                    &mut out,
+                    "whb_website_status_code",
+                    &labels(website, &[]),
+                    status,
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "whb_checks_total",
+            "counter",
+            "Status checks since the app started",
+        );
+        let checks = self.checks.lock().unwrap();
+        for website in websites {
+            for (up, result) in [(true, "up"), (false, "down")] {
+                if let Some(count) = checks.get(&(website.id, up)) {
+                    sample(
+                        &mut out,
+                        "whb_checks_total",
+                        &labels(website, &[("result", result)]),
+                        count,
+                    );
+                }
+            }
+        }
+
+        header(
+            &mut out,
+            "whb_response_time_seconds",
+            "histogram",
+            "Response time of status checks since the app started",
+        );
+        let response_times = self.response_times.lock().unwrap();
+        for website in websites {
+            let Some(histogram) = response_times.get(&website.id) else {
+                continue;
+            };
+            for (bucket, bound) in histogram.buckets.iter().zip(RESPONSE_TIME_BUCKETS) {
+                let bound = bound.to_string();
+                sample(
+                    &mut out,
+                    "whb_response_time_seconds_bucket",
+                    &labels(website, &[("le", &bound)]),
+                    bucket,
+                );
+            }
+            sample(
+                &mut out,
+                "whb_response_time_seconds_bucket",
+                &labels(website, &[("le", "+Inf")]),
+                histogram.count,
+            );
+            sample(
+                &mut out,
+                "whb_response_time_seconds_sum",
+                &labels(website, &[]),
+                histogram.sum,
+            );
+            sample(
+                &mut out,
+                "whb_response_time_seconds_count",
+                &labels(website, &[]),
+                histogram.count,
+            );
+        }
+
+        header(
+            &mut out,
+            "whb_certificate_expiry_days",
+            "gauge",
+            "Days until the TLS certificate expires, negative once expired",
+        );
+        for website in websites {
+            let expires_at = website
+                .certificate_expires_at
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok());
+            if let Some(expires_at) = expires_at {
+                let days = (expires_at.with_timezone(&Utc) - now).num_seconds() as f64 / 86_400.0;
+                sample(
+                    &mut out,
+                    "whb_certificate_expiry_days",
+                    &labels(website, &[]),
+                    format!("{:.3}", days),
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "whb_web_vital",
+            "gauge",
+            "Core Web Vitals as shown on the dashboard: milliseconds, CLS unitless",
+        );
+        for website in websites {
+            let Some(vitals) = &website.vitals else {
+                continue;
+            };
+            let values = [
+                ("lcp", vitals.lcp),
+                ("fid", vitals.fid),
+                ("cls", vitals.cls),
+                ("fcp", vitals.fcp),
+                ("ttfb", vitals.ttfb),
+            ];
+            // Zero means not measured yet
+            for (metric, value) in values.into_iter().filter(|(_, value)| *value > 0.0) {
+                sample(
+                    &mut out,
+                    "whb_web_vital",
+                    &labels(website, &[("metric", metric)]),
+                    value,
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "whb_vulnerabilities",
+            "gauge",
+            "Vulnerabilities found by the latest WordPress scan, by severity",
+        );
+        for website in websites {
+            let Some(record) = latest_scans.get(&website.id) else {
+                continue;
+            };
+            let mut by_severity: BTreeMap<String, usize> = BTreeMap::new();
+            for vulnerability in &record.result.vulnerabilities {
+                let severity = vulnerability
+                    .severity
+                    .as_deref()
+                    .unwrap_or("unknown")
+                    .to_lowercase();
+                *by_severity.entry(severity).or_default() += 1;
+            }
+            if by_severity.is_empty() {
+                by_severity.insert("unknown".to_string(), 0);
+            }
+            for (severity, count) in by_severity {
+                sample(
+                    &mut out,
+                    "whb_vulnerabilities",
+                    &labels(website, &[("severity", &severity)]),
+                    count,
+                );
+            }
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+}
+
+fn labels(website: &Website, extra: &[(&str, &str)]) -> String {
+    let id = website.id.to_string();
+    [
+        ("website_id", id.as_str()),
+        ("name", &website.name),
+        ("url", &website.url),
+    ]
+    .iter()
+    .chain(extra)
+    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::website::WebVitals;
+    use crate::models::wpscan::{Vulnerability, WpscanResult};
+
+    fn check(status: u16, response_time_ms: Option<u64>) -> CheckResult {
+        CheckResult {
+            website_id: 7,
+            checked_at: "2026-10-18T09:00:00+00:00".to_string(),
+            status,
+            response_time_ms,
+            maintenance: false,
+            vantage: None,
+            unconfirmed: false,
+        }
+    }
+
+    fn vulnerability(severity: Option<&str>) -> Vulnerability {
+        Vulnerability {
+            id: "1".to_string(),
+            title: "XSS".to_string(),
+            description: None,
+            vuln_type: None,
+            severity: severity.map(str::to_string),
+            fixed_in: None,
+            references: Vec::new(),
+            cve: None,
+        }
+    }
+
+    #[test]
+    fn renders_prometheus_text() {
+        let service = MetricsService::new();
+        service.observe(&check(200, Some(80)));
+        service.observe(&check(200, Some(300)));
+        service.observe(&check(0, None));
+
+        let website = Website {
+            id: 7,
+            name: "Shop \"EU\"".to_string(),
+            url: "https://shop.example".to_string(),
+            status: Some(200),
+            certificate_expires_at: Some("2026-10-28T09:00:00+00:00".to_string()),
+            vitals: Some(WebVitals {
+                lcp: 2100.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let scan = ScanRecord {
+            website_id: 7,
+            result: WpscanResult {
+                url: website.url.clone(),
+                wordpress_version: None,
+                vulnerabilities: vec![
+                    vulnerability(Some("High")),
+                    vulnerability(Some("high")),
+                    vulnerability(None),
+                ],
+                plugins: Vec::new(),
+                themes: Vec::new(),
+                users: Vec::new(),
+                scan_date: String::new(),
+                is_wordpress: true,
+                api_requests_remaining: None,
+                findings: Vec::new(),
+                aggressive: false,
+            },
+            changes: Vec::new(),
+        };
+        let now = DateTime::parse_from_rfc3339("2026-10-18T09:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let text = service.render(&[website], &HashMap::from([(7, scan)]), now);
+        let labels = r#"website_id="7",name="Shop \"EU\"",url="https://shop.example""#;
+        for line in [
+            format!("whb_website_up{{{}}} 1", labels),
+            format!("whb_checks_total{{{},result=\"up\"}} 2", labels),
+            format!("whb_checks_total{{{},result=\"down\"}} 1", labels),
+            format!(
+                "whb_response_time_seconds_bucket{{{},le=\"0.1\"}} 1",
+                labels
+            ),
+            format!(
+                "whb_response_time_seconds_bucket{{{},le=\"0.5\"}} 2",
+                labels
+            ),
+            format!(
+                "whb_response_time_seconds_bucket{{{},le=\"+Inf\"}} 2",
+                labels
+            ),
+            format!("whb_response_time_seconds_sum{{{}}} 0.38", labels),
+            format!("whb_certificate_expiry_days{{{}}} 10.000", labels),
+            format!("whb_web_vital{{{},metric=\"lcp\"}} 2100", labels),
+            format!("whb_vulnerabilities{{{},severity=\"high\"}} 2", labels),
+            format!("whb_vulnerabilities{{{},severity=\"unknown\"}} 1", labels),
+            "# TYPE whb_response_time_seconds histogram".to_string(),
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing {}\n{}",
+                line,
+                text
+            );
+        }
+        assert!(!text.contains("metric=\"cls\""));
+    }
+}
@@ -1,3 +1,31 @@
 pub mod back4app_service;
 pub mod storage_service;
 pub mod wpscan_service;
+pub mod screenshot_history_service;
+pub mod screenshot_service;
+pub mod job_service;
+pub mod scan_storage_service;
+pub mod wordpress_fingerprint;
+pub mod wordpress_hardening;
+pub mod technology_service;
+pub mod page_analysis;
+pub mod scan_report;
+pub mod certificate_service;
+pub mod alert_service;
+pub mod check_history_service;
+pub mod alert_rules;
+pub mod alert_rule_service;
+pub mod maintenance_service;
+pub mod check_service;
+pub mod agent_service;
+pub mod metrics_service;
+pub mod status_page;
+pub mod security_headers;
+pub mod report_service;
+pub mod report_schedule_service;
+pub mod bearer_token;
+pub mod search_service;
+pub mod backup_service;
+pub mod settings_service;
+pub mod import_export_service;
+pub mod file_lock;
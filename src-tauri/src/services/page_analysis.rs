@@ -0,0 +1,171 @@
+// services/page_analysis.rs
+use crate::models::technology::Technology;
+use crate::services::technology_service::TechnologyService;
+use crate::services::wordpress_fingerprint::{PageFingerprint, fingerprint_document};
+use reqwest::Client;
+use scraper::Html;
+use std::time::Duration;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What a single page load reveals: response headers, cookies and the HTML body
+#[derive(Debug, Default, Clone)]
+pub struct FetchedPage {
+    pub url: String, // Final URL after redirects
+    pub status: u16,
+    pub headers: Vec<(String, String)>, // Lowercase names
+    pub cookies: Vec<(String, String)>,
+    pub html: String,
+}
+
+impl FetchedPage {
+    /// Load a page once; every detector works from this copy
+    pub async fn fetch(client: &Client, url: &str) -> Result<Self, String> {
+        let response = client
+            .get(url)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .timeout(FETCH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+        let final_url = response.url().to_string();
+        let status = response.status().as_u16();
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    name.as_str().to_lowercase(),
+                    value.to_str().ok()?.to_string(),
+                ))
+            })
+            .collect();
+        let cookies = headers
+            .iter()
+            .filter(|(name, _)| name == "set-cookie")
+            .filter_map(|(_, value)| parse_cookie(value))
+            .collect();
+        let html = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+
+        Ok(Self {
+            url: final_url,
+            status,
+            headers,
+            cookies,
+            html,
+        })
+    }
+
+    pub fn header_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.headers
+            .iter()
+            .filter(move |(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The combined verdict of every detector run on one page
+#[derive(Debug, Default, Clone)]
+pub struct PageAnalysis {
+    pub is_wordpress: bool,
+    pub fingerprint: PageFingerprint,
+    pub technologies: Vec<Technology>, // Empty when no technology rules were given
+}
+
+/// Parse the page once and share the document between the WordPress fingerprint and
+/// the technology rules, so both agree on whether the site runs WordPress.
+pub fn analyze_page(page: &FetchedPage, technologies: Option<&TechnologyService>) -> PageAnalysis {
+    let document = Html::parse_document(&page.html);
+    let fingerprint = fingerprint_document(&document, &page.url);
+    let mut detected = technologies
+        .map(|service| service.analyze(page, &document))
+        .unwrap_or_default();
+
+    let is_wordpress = fingerprint.is_wordpress()
+        || detected.iter().any(|t| t.name == "WordPress")
+        || has_wordpress_link_header(page);
+
+    // Evidence only the fingerprint understood (renamed content dirs, REST link header)
+    if is_wordpress && technologies.is_some() && !detected.iter().any(|t| t.name == "WordPress") {
+        detected.push(Technology {
+            name: "WordPress".to_string(),
+            category: "cms".to_string(),
+            version: fingerprint.generator_version.clone(),
+        });
+        detected.sort_by(|a, b| a.category.cmp(&b.category).then(a.name.cmp(&b.name)));
+    }
+
+    PageAnalysis {
+        is_wordpress,
+        fingerprint,
+        technologies: detected,
+    }
+}
+
+/// WordPress announces its REST API in a `Link: <…/wp-json/>; rel="https://api.w.org/"` header
+fn has_wordpress_link_header(page: &FetchedPage) -> bool {
+    page.header_values("link")
+        .any(|value| value.contains("rel=\"https://api.w.org/\""))
+}
+
+/// "name=value; Path=/; HttpOnly" -> (name, value)
+fn parse_cookie(header: &str) -> Option<(String, String)> {
+    let pair = header.split(';').next()?;
+    let (name, value) = pair.split_once('=')?;
+    Some((name.trim().to_string(), value.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(html: &str, headers: Vec<(&str, &str)>) -> FetchedPage {
+        FetchedPage {
+            url: "https://example.com/".to_string(),
+            status: 200,
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            cookies: Vec::new(),
+            html: html.to_string(),
+        }
+    }
+
+    #[test]
+    fn detectors_share_one_verdict() {
+        let technologies =
+            TechnologyService::with_rules(include_str!("../../resources/technologies.json"))
+                .unwrap();
+
+        // Renamed content directory: only the fingerprint recognises it
+        let renamed = page(
+            r#"<link href="/app/themes/sage/main.css"><script src="/app/plugins/wordfence/a.js"></script>"#,
+            vec![],
+        );
+        let analysis = analyze_page(&renamed, Some(&technologies));
+        assert!(analysis.is_wordpress);
+        assert!(analysis.technologies.iter().any(|t| t.name == "WordPress"));
+
+        // Headless setups still send the REST API link header
+        let headless = page(
+            "<html><body></body></html>",
+            vec![(
+                "link",
+                "<https://example.com/wp-json/>; rel=\"https://api.w.org/\"",
+            )],
+        );
+        assert!(analyze_page(&headless, None).is_wordpress);
+
+        // Merely mentioning WordPress is not enough
+        let blog = page("<p>Why we moved away from WordPress</p>", vec![]);
+        let analysis = analyze_page(&blog, Some(&technologies));
+        assert!(!analysis.is_wordpress);
+        assert!(!analysis.technologies.iter().any(|t| t.name == "WordPress"));
+    }
+}
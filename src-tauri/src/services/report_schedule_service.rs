@@ -0,0 +1,465 @@
+// services/report_schedule_service.rs
+use crate::models::alert::SmtpSettings;
+use crate::models::report::{GeneratedReport, ReportDelivery, ReportRequest, ReportSchedule};
+use crate::services::alert_service::smtp_transport;
+use crate::services::report_service::{ReportService, ReportSources, generate_report};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone, Utc};
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
+use lettre::{AsyncTransport, Message};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+// Oldest deliveries are dropped beyond this
+const MAX_DELIVERIES: usize = 500;
+// A failed delivery is retried after this long, doubled after every further failure
+const RETRY_DELAY_MINUTES: i64 = 15;
+const MAX_RETRY_DELAY_MINUTES: i64 = 6 * 60;
+
+/// Report schedules and the history of their deliveries, each stored as one JSON file
+#[derive(Debug)]
+pub struct ReportScheduleService {
+    schedules_path: String,
+    deliveries_path: String,
+    schedules: Mutex<Vec<ReportSchedule>>,
+    deliveries: Mutex<Vec<ReportDelivery>>, // Oldest first
+}
+
+impl ReportScheduleService {
+    pub fn new(schedules_path: String, deliveries_path: String) -> Self {
+        let schedules = fs::read_to_string(&schedules_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        let deliveries = fs::read_to_string(&deliveries_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            schedules_path,
+            deliveries_path,
+            schedules: Mutex::new(schedules),
+            deliveries: Mutex::new(deliveries),
+        }
+    }
+
+    pub fn get_schedules(&self) -> Vec<ReportSchedule> {
+        self.schedules.lock().unwrap().clone()
+    }
+
+    pub fn save_schedules(&self, schedules: Vec<ReportSchedule>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&schedules)
+            .map_err(|e| format!("Failed to serialize report schedules: {}", e))?;
+        fs::write(&self.schedules_path, json)
+            .map_err(|e| format!("Failed to save report schedules: {}", e))?;
+
+        *self.schedules.lock().unwrap() = schedules;
+        Ok(())
+    }
+
+    /// Deliveries, most recent first, optionally of one schedule only
+    pub fn get_deliveries(&self, schedule_id: Option<&str>) -> Vec<ReportDelivery> {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|delivery| schedule_id.is_none_or(|id| delivery.schedule_id == id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn record_delivery(&self, delivery: ReportDelivery) -> Result<(), String> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.push(delivery);
+        let overflow = deliveries.len().saturating_sub(MAX_DELIVERIES);
+        deliveries.drain(..overflow);
+
+        let json = serde_json::to_string_pretty(&*deliveries)
+            .map_err(|e| format!("Failed to serialize report deliveries: {}", e))?;
+        fs::write(&self.deliveries_path, json)
+            .map_err(|e| format!("Failed to save report deliveries: {}", e))
+    }
+
+    /// Enabled schedules whose latest occurrence has not been delivered yet, see
+    /// `needs_delivery`; occurrences follow the local clock
+    pub fn due_schedules(&self, now: DateTime<Utc>) -> Vec<ReportSchedule> {
+        let local = now.with_timezone(&Local);
+        let deliveries = self.deliveries.lock().unwrap();
+        self.get_schedules()
+            .into_iter()
+            .filter(|schedule| needs_delivery(schedule, &local, &deliveries))
+            .collect()
+    }
+}
+
+/// Whether a schedule should run now, given the deliveries so far (oldest first).
+/// Only a successful delivery counts as a run; after a failure the schedule is tried
+/// again once the retry delay has passed.
+pub fn needs_delivery<Tz: TimeZone>(
+    schedule: &ReportSchedule,
+    now: &DateTime<Tz>,
+    deliveries: &[ReportDelivery],
+) -> bool {
+    let mut last_run = None;
+    let mut last_failure = None;
+    let mut failures = 0;
+    for delivery in deliveries
+        .iter()
+        .rev()
+        .filter(|delivery| delivery.schedule_id == schedule.id)
+    {
+        if delivery.success {
+            last_run = parse_time(&delivery.delivered_at);
+            break;
+        }
+        failures += 1;
+        last_failure = last_failure.or_else(|| parse_time(&delivery.delivered_at));
+    }
+
+    let retry_at = last_failure.map(|failed_at| failed_at + retry_delay(failures));
+    is_due(schedule, now, last_run)
+        && retry_at.is_none_or(|retry_at| retry_at <= now.with_timezone(&Utc))
+}
+
+fn retry_delay(failures: u32) -> chrono::Duration {
+    let minutes = RETRY_DELAY_MINUTES.saturating_mul(1 << failures.saturating_sub(1).min(16));
+    chrono::Duration::minutes(minutes.min(MAX_RETRY_DELAY_MINUTES))
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// The most recent time at or before `now` the schedule should have run
+pub fn latest_occurrence<Tz: TimeZone>(
+    schedule: &ReportSchedule,
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    let today = now.date_naive();
+    // Newest first
+    let dates: Vec<NaiveDate> = match schedule.frequency.as_str() {
+        "weekly" => (0..=7)
+            .filter_map(|ago| today.checked_sub_days(Days::new(ago)))
+            .filter(|date| date.weekday().num_days_from_sunday() == schedule.day)
+            .collect(),
+        _ => {
+            let previous_month = today
+                .with_day(1)
+                .and_then(|first| first.checked_sub_days(Days::new(1)));
+            [Some(today), previous_month]
+                .into_iter()
+                .flatten()
+                .filter_map(|date| NaiveDate::from_ymd_opt(date.year(), date.month(), schedule.day))
+                .collect()
+        }
+    };
+
+    dates
+        .into_iter()
+        .filter_map(|date| date.and_hms_opt(schedule.hour, 0, 0))
+        .filter_map(|at| now.timezone().from_local_datetime(&at).earliest())
+        .find(|at| at <= now)
+}
+
+/// Whether the latest occurrence is newer than the last run, or than the schedule
+/// itself when it never ran
+pub fn is_due<Tz: TimeZone>(
+    schedule: &ReportSchedule,
+    now: &DateTime<Tz>,
+    last_run: Option<DateTime<Utc>>,
+) -> bool {
+    let Some(occurrence) = latest_occurrence(schedule, now) else {
+        return false;
+    };
+    let since = last_run.or_else(|| schedule.created_at.as_deref().and_then(parse_time));
+    schedule.enabled && since.is_none_or(|since| since < occurrence.with_timezone(&Utc))
+}
+
+pub fn period_days(schedule: &ReportSchedule) -> u32 {
+    match schedule.frequency.as_str() {
+        "weekly" => 7,
+        _ => 30,
+    }
+}
+
+/// Generate the schedule's report and deliver it. Failures end up in the returned
+/// delivery rather than an error, so they show in the history.
+pub async fn run_schedule(
+    reports: &ReportService,
+    sources: &ReportSources<'_>,
+    schedule: &ReportSchedule,
+    now: DateTime<Utc>,
+) -> ReportDelivery {
+    let folder = schedule
+        .folder
+        .clone()
+        .filter(|_| schedule.delivery == "folder");
+    let request = ReportRequest {
+        scope: schedule.scope.clone(),
+        target: schedule.target.clone(),
+        template: schedule.template.clone(),
+        formats: schedule.formats.clone(),
+        period_days: Some(period_days(schedule)),
+        output_dir: folder.clone(),
+    };
+    let destination = match (&schedule.smtp, &folder) {
+        (_, Some(folder)) => folder.clone(),
+        (Some(smtp), None) => smtp.to.join(", "),
+        (None, None) => String::new(),
+    };
+
+    let result = match generate_report(reports, sources, &request, now).await {
+        Ok(report) => match (schedule.delivery.as_str(), &schedule.smtp) {
+            ("folder", _) => Ok(report),
+            ("email", Some(smtp)) => email_report(smtp, schedule, &report).await.map(|()| report),
+            _ => Err(format!(
+                "Schedule {} has no delivery settings",
+                schedule.name
+            )),
+        },
+        Err(e) => Err(e),
+    };
+
+    let (title, files, error) = match result {
+        Ok(report) => (Some(report.title), report.files, None),
+        Err(e) => (None, Vec::new(), Some(e)),
+    };
+    ReportDelivery {
+        schedule_id: schedule.id.clone(),
+        schedule_name: schedule.name.clone(),
+        title,
+        delivered_at: now.to_rfc3339(),
+        period_days: period_days(schedule),
+        delivery: schedule.delivery.clone(),
+        destination,
+        files,
+        success: error.is_none(),
+        error,
+    }
+}
+
+async fn email_report(
+    smtp: &SmtpSettings,
+    schedule: &ReportSchedule,
+    report: &GeneratedReport,
+) -> Result<(), String> {
+    let message = report_message(smtp, schedule, report)?;
+    smtp_transport(smtp)?
+        .send(message)
+        .await
+        .map_err(|e| format!("Failed to send report e-mail: {}", e))?;
+    Ok(())
+}
+
+fn report_message(
+    smtp: &SmtpSettings,
+    schedule: &ReportSchedule,
+    report: &GeneratedReport,
+) -> Result<Message, String> {
+    if smtp.to.is_empty() {
+        return Err("No e-mail recipients configured".to_string());
+    }
+
+    let mut builder = Message::builder()
+        .from(
+            smtp.from
+                .parse()
+                .map_err(|e| format!("Invalid sender {}: {}", smtp.from, e))?,
+        )
+        .subject(&report.title);
+    for recipient in &smtp.to {
+        builder = builder.to(recipient
+            .parse()
+            .map_err(|e| format!("Invalid recipient {}: {}", recipient, e))?);
+    }
+
+    let body = format!(
+        "{}\n\nAttached is the {} report covering the last {} days for {} {}.\n",
+        report.title,
+        schedule.frequency,
+        report.period_days,
+        report.website_ids.len(),
+        if report.website_ids.len() == 1 {
+            "website"
+        } else {
+            "websites"
+        }
+    );
+    let mut content = MultiPart::mixed().singlepart(SinglePart::plain(body));
+    for file in &report.files {
+        let path = Path::new(file);
+        let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+        let content_type = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("pdf") => ContentType::parse("application/pdf"),
+            _ => ContentType::parse("text/html; charset=utf-8"),
+        }
+        .map_err(|e| format!("Invalid attachment type: {}", e))?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        content = content.singlepart(Attachment::new(name).body(data, content_type));
+    }
+
+    builder
+        .multipart(content)
+        .map_err(|e| format!("Failed to build e-mail: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_time(value).unwrap()
+    }
+
+    fn schedule(frequency: &str, day: u32, hour: u32) -> ReportSchedule {
+        ReportSchedule {
+            id: "client-a".to_string(),
+            frequency: frequency.to_string(),
+            day,
+            hour,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_latest_weekly_and_monthly_occurrence() {
+        // 2026-10-18 is a Sunday
+        let now = at("2026-10-18T12:00:00Z");
+        let monday = schedule("weekly", 1, 8);
+        assert_eq!(
+            latest_occurrence(&monday, &now),
+            Some(at("2026-10-12T08:00:00Z"))
+        );
+        let sunday_evening = schedule("weekly", 0, 18);
+        assert_eq!(
+            latest_occurrence(&sunday_evening, &now),
+            Some(at("2026-10-11T18:00:00Z"))
+        );
+
+        let first_of_month = schedule("monthly", 1, 8);
+        assert_eq!(
+            latest_occurrence(&first_of_month, &now),
+            Some(at("2026-10-01T08:00:00Z"))
+        );
+        // Not yet this month, so last month's
+        let late_month = schedule("monthly", 28, 8);
+        assert_eq!(
+            latest_occurrence(&late_month, &now),
+            Some(at("2026-09-28T08:00:00Z"))
+        );
+        let january = at("2026-01-05T00:00:00Z");
+        assert_eq!(
+            latest_occurrence(&late_month, &january),
+            Some(at("2025-12-28T08:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn due_once_per_occurrence_after_creation() {
+        let now = at("2026-10-18T12:00:00Z");
+        let mut monday = schedule("weekly", 1, 8);
+
+        // Created after last Monday's run time: wait for next Monday
+        monday.created_at = Some("2026-10-14T09:00:00+00:00".to_string());
+        assert!(!is_due(&monday, &now, None));
+        assert!(is_due(&monday, &at("2026-10-19T08:00:00Z"), None));
+
+        monday.created_at = Some("2026-10-01T09:00:00+00:00".to_string());
+        assert!(is_due(&monday, &now, None));
+        assert!(!is_due(&monday, &now, Some(at("2026-10-12T08:00:30Z"))));
+        assert!(is_due(&monday, &now, Some(at("2026-10-05T08:00:30Z"))));
+
+        monday.enabled = false;
+        assert!(!is_due(&monday, &now, None));
+    }
+
+    #[test]
+    fn mails_report_files_as_attachments() {
+        let dir = std::env::temp_dir().join(format!("whb-report-mail-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("website-report-client-a-2026-10-18.pdf");
+        fs::write(&file, b"%PDF-1.4").unwrap();
+
+        let smtp = SmtpSettings {
+            host: "127.0.0.1".to_string(),
+            port: 25,
+            security: "none".to_string(),
+            username: None,
+            password: None,
+            from: "reports@agency.example".to_string(),
+            to: vec!["client@example.com".to_string()],
+        };
+        let report = GeneratedReport {
+            title: "Website report: Client A".to_string(),
+            generated_at: "2026-10-18T08:00:00+00:00".to_string(),
+            period_days: 30,
+            website_ids: vec![1, 2],
+            files: vec![file.to_string_lossy().to_string()],
+        };
+
+        let message = report_message(&smtp, &schedule("monthly", 1, 8), &report).unwrap();
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(raw.contains("Subject: Website report: Client A"));
+        assert!(raw.contains("To: client@example.com"));
+        assert!(raw.contains("monthly report covering the last 30 days for 2 websites"));
+        assert!(raw.contains("Content-Type: application/pdf"));
+        assert!(raw.contains("attachment;"));
+        assert!(raw.contains("\"website-report-client-a-2026-10-18.pdf\""));
+    }
+
+    fn delivery(delivered_at: &str, success: bool) -> ReportDelivery {
+        ReportDelivery {
+            schedule_id: "client-a".to_string(),
+            schedule_name: "Client A".to_string(),
+            title: None,
+            delivered_at: delivered_at.to_string(),
+            period_days: 7,
+            delivery: "email".to_string(),
+            destination: "client@example.com".to_string(),
+            files: Vec::new(),
+            success,
+            error: (!success).then(|| "Failed to send report e-mail".to_string()),
+        }
+    }
+
+    fn due(schedule: &ReportSchedule, now: &str, deliveries: &[ReportDelivery]) -> bool {
+        needs_delivery(schedule, &at(now), deliveries)
+    }
+
+    #[test]
+    fn failed_deliveries_are_retried_with_a_growing_delay() {
+        let mut monday = schedule("weekly", 1, 8);
+        monday.created_at = Some("2026-10-01T09:00:00+00:00".to_string());
+        let mut deliveries = vec![delivery("2026-10-12T08:00:30+00:00", false)];
+
+        // Retried 15 minutes after the first failure, not counted as run
+        assert!(!due(&monday, "2026-10-12T08:10:00Z", &deliveries));
+        assert!(due(&monday, "2026-10-12T08:15:30Z", &deliveries));
+
+        // 30 minutes after the second
+        deliveries.push(delivery("2026-10-12T08:15:30+00:00", false));
+        assert!(!due(&monday, "2026-10-12T08:40:00Z", &deliveries));
+        assert!(due(&monday, "2026-10-12T08:45:30Z", &deliveries));
+
+        // Delivered: done until next Monday
+        deliveries.push(delivery("2026-10-12T08:45:30+00:00", true));
+        assert!(!due(&monday, "2026-10-18T12:00:00Z", &deliveries));
+        assert!(due(&monday, "2026-10-19T08:00:00Z", &deliveries));
+
+        // Deliveries of other schedules do not count
+        let mut other = schedule("weekly", 1, 8);
+        other.id = "client-b".to_string();
+        other.created_at = monday.created_at.clone();
+        assert!(due(&other, "2026-10-18T12:00:00Z", &deliveries));
+    }
+}
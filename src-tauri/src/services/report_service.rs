@@ -0,0 +1,1013 @@
+// services/report_service.rs
+use crate::models::maintenance::UptimeSummary;
+use crate::models::report::{GeneratedReport, ReportRequest, ReportSettings};
+use crate::models::status_page::Incident;
+use crate::models::website::{Website, WebsiteNotes, WebsiteReport};
+use crate::models::wpscan::{Finding, FixItem, ScanRecord};
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::maintenance_service::uptime_summary;
+use crate::services::page_analysis::FetchedPage;
+use crate::services::scan_report::{build_scan_report, severity_rank};
+use crate::services::scan_storage_service::ScanStorageService;
+use crate::services::screenshot_history_service::ScreenshotHistoryService;
+use crate::services::screenshot_service::ScreenshotService;
+use crate::services::security_headers::check_security_headers;
+use crate::services::status_page::{MAX_DAYS, escape_html, find_incidents};
+use crate::services::storage_service::StorageService;
+use base64::encode;
+use chrono::{DateTime, Duration, Utc};
+use headless_chrome::Browser;
+use headless_chrome::protocol::page::PrintToPdfOptions;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("../../resources/report-template.html");
+const REPORT_SCOPES: &[&str] = &["website", "tag"];
+const REPORT_FORMATS: &[&str] = &["html", "pdf"];
+const WEBSITE_START: &str = "<!-- website -->";
+const WEBSITE_END: &str = "<!-- /website -->";
+const MAX_RECOMMENDED_FIXES: usize = 10;
+const CERTIFICATE_WARNING_DAYS: i64 = 30;
+const UPTIME_TARGET: f64 = 99.9;
+
+// Google's thresholds: good up to the first value, poor above the second
+const VITALS: &[(&str, &str, f64, f64)] = &[
+    ("lcp", "Largest Contentful Paint", 2500.0, 4000.0),
+    ("fid", "First Input Delay", 100.0, 300.0),
+    ("cls", "Cumulative Layout Shift", 0.1, 0.25),
+    ("fcp", "First Contentful Paint", 1800.0, 3000.0),
+    ("ttfb", "Time to First Byte", 800.0, 1800.0),
+];
+
+/// Report branding, stored as one JSON file, and the custom templates next to it
+#[derive(Debug)]
+pub struct ReportService {
+    file_path: String,
+    templates_dir: PathBuf,
+    settings: Mutex<ReportSettings>,
+}
+
+impl ReportService {
+    pub fn new(file_path: String, templates_dir: String) -> Self {
+        let settings = fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            file_path,
+            templates_dir: PathBuf::from(templates_dir),
+            settings: Mutex::new(settings),
+        }
+    }
+
+    pub fn get_settings(&self) -> ReportSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn save_settings(&self, settings: ReportSettings) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize report settings: {}", e))?;
+        fs::write(&self.file_path, json)
+            .map_err(|e| format!("Failed to save report settings: {}", e))?;
+
+        *self.settings.lock().unwrap() = settings;
+        Ok(())
+    }
+
+    /// "default" followed by the custom templates
+    pub fn list_templates(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(&self.templates_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+                    .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.insert(0, "default".to_string());
+        names
+    }
+
+    pub fn get_template(&self, name: &str) -> Result<String, String> {
+        if name == "default" {
+            return Ok(DEFAULT_TEMPLATE.to_string());
+        }
+        validate_template_name(name)?;
+        fs::read_to_string(self.templates_dir.join(format!("{}.html", name)))
+            .map_err(|e| format!("Failed to read report template {}: {}", name, e))
+    }
+
+    pub fn save_template(&self, name: &str, content: &str) -> Result<(), String> {
+        if name == "default" {
+            return Err(
+                "The default template cannot be changed; save a copy under another name"
+                    .to_string(),
+            );
+        }
+        validate_template_name(name)?;
+        split_template(content)?;
+
+        fs::create_dir_all(&self.templates_dir)
+            .map_err(|e| format!("Failed to create report template directory: {}", e))?;
+        fs::write(self.templates_dir.join(format!("{}.html", name)), content)
+            .map_err(|e| format!("Failed to save report template: {}", e))
+    }
+}
+
+fn validate_template_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Template names may only use letters, digits, - and _: {}",
+            name
+        ))
+    }
+}
+
+/// The services a report reads from
+pub struct ReportSources<'a> {
+    pub storage: &'a StorageService,
+    pub history: &'a CheckHistoryService,
+    pub scans: &'a ScanStorageService,
+    pub screenshots: &'a ScreenshotHistoryService,
+}
+
+/// Everything a report says about one website
+#[derive(Debug, Clone)]
+pub struct WebsiteReportData {
+    pub website: Website,
+    pub uptime: UptimeSummary,
+    pub average_response_ms: Option<u64>,
+    pub incidents: Vec<Incident>,
+    pub scan: Option<ScanRecord>,
+    pub header_findings: Option<Vec<Finding>>, // None when the site could not be fetched
+    pub screenshot: Option<String>,            // Data URL of the latest capture
+}
+
+pub fn validate_request(request: &ReportRequest) -> Result<(), String> {
+    if !REPORT_SCOPES.contains(&request.scope.as_str()) {
+        return Err(format!("Unknown report scope: {}", request.scope));
+    }
+    if request.target.trim().is_empty() {
+        return Err(format!("Choose a {} for the report", request.scope));
+    }
+    if request.formats.is_empty() {
+        return Err("Choose at least one report format".to_string());
+    }
+    if let Some(format) = request
+        .formats
+        .iter()
+        .find(|format| !REPORT_FORMATS.contains(&format.as_str()))
+    {
+        return Err(format!("Unknown report format: {}", format));
+    }
+    request.period_days.map_or(Ok(()), validate_period)
+}
+
+pub fn validate_period(days: u32) -> Result<(), String> {
+    if days == 0 || days > MAX_DAYS {
+        return Err(format!(
+            "Reports can cover 1 to {} days of check history",
+            MAX_DAYS
+        ));
+    }
+    Ok(())
+}
+
+/// Websites a report covers: one website by id, or every website with a tag
+pub fn select_report_websites(websites: Vec<Website>, scope: &str, target: &str) -> Vec<Website> {
+    websites
+        .into_iter()
+        .filter(|website| match scope {
+            "website" => website.id.to_string() == target,
+            "tag" => website
+                .tags
+                .as_ref()
+                .is_some_and(|tags| tags.iter().any(|tag| tag.eq_ignore_ascii_case(target))),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Generate a report, write it in the requested formats and store the filled-in
+/// `WebsiteReport` in each website's notes
+pub async fn generate_report(
+    service: &ReportService,
+    sources: &ReportSources<'_>,
+    request: &ReportRequest,
+    now: DateTime<Utc>,
+) -> Result<GeneratedReport, String> {
+    let settings = service.get_settings();
+    let period_days = request.period_days.unwrap_or(settings.period_days).max(1);
+    let template = service.get_template(&request.template)?;
+
+    let websites = select_report_websites(
+        sources.storage.get_websites().map_err(|e| e.to_string())?,
+        &request.scope,
+        &request.target,
+    );
+    if websites.is_empty() {
+        return Err(format!(
+            "No websites match {} {}",
+            request.scope, request.target
+        ));
+    }
+
+    let client = reqwest::Client::new();
+    let mut sections = Vec::new();
+    for website in websites {
+        let data = gather_website_data(website, sources, &client, period_days, now).await?;
+        let report = fill_website_report(&data, period_days, now);
+        sources
+            .storage
+            .modify_website(data.website.id, |w| {
+                w.notes.get_or_insert_with(WebsiteNotes::default).report = report.clone();
+            })
+            .map_err(|e| format!("Failed to save report of {}: {}", data.website.name, e))?;
+        sections.push((data, report));
+    }
+
+    let title = match (request.scope.as_str(), sections.as_slice()) {
+        ("website", [(data, _)]) => format!("Website report: {}", data.website.name),
+        _ => {
+            // The tag as it is written on the websites rather than as requested
+            let group = sections
+                .iter()
+                .flat_map(|(data, _)| data.website.tags.iter().flatten())
+                .find(|tag| tag.eq_ignore_ascii_case(&request.target))
+                .unwrap_or(&request.target);
+            format!("Website report: {}", group)
+        }
+    };
+    let html = render_report(&template, &settings, &title, &sections, period_days, now)?;
+
+    let output_dir = PathBuf::from(
+        request
+            .output_dir
+            .as_deref()
+            .unwrap_or(&settings.output_dir),
+    );
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create report directory: {}", e))?;
+    let base_name = format!("{}-{}", slugify(&title), now.format("%Y-%m-%d"));
+    let html_path = output_dir.join(format!("{}.html", base_name));
+    fs::write(&html_path, &html).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    let mut files = Vec::new();
+    if request.formats.iter().any(|format| format == "pdf") {
+        let pdf_path = output_dir.join(format!("{}.pdf", base_name));
+        let source = html_path.clone();
+        let pdf = tokio::task::spawn_blocking(move || print_pdf(&source))
+            .await
+            .map_err(|e| format!("PDF task failed: {}", e))??;
+        fs::write(&pdf_path, pdf).map_err(|e| format!("Failed to write PDF report: {}", e))?;
+        files.push(pdf_path.to_string_lossy().to_string());
+    }
+    // The PDF is printed from the HTML file, which is removed again if not wanted
+    if request.formats.iter().any(|format| format == "html") {
+        files.insert(0, html_path.to_string_lossy().to_string());
+    } else {
+        let _ = fs::remove_file(&html_path);
+    }
+
+    Ok(GeneratedReport {
+        title,
+        generated_at: now.to_rfc3339(),
+        period_days,
+        website_ids: sections.iter().map(|(data, _)| data.website.id).collect(),
+        files,
+    })
+}
+
+async fn gather_website_data(
+    website: Website,
+    sources: &ReportSources<'_>,
+    client: &reqwest::Client,
+    period_days: u32,
+    now: DateTime<Utc>,
+) -> Result<WebsiteReportData, String> {
+    let since = now - Duration::days(period_days as i64);
+    let checks = sources.history.get_history(website.id, Some(since))?;
+    let response_times: Vec<u64> = checks
+        .iter()
+        .filter_map(|check| check.response_time_ms)
+        .collect();
+    let average_response_ms = (!response_times.is_empty())
+        .then(|| response_times.iter().sum::<u64>() / response_times.len() as u64);
+
+    let screenshot = sources
+        .screenshots
+        .get_history(website.id)?
+        .pop()
+        .and_then(|capture| {
+            let data = fs::read(&capture.file_path).ok()?;
+            let format = match Path::new(&capture.file_path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+            {
+                Some("jpg") | Some("jpeg") => "jpeg",
+                Some("webp") => "webp",
+                _ => "png",
+            };
+            Some(format!(
+                "data:{};base64,{}",
+                ScreenshotService::mime_type(format),
+                encode(data)
+            ))
+        });
+
+    let header_findings = match FetchedPage::fetch(client, &website.url).await {
+        Ok(page) => Some(check_security_headers(&page)),
+        Err(e) => {
+            eprintln!("Skipping header checks for {}: {}", website.name, e);
+            None
+        }
+    };
+
+    Ok(WebsiteReportData {
+        uptime: uptime_summary(website.id, period_days, &checks),
+        average_response_ms,
+        incidents: find_incidents(&website, &checks),
+        scan: sources.scans.get_latest(website.id)?,
+        header_findings,
+        screenshot,
+        website,
+    })
+}
+
+fn fix_list(data: &WebsiteReportData) -> Vec<FixItem> {
+    build_scan_report(&[(data.website.clone(), data.scan.clone())]).fix_list
+}
+
+/// Scan hardening findings and response header findings, most severe first
+fn findings(data: &WebsiteReportData) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = data
+        .scan
+        .iter()
+        .flat_map(|scan| scan.result.findings.clone())
+        .chain(data.header_findings.clone().unwrap_or_default())
+        .collect();
+    findings.sort_by_key(|finding| std::cmp::Reverse(severity_rank(&finding.severity)));
+    findings
+}
+
+fn certificate_days(website: &Website, now: DateTime<Utc>) -> Option<i64> {
+    let expires_at =
+        DateTime::parse_from_rfc3339(website.certificate_expires_at.as_deref()?).ok()?;
+    Some((expires_at.to_utc() - now).num_days())
+}
+
+/// (metric key, name, value, rating) of every measured vital
+fn rated_vitals(website: &Website) -> Vec<(&'static str, &'static str, f64, &'static str)> {
+    let Some(vitals) = &website.vitals else {
+        return Vec::new();
+    };
+    VITALS
+        .iter()
+        .filter_map(|(key, name, good, poor)| {
+            let value = match *key {
+                "lcp" => vitals.lcp,
+                "fid" => vitals.fid,
+                "cls" => vitals.cls,
+                "fcp" => vitals.fcp,
+                _ => vitals.ttfb,
+            };
+            // Zero means not measured yet
+            let rating = match value {
+                v if v <= 0.0 => return None,
+                v if v <= *good => "good",
+                v if v <= *poor => "needs-improvement",
+                _ => "poor",
+            };
+            Some((*key, *name, value, rating))
+        })
+        .collect()
+}
+
+fn format_vital(key: &str, value: f64) -> String {
+    match key {
+        "cls" => format!("{:.2}", value),
+        _ if value >= 1000.0 => format!("{:.1} s", value / 1000.0),
+        _ => format!("{:.0} ms", value),
+    }
+}
+
+fn format_uptime(uptime: &UptimeSummary) -> String {
+    uptime
+        .uptime_percent
+        .map(|percent| format!("{:.2}%", percent))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
+/// Write the summary, performance, security and recommendations texts from the data.
+/// Recommendations are one "- " line each.
+pub fn fill_website_report(
+    data: &WebsiteReportData,
+    period_days: u32,
+    now: DateTime<Utc>,
+) -> WebsiteReport {
+    let website = &data.website;
+
+    let mut summary = match data.uptime.uptime_percent {
+        Some(_) => format!(
+            "{} was available {} of the time over the last {} days ({} checks).",
+            website.name,
+            format_uptime(&data.uptime),
+            period_days,
+            data.uptime.checks
+        ),
+        None => format!(
+            "{} was not checked during the last {} days.",
+            website.name, period_days
+        ),
+    };
+    match data.incidents.len() {
+        0 => summary.push_str(" There were no outages."),
+        1 => summary.push_str(" There was 1 outage."),
+        n => {
+            let _ = write!(summary, " There were {} outages.", n);
+        }
+    }
+    if data.uptime.maintenance_checks > 0 {
+        summary.push_str(" Planned maintenance is not counted against availability.");
+    }
+
+    let vitals = rated_vitals(website);
+    let mut performance = match data.average_response_ms {
+        Some(ms) => format!("The average response time was {} ms.", ms),
+        None => "No response times were recorded.".to_string(),
+    };
+    if vitals.is_empty() {
+        performance.push_str(" Core Web Vitals have not been measured yet.");
+    } else {
+        let good = vitals
+            .iter()
+            .filter(|(_, _, _, rating)| *rating == "good")
+            .count();
+        let _ = write!(
+            performance,
+            " {} of {} Core Web Vitals are rated good.",
+            good,
+            vitals.len()
+        );
+    }
+
+    let fixes = fix_list(data);
+    let findings = findings(data);
+    let mut security = match &data.scan {
+        Some(scan) if scan.result.is_wordpress => format!(
+            "WordPress {} was scanned on {} and has {} known {}.",
+            scan.result
+                .wordpress_version
+                .as_deref()
+                .unwrap_or("(unknown version)"),
+            scan.result.scan_date,
+            fixes.len(),
+            if fixes.len() == 1 {
+                "vulnerability"
+            } else {
+                "vulnerabilities"
+            }
+        ),
+        Some(_) => "The last scan found no WordPress installation.".to_string(),
+        None => "The website has not been scanned for vulnerabilities yet.".to_string(),
+    };
+    match &data.header_findings {
+        Some(headers) if headers.is_empty() => {
+            security.push_str(" All recommended security headers are set.")
+        }
+        Some(headers) => {
+            let _ = write!(
+                security,
+                " {} security header issues were found.",
+                headers.len()
+            );
+        }
+        None => security.push_str(" The security headers could not be checked."),
+    }
+    match certificate_days(website, now) {
+        Some(days) if days < 0 => security.push_str(" The TLS certificate has expired."),
+        Some(days) => {
+            let _ = write!(
+                security,
+                " The TLS certificate is valid for {} more days.",
+                days
+            );
+        }
+        None => {}
+    }
+
+    let mut recommendations = Vec::new();
+    if data
+        .uptime
+        .uptime_percent
+        .is_some_and(|percent| percent < UPTIME_TARGET)
+    {
+        recommendations.push(format!(
+            "Find the causes of downtime: availability was {}, below the {}% target.",
+            format_uptime(&data.uptime),
+            UPTIME_TARGET
+        ));
+    }
+    if let Some(days) = certificate_days(website, now).filter(|d| *d < CERTIFICATE_WARNING_DAYS) {
+        recommendations.push(match days {
+            d if d < 0 => "Renew the expired TLS certificate.".to_string(),
+            d => format!("Renew the TLS certificate, which expires in {} days.", d),
+        });
+    }
+    for fix in fixes.iter().take(MAX_RECOMMENDED_FIXES) {
+        let component = match fix.component_type.as_str() {
+            "wordpress" => "WordPress".to_string(),
+            other => format!("the {} {}", other, fix.slug),
+        };
+        recommendations.push(match &fix.fixed_in {
+            Some(version) => format!(
+                "Update {} to {} or later to fix \"{}\" ({}).",
+                component, version, fix.title, fix.severity
+            ),
+            None => format!(
+                "Replace or disable {}: \"{}\" ({}) has no fix yet.",
+                component, fix.title, fix.severity
+            ),
+        });
+    }
+    if fixes.len() > MAX_RECOMMENDED_FIXES {
+        recommendations.push(format!(
+            "Fix the {} remaining vulnerabilities listed in the security section.",
+            fixes.len() - MAX_RECOMMENDED_FIXES
+        ));
+    }
+    recommendations.extend(
+        findings
+            .iter()
+            .filter(|finding| severity_rank(&finding.severity) >= severity_rank("medium"))
+            .map(|finding| format!("{}: {}", finding.title, finding.description)),
+    );
+    recommendations.extend(
+        vitals
+            .iter()
+            .filter(|(_, _, _, rating)| *rating != "good")
+            .map(|(key, name, value, _)| {
+                let good = VITALS
+                    .iter()
+                    .find(|(k, ..)| k == key)
+                    .map(|(_, _, good, _)| *good)
+                    .unwrap_or_default();
+                format!(
+                    "Improve {}: {} (good is up to {}).",
+                    name,
+                    format_vital(key, *value),
+                    format_vital(key, good)
+                )
+            }),
+    );
+
+    WebsiteReport {
+        summary,
+        performance,
+        security,
+        recommendations: if recommendations.is_empty() {
+            "No action needed this period.".to_string()
+        } else {
+            recommendations
+                .iter()
+                .map(|line| format!("- {}", line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        generated_date: now.to_rfc3339(),
+    }
+}
+
+/// (before the website block, the block, after it)
+fn split_template(template: &str) -> Result<(&str, &str, &str), String> {
+    let (head, rest) = template
+        .split_once(WEBSITE_START)
+        .ok_or_else(|| format!("The template needs a {} block", WEBSITE_START))?;
+    let (block, tail) = rest.split_once(WEBSITE_END).ok_or_else(|| {
+        format!(
+            "The template's website block needs a closing {}",
+            WEBSITE_END
+        )
+    })?;
+    Ok((head, block, tail))
+}
+
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |html, (name, value)| {
+            html.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+pub fn render_report(
+    template: &str,
+    settings: &ReportSettings,
+    title: &str,
+    sections: &[(WebsiteReportData, WebsiteReport)],
+    period_days: u32,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    let (head, block, tail) = split_template(template)?;
+
+    let logo = match &settings.logo_path {
+        Some(path) if !path.is_empty() => {
+            let data =
+                fs::read(path).map_err(|e| format!("Failed to read logo {}: {}", path, e))?;
+            let mime = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+                Some("svg") => "image/svg+xml",
+                Some("jpg") | Some("jpeg") => "image/jpeg",
+                _ => "image/png",
+            };
+            format!(
+                "<img class=\"logo\" src=\"data:{};base64,{}\" alt=\"{}\">",
+                mime,
+                encode(data),
+                escape_html(&settings.company_name)
+            )
+        }
+        _ => String::new(),
+    };
+    let report_values = [
+        ("title", escape_html(title)),
+        ("company_name", escape_html(&settings.company_name)),
+        ("logo", logo),
+        ("accent_color", escape_html(&settings.accent_color)),
+        ("generated_date", now.format("%Y-%m-%d").to_string()),
+        ("period_days", period_days.to_string()),
+        ("website_count", sections.len().to_string()),
+        ("footer", escape_html(&settings.footer)),
+        ("contact_email", escape_html(&settings.contact_email)),
+    ];
+
+    let mut html = fill(head, &report_values);
+    for (data, report) in sections {
+        html.push_str(&fill(block, &website_values(data, report, now)));
+    }
+    html.push_str(&fill(tail, &report_values));
+    Ok(html)
+}
+
+fn website_values(
+    data: &WebsiteReportData,
+    report: &WebsiteReport,
+    now: DateTime<Utc>,
+) -> Vec<(&'static str, String)> {
+    let website = &data.website;
+    let screenshot = data
+        .screenshot
+        .as_ref()
+        .map(|url| {
+            format!(
+                "<img class=\"screenshot\" src=\"{}\" alt=\"{}\">",
+                url,
+                escape_html(&website.name)
+            )
+        })
+        .unwrap_or_default();
+
+    let vitals_rows: Vec<Vec<String>> = rated_vitals(website)
+        .into_iter()
+        .map(|(key, name, value, rating)| {
+            vec![
+                name.to_string(),
+                format_vital(key, value),
+                format!(
+                    "<span class=\"{}\">{}</span>",
+                    rating,
+                    rating.replace('-', " ")
+                ),
+            ]
+        })
+        .collect();
+
+    let incident_rows: Vec<Vec<String>> = data
+        .incidents
+        .iter()
+        .rev()
+        .map(|incident| {
+            vec![
+                escape_html(&incident.started_at),
+                incident
+                    .resolved_at
+                    .as_deref()
+                    .map(escape_html)
+                    .unwrap_or_else(|| "ongoing".to_string()),
+                incident
+                    .duration_minutes
+                    .map(|minutes| format!("{} min", minutes))
+                    .unwrap_or_default(),
+                match incident.status {
+                    0 => "no response".to_string(),
+                    status => format!("HTTP {}", status),
+                },
+            ]
+        })
+        .collect();
+
+    let vulnerability_rows: Vec<Vec<String>> = fix_list(data)
+        .iter()
+        .map(|fix| {
+            vec![
+                escape_html(&format!("{} {}", fix.component_type, fix.slug)),
+                escape_html(&fix.title),
+                severity_cell(&fix.severity),
+                escape_html(fix.fixed_in.as_deref().unwrap_or("no fix yet")),
+            ]
+        })
+        .collect();
+
+    let finding_rows: Vec<Vec<String>> = findings(data)
+        .iter()
+        .map(|finding| {
+            vec![
+                escape_html(&finding.title),
+                severity_cell(&finding.severity),
+                escape_html(&finding.description),
+            ]
+        })
+        .collect();
+
+    let recommendations = match report.recommendations.lines().next() {
+        Some(line) if line.starts_with("- ") => format!(
+            "<ul>{}</ul>",
+            report
+                .recommendations
+                .lines()
+                .map(|line| format!("<li>{}</li>", escape_html(line.trim_start_matches("- "))))
+                .collect::<String>()
+        ),
+        _ => format!("<p>{}</p>", escape_html(&report.recommendations)),
+    };
+
+    let certificate = certificate_days(website, now)
+        .map(|days| days.to_string())
+        .unwrap_or_default();
+    vec![
+        ("website.name", escape_html(&website.name)),
+        ("website.url", escape_html(&website.url)),
+        ("website.uptime", format_uptime(&data.uptime)),
+        ("website.certificate_days", certificate),
+        ("website.summary", escape_html(&report.summary)),
+        ("website.performance", escape_html(&report.performance)),
+        ("website.security", escape_html(&report.security)),
+        ("website.recommendations", recommendations),
+        ("website.screenshot", screenshot),
+        (
+            "website.vitals_table",
+            table(
+                &["Metric", "Value", "Rating"],
+                &vitals_rows,
+                "No measurements yet.",
+            ),
+        ),
+        (
+            "website.incidents_table",
+            table(
+                &["Started", "Resolved", "Duration", "Error"],
+                &incident_rows,
+                "No outages.",
+            ),
+        ),
+        (
+            "website.vulnerabilities_table",
+            table(
+                &["Component", "Vulnerability", "Severity", "Fixed in"],
+                &vulnerability_rows,
+                "",
+            ),
+        ),
+        (
+            "website.findings_table",
+            table(&["Issue", "Severity", "Details"], &finding_rows, ""),
+        ),
+    ]
+}
+
+fn severity_cell(severity: &str) -> String {
+    let severity = escape_html(&severity.to_lowercase());
+    format!("<span class=\"{}\">{}</span>", severity, severity)
+}
+
+/// An HTML table of already escaped cells, or the empty text as a paragraph
+fn table(headers: &[&str], rows: &[Vec<String>], empty: &str) -> String {
+    if rows.is_empty() {
+        return match empty {
+            "" => String::new(),
+            text => format!("<p>{}</p>", text),
+        };
+    }
+    let mut html = String::from("<table><tr>");
+    for header in headers {
+        let _ = write!(html, "<th>{}</th>", header);
+    }
+    html.push_str("</tr>");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let _ = write!(html, "<td>{}</td>", cell);
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn slugify(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Print an HTML file to PDF with headless Chrome. Blocks, so run it on a blocking thread.
+pub fn print_pdf(html_path: &Path) -> Result<Vec<u8>, String> {
+    let path = fs::canonicalize(html_path)
+        .map_err(|e| format!("Failed to find report {}: {}", html_path.display(), e))?;
+    let url = reqwest::Url::from_file_path(&path)
+        .map_err(|_| format!("Failed to build file URL for {}", path.display()))?;
+
+    let browser = Browser::default().map_err(|e| format!("Failed to launch browser: {}", e))?;
+    let tab = browser
+        .new_tab()
+        .map_err(|e| format!("Failed to create new tab: {}", e))?;
+    tab.navigate_to(url.as_str())
+        .map_err(|e| format!("Failed to open report: {}", e))?;
+    tab.wait_until_navigated()
+        .map_err(|e| format!("Failed to wait for report: {}", e))?;
+
+    tab.print_to_pdf(Some(PrintToPdfOptions {
+        landscape: None,
+        display_header_footer: None,
+        print_background: Some(true),
+        scale: None,
+        paper_width: None,
+        paper_height: None,
+        margin_top: None,
+        margin_bottom: None,
+        margin_left: None,
+        margin_right: None,
+        page_ranges: None,
+        ignore_invalid_page_ranges: None,
+        header_template: None,
+        footer_template: None,
+        // The template's @page rule sets the paper size and margins
+        prefer_css_page_size: Some(true),
+    }))
+    .map_err(|e| format!("Failed to print report to PDF: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::website::WebVitals;
+    use crate::models::wpscan::{Plugin, Vulnerability, WpscanResult};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T12:00:00+00:00")
+            .unwrap()
+            .to_utc()
+    }
+
+    fn data() -> WebsiteReportData {
+        let website = Website {
+            id: 3,
+            name: "Bakery <Main>".to_string(),
+            url: "https://bakery.example".to_string(),
+            vitals: Some(WebVitals {
+                lcp: 4600.0,
+                cls: 0.05,
+                ..Default::default()
+            }),
+            certificate_expires_at: Some("2026-10-28T12:00:00+00:00".to_string()),
+            ..Default::default()
+        };
+        let vulnerability = Vulnerability {
+            id: "v1".to_string(),
+            title: "Stored XSS".to_string(),
+            description: None,
+            vuln_type: None,
+            severity: Some("high".to_string()),
+            fixed_in: Some("2.1.0".to_string()),
+            references: Vec::new(),
+            cve: None,
+        };
+        let scan = ScanRecord {
+            website_id: 3,
+            result: WpscanResult {
+                url: website.url.clone(),
+                wordpress_version: Some("6.6.2".to_string()),
+                vulnerabilities: Vec::new(),
+                plugins: vec![Plugin {
+                    name: "Contact Form".to_string(),
+                    version: Some("2.0.1".to_string()),
+                    vulnerabilities: vec![vulnerability],
+                    slug: "contact-form".to_string(),
+                }],
+                themes: Vec::new(),
+                users: Vec::new(),
+                scan_date: "2026-10-17".to_string(),
+                is_wordpress: true,
+                api_requests_remaining: None,
+                findings: Vec::new(),
+                aggressive: false,
+            },
+            changes: Vec::new(),
+        };
+
+        WebsiteReportData {
+            uptime: UptimeSummary {
+                website_id: 3,
+                days: 30,
+                checks: 1000,
+                up_checks: 990,
+                maintenance_checks: 0,
+                uptime_percent: Some(99.0),
+            },
+            average_response_ms: Some(420),
+            incidents: Vec::new(),
+            scan: Some(scan),
+            header_findings: Some(vec![Finding {
+                id: "missing_csp".to_string(),
+                title: "Content-Security-Policy header missing".to_string(),
+                severity: "medium".to_string(),
+                description: "No policy.".to_string(),
+                url: None,
+            }]),
+            screenshot: None,
+            website,
+        }
+    }
+
+    #[test]
+    fn fills_report_texts_from_data() {
+        let report = fill_website_report(&data(), 30, now());
+        assert!(
+            report
+                .summary
+                .contains("available 99.00% of the time over the last 30 days")
+        );
+        assert!(
+            report
+                .performance
+                .contains("1 of 2 Core Web Vitals are rated good")
+        );
+        assert!(report.security.contains("WordPress 6.6.2"));
+        assert!(report.security.contains("1 known vulnerability"));
+        assert!(report.security.contains("valid for 10 more days"));
+        assert_eq!(report.generated_date, now().to_rfc3339());
+
+        let lines: Vec<&str> = report.recommendations.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains("below the 99.9% target"));
+        assert!(lines[1].contains("expires in 10 days"));
+        assert!(lines[2].contains("Update the plugin contact-form to 2.1.0"));
+        assert!(lines[3].starts_with("- Content-Security-Policy header missing"));
+        assert!(lines[4].contains("Largest Contentful Paint: 4.6 s (good is up to 2.5 s)"));
+    }
+
+    #[test]
+    fn renders_template_per_website() {
+        let data = data();
+        let report = fill_website_report(&data, 30, now());
+        let settings = ReportSettings {
+            company_name: "Agency & Co".to_string(),
+            ..Default::default()
+        };
+        let sections = [(data.clone(), report.clone()), (data, report)];
+
+        let html = render_report(
+            DEFAULT_TEMPLATE,
+            &settings,
+            "Client A",
+            &sections,
+            30,
+            now(),
+        )
+        .unwrap();
+        assert_eq!(html.matches("<h2>Bakery &lt;Main&gt;</h2>").count(), 2);
+        assert!(html.contains("Agency &amp; Co &middot; 2026-10-18"));
+        assert!(html.contains("<li>Renew the TLS certificate"));
+        assert!(html.contains("<td>plugin contact-form</td>"));
+        assert!(!html.contains("{{"));
+
+        assert!(split_template("<html>{{title}}</html>").is_err());
+        let custom = "<h1>{{title}}</h1><!-- website --><p>{{website.uptime}}</p><!-- /website -->";
+        let html = render_report(custom, &settings, "Client A", &sections[..1], 30, now()).unwrap();
+        assert_eq!(html, "<h1>Client A</h1><p>99.00%</p>");
+    }
+}
@@ -0,0 +1,295 @@
+// services/scan_report.rs
+use crate::models::website::Website;
+use crate::models::wpscan::{
+    AffectedSite, FixItem, PluginUsage, PortfolioScanReport, ScanRecord, SharedVulnerableVersion,
+    Vulnerability,
+};
+use std::collections::BTreeMap;
+
+// Vulnerabilities without a CVSS score are ranked like the security notes file them
+const DEFAULT_SEVERITY: &str = "medium";
+
+// (component type, slug, version)
+type ComponentVersion = (String, String, String);
+
+/// Roll the latest scan of every website up into installs per plugin, vulnerable
+/// versions shared between sites and a fix list ordered by severity, then reach.
+pub fn build_scan_report(sites: &[(Website, Option<ScanRecord>)]) -> PortfolioScanReport {
+    let mut sites_without_scan = Vec::new();
+    let mut plugins: BTreeMap<String, PluginUsage> = BTreeMap::new();
+    let mut versions: BTreeMap<ComponentVersion, (Vec<&Vulnerability>, Vec<AffectedSite>)> =
+        BTreeMap::new();
+    // (type, slug, vulnerability key) -> fix
+    let mut fixes: BTreeMap<(String, String, String), FixItem> = BTreeMap::new();
+
+    let mut sites_scanned = 0;
+    let mut wordpress_sites = 0;
+
+    for (website, record) in sites {
+        let Some(record) = record else {
+            sites_without_scan.push(site(website, None));
+            continue;
+        };
+        sites_scanned += 1;
+        let result = &record.result;
+        if !result.is_wordpress {
+            continue;
+        }
+        wordpress_sites += 1;
+
+        for plugin in &result.plugins {
+            let usage = plugins
+                .entry(plugin.slug.clone())
+                .or_insert_with(|| PluginUsage {
+                    slug: plugin.slug.clone(),
+                    name: plugin.name.clone(),
+                    installs: Vec::new(),
+                    vulnerable_installs: 0,
+                });
+            usage
+                .installs
+                .push(site(website, plugin.version.as_deref()));
+            if !plugin.vulnerabilities.is_empty() {
+                usage.vulnerable_installs += 1;
+            }
+        }
+
+        let components = std::iter::once((
+            "wordpress",
+            "core",
+            &result.wordpress_version,
+            &result.vulnerabilities,
+        ))
+        .chain(
+            result
+                .plugins
+                .iter()
+                .map(|p| ("plugin", p.slug.as_str(), &p.version, &p.vulnerabilities)),
+        )
+        .chain(
+            result
+                .themes
+                .iter()
+                .map(|t| ("theme", t.slug.as_str(), &t.version, &t.vulnerabilities)),
+        );
+
+        for (kind, slug, version, vulnerabilities) in components {
+            if vulnerabilities.is_empty() {
+                continue;
+            }
+            let affected = site(website, version.as_deref());
+
+            // Without a version there is no telling whether two sites run the same code
+            if let Some(version) = version {
+                let entry = versions
+                    .entry((kind.to_string(), slug.to_string(), version.clone()))
+                    .or_default();
+                for vuln in vulnerabilities {
+                    if !entry
+                        .0
+                        .iter()
+                        .any(|v| v.id == vuln.id && v.title == vuln.title)
+                    {
+                        entry.0.push(vuln);
+                    }
+                }
+                entry.1.push(affected.clone());
+            }
+
+            for vuln in vulnerabilities {
+                let key = if vuln.id == "unknown" {
+                    vuln.title.clone()
+                } else {
+                    vuln.id.clone()
+                };
+                fixes
+                    .entry((kind.to_string(), slug.to_string(), key))
+                    .or_insert_with(|| FixItem {
+                        priority: 0,
+                        component_type: kind.to_string(),
+                        slug: slug.to_string(),
+                        vulnerability_id: vuln.id.clone(),
+                        title: vuln.title.clone(),
+                        severity: severity(vuln),
+                        cve: vuln.cve.clone(),
+                        fixed_in: vuln.fixed_in.clone(),
+                        sites: Vec::new(),
+                    })
+                    .sites
+                    .push(affected.clone());
+            }
+        }
+    }
+
+    let mut plugins: Vec<PluginUsage> = plugins.into_values().collect();
+    plugins.sort_by_key(|usage| std::cmp::Reverse(usage.installs.len()));
+
+    let mut shared_vulnerable_versions: Vec<SharedVulnerableVersion> = versions
+        .into_iter()
+        .filter(|(_, (_, sites))| sites.len() > 1)
+        .map(
+            |((component_type, slug, version), (vulnerabilities, sites))| SharedVulnerableVersion {
+                component_type,
+                slug,
+                version,
+                highest_severity: vulnerabilities
+                    .iter()
+                    .map(|v| severity(v))
+                    .max_by_key(|s| severity_rank(s))
+                    .unwrap_or_else(|| DEFAULT_SEVERITY.to_string()),
+                vulnerabilities: vulnerabilities.iter().map(|v| v.title.clone()).collect(),
+                sites,
+            },
+        )
+        .collect();
+    shared_vulnerable_versions.sort_by(|a, b| {
+        severity_rank(&b.highest_severity)
+            .cmp(&severity_rank(&a.highest_severity))
+            .then(b.sites.len().cmp(&a.sites.len()))
+    });
+
+    let mut fix_list: Vec<FixItem> = fixes.into_values().collect();
+    fix_list.sort_by(|a, b| {
+        severity_rank(&b.severity)
+            .cmp(&severity_rank(&a.severity))
+            .then(b.sites.len().cmp(&a.sites.len()))
+    });
+    for (index, fix) in fix_list.iter_mut().enumerate() {
+        fix.priority = index + 1;
+    }
+
+    PortfolioScanReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        sites_scanned,
+        wordpress_sites,
+        sites_without_scan,
+        plugins,
+        shared_vulnerable_versions,
+        fix_list,
+    }
+}
+
+fn site(website: &Website, version: Option<&str>) -> AffectedSite {
+    AffectedSite {
+        website_id: website.id,
+        name: website.name.clone(),
+        version: version.map(str::to_string),
+    }
+}
+
+fn severity(vuln: &Vulnerability) -> String {
+    vuln.severity
+        .as_deref()
+        .unwrap_or(DEFAULT_SEVERITY)
+        .to_lowercase()
+}
+
+pub fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 4,
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wpscan::{Plugin, WpscanResult};
+
+    fn vuln(id: &str, severity: &str) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            title: format!("Vulnerability {}", id),
+            description: None,
+            vuln_type: None,
+            severity: Some(severity.to_string()),
+            fixed_in: Some("9.9".to_string()),
+            references: Vec::new(),
+            cve: None,
+        }
+    }
+
+    fn plugin(slug: &str, version: &str, vulnerabilities: Vec<Vulnerability>) -> Plugin {
+        Plugin {
+            name: slug.to_string(),
+            version: Some(version.to_string()),
+            vulnerabilities,
+            slug: slug.to_string(),
+        }
+    }
+
+    fn scanned(id: i64, plugins: Vec<Plugin>) -> (Website, Option<ScanRecord>) {
+        let website = Website {
+            id,
+            name: format!("Site {}", id),
+            ..Default::default()
+        };
+        let record = ScanRecord {
+            website_id: id,
+            result: WpscanResult {
+                url: website.url.clone(),
+                wordpress_version: Some("6.5".to_string()),
+                vulnerabilities: Vec::new(),
+                plugins,
+                themes: Vec::new(),
+                users: Vec::new(),
+                scan_date: "2026-01-01T00:00:00Z".to_string(),
+                is_wordpress: true,
+                api_requests_remaining: None,
+                findings: Vec::new(),
+                aggressive: false,
+            },
+            changes: Vec::new(),
+        };
+        (website, Some(record))
+    }
+
+    #[test]
+    fn aggregates_installs_shared_versions_and_fixes() {
+        let sites = vec![
+            scanned(
+                1,
+                vec![
+                    plugin("contact-form-7", "5.3", vec![vuln("cf7", "medium")]),
+                    plugin("akismet", "5.0", vec![]),
+                ],
+            ),
+            scanned(
+                2,
+                vec![plugin("contact-form-7", "5.3", vec![vuln("cf7", "medium")])],
+            ),
+            scanned(
+                3,
+                vec![plugin("revslider", "4.1", vec![vuln("rs", "critical")])],
+            ),
+            (
+                Website {
+                    id: 4,
+                    ..Default::default()
+                },
+                None,
+            ),
+        ];
+
+        let report = build_scan_report(&sites);
+
+        assert_eq!(report.sites_scanned, 3);
+        assert_eq!(report.sites_without_scan.len(), 1);
+        assert_eq!(report.plugins[0].slug, "contact-form-7");
+        assert_eq!(report.plugins[0].installs.len(), 2);
+        assert_eq!(report.plugins[0].vulnerable_installs, 2);
+
+        assert_eq!(report.shared_vulnerable_versions.len(), 1);
+        assert_eq!(report.shared_vulnerable_versions[0].version, "5.3");
+        assert_eq!(report.shared_vulnerable_versions[0].sites.len(), 2);
+
+        // Severity first, then the number of affected sites
+        let order: Vec<&str> = report.fix_list.iter().map(|f| f.slug.as_str()).collect();
+        assert_eq!(order, vec!["revslider", "contact-form-7"]);
+        assert_eq!(report.fix_list[0].priority, 1);
+        assert_eq!(report.fix_list[1].sites.len(), 2);
+    }
+}
@@ -0,0 +1,622 @@
+// services/scan_storage_service.rs
+use crate::models::website::{SecurityNotes, SecurityVulnerability, Website, WebsiteNotes};
+use crate::models::wpscan::{ScanChange, ScanRecord, Vulnerability, WpscanResult};
+use crate::services::storage_service::StorageService;
+use crate::services::wpscan_service::WpscanService;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Persists WPScan results per website and tracks what changed between scans
+#[derive(Debug)]
+pub struct ScanStorageService {
+    base_dir: PathBuf,
+}
+
+impl ScanStorageService {
+    pub fn new(base_dir: String) -> Self {
+        Self {
+            base_dir: PathBuf::from(base_dir),
+        }
+    }
+
+    /// Store a scan result and diff it against the previous comparable scan of the website
+    pub fn record_scan(
+        &self,
+        website_id: i64,
+        result: &WpscanResult,
+    ) -> Result<ScanRecord, String> {
+        let mut history = self.get_history(website_id)?;
+
+        let changes = match comparable_scan(&history, result) {
+            Some(previous) => diff_scans(&previous.result, result),
+            None => Vec::new(),
+        };
+
+        let record = ScanRecord {
+            website_id,
+            result: result.clone(),
+            changes,
+        };
+
+        history.push(record.clone());
+
+        fs::create_dir_all(&self.base_dir)
+            .map_err(|e| format!("Failed to create scan directory: {}", e))?;
+        let json = serde_json::to_string_pretty(&history)
+            .map_err(|e| format!("Failed to serialize scan history: {}", e))?;
+        fs::write(self.history_path(website_id), json)
+            .map_err(|e| format!("Failed to save scan history: {}", e))?;
+
+        Ok(record)
+    }
+
+    /// All scans for a website, oldest first
+    pub fn get_history(&self, website_id: i64) -> Result<Vec<ScanRecord>, String> {
+        let path = self.history_path(website_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read scan history: {}", e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse scan history: {}", e))
+    }
+
+    pub fn get_latest(&self, website_id: i64) -> Result<Option<ScanRecord>, String> {
+        Ok(self.get_history(website_id)?.pop())
+    }
+
+    /// The latest stored scan `result` should be compared with, see `comparable_scan`
+    pub fn get_comparable(
+        &self,
+        website_id: i64,
+        result: &WpscanResult,
+    ) -> Result<Option<ScanRecord>, String> {
+        Ok(comparable_scan(&self.get_history(website_id)?, result).cloned())
+    }
+
+    fn history_path(&self, website_id: i64) -> PathBuf {
+        self.base_dir.join(format!("{}.json", website_id))
+    }
+}
+
+/// The latest scan made the same way as `result`. A passive scan cannot see plugins
+/// that only wordlist probing found, so comparing across modes would report them removed.
+fn comparable_scan<'a>(history: &'a [ScanRecord], result: &WpscanResult) -> Option<&'a ScanRecord> {
+    history
+        .iter()
+        .rev()
+        .find(|record| record.result.aggressive == result.aggressive)
+}
+
+/// Every vulnerability in a scan keyed by a stable id, with the component it affects
+fn vulnerabilities_by_key(result: &WpscanResult) -> BTreeMap<String, (String, &Vulnerability)> {
+    let mut found = BTreeMap::new();
+
+    let core = format!(
+        "WordPress {}",
+        result.wordpress_version.as_deref().unwrap_or("core")
+    );
+    let components = std::iter::once(("wordpress", core, &result.vulnerabilities))
+        .chain(
+            result
+                .plugins
+                .iter()
+                .map(|p| ("plugin", p.slug.clone(), &p.vulnerabilities)),
+        )
+        .chain(
+            result
+                .themes
+                .iter()
+                .map(|t| ("theme", t.slug.clone(), &t.vulnerabilities)),
+        );
+
+    for (kind, component, vulnerabilities) in components {
+        for vuln in vulnerabilities {
+            let id = if vuln.id == "unknown" {
+                &vuln.title
+            } else {
+                &vuln.id
+            };
+            let slug = if kind == "wordpress" {
+                "core"
+            } else {
+                &component
+            };
+            found.insert(
+                format!("wpscan:{}:{}:{}", kind, slug, id),
+                (component.clone(), vuln),
+            );
+        }
+    }
+
+    found
+}
+
+fn diff_components(
+    kind: &str,
+    previous: BTreeMap<&str, Option<&str>>,
+    current: BTreeMap<&str, Option<&str>>,
+    changes: &mut Vec<ScanChange>,
+) {
+    for (slug, version) in &current {
+        match previous.get(slug) {
+            None => changes.push(ScanChange {
+                change_type: format!("{}_added", kind),
+                component: slug.to_string(),
+                from: None,
+                to: version.map(str::to_string),
+                description: format!("New {} {} detected", kind, slug),
+            }),
+            Some(old) if old != version => changes.push(ScanChange {
+                change_type: format!("{}_updated", kind),
+                component: slug.to_string(),
+                from: old.map(str::to_string),
+                to: version.map(str::to_string),
+                description: format!(
+                    "{} {} changed from {} to {}",
+                    kind,
+                    slug,
+                    old.unwrap_or("unknown"),
+                    version.unwrap_or("unknown")
+                ),
+            }),
+            _ => {}
+        }
+    }
+
+    for (slug, version) in &previous {
+        if !current.contains_key(slug) {
+            changes.push(ScanChange {
+                change_type: format!("{}_removed", kind),
+                component: slug.to_string(),
+                from: version.map(str::to_string),
+                to: None,
+                description: format!("{} {} is no longer detected", kind, slug),
+            });
+        }
+    }
+}
+
+/// Everything that changed between two scans of the same site
+pub fn diff_scans(previous: &WpscanResult, current: &WpscanResult) -> Vec<ScanChange> {
+    let mut changes = Vec::new();
+
+    if previous.wordpress_version != current.wordpress_version {
+        changes.push(ScanChange {
+            change_type: "core_updated".to_string(),
+            component: "wordpress".to_string(),
+            from: previous.wordpress_version.clone(),
+            to: current.wordpress_version.clone(),
+            description: format!(
+                "WordPress changed from {} to {}",
+                previous.wordpress_version.as_deref().unwrap_or("unknown"),
+                current.wordpress_version.as_deref().unwrap_or("unknown")
+            ),
+        });
+    }
+
+    diff_components(
+        "plugin",
+        previous
+            .plugins
+            .iter()
+            .map(|p| (p.slug.as_str(), p.version.as_deref()))
+            .collect(),
+        current
+            .plugins
+            .iter()
+            .map(|p| (p.slug.as_str(), p.version.as_deref()))
+            .collect(),
+        &mut changes,
+    );
+    diff_components(
+        "theme",
+        previous
+            .themes
+            .iter()
+            .map(|t| (t.slug.as_str(), t.version.as_deref()))
+            .collect(),
+        current
+            .themes
+            .iter()
+            .map(|t| (t.slug.as_str(), t.version.as_deref()))
+            .collect(),
+        &mut changes,
+    );
+
+    let previous_vulns = vulnerabilities_by_key(previous);
+    let current_vulns = vulnerabilities_by_key(current);
+
+    for (key, (component, vuln)) in &current_vulns {
+        if !previous_vulns.contains_key(key) {
+            changes.push(ScanChange {
+                change_type: "vulnerability_new".to_string(),
+                component: component.clone(),
+                from: None,
+                to: vuln.fixed_in.clone(),
+                description: format!("{}: {}", component, vuln.title),
+            });
+        }
+    }
+
+    for (key, (component, vuln)) in &previous_vulns {
+        if !current_vulns.contains_key(key) {
+            changes.push(ScanChange {
+                change_type: "vulnerability_fixed".to_string(),
+                component: component.clone(),
+                from: None,
+                to: None,
+                description: format!("{}: {}", component, vuln.title),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Mirror a scan into the website's security notes: vulnerabilities found are
+/// opened (or reopened), scan-maintained ones that disappeared since the `previous`
+/// comparable scan are marked fixed. Entries added by hand are never touched.
+pub fn sync_security_notes(
+    security: &mut SecurityNotes,
+    record: &ScanRecord,
+    previous: Option<&WpscanResult>,
+) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let current = vulnerabilities_by_key(&record.result);
+    let before = previous.map(vulnerabilities_by_key).unwrap_or_default();
+
+    for (key, (component, vuln)) in &current {
+        match security
+            .vulnerabilities
+            .iter_mut()
+            .find(|v| v.scan_id.as_deref() == Some(key.as_str()))
+        {
+            Some(existing) => {
+                if existing.status == "fixed" {
+                    existing.status = "open".to_string();
+                }
+            }
+            None => security.vulnerabilities.push(SecurityVulnerability {
+                name: format!("{}: {}", component, vuln.title),
+                severity: vuln.severity.as_deref().unwrap_or("medium").to_lowercase(),
+                description: vuln
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| match &vuln.fixed_in {
+                        Some(version) => format!("Fixed in {}", version),
+                        None => "No fix available yet".to_string(),
+                    }),
+                status: "open".to_string(),
+                discovered: now.clone(),
+                scan_id: Some(key.clone()),
+            }),
+        }
+    }
+
+    for existing in security.vulnerabilities.iter_mut() {
+        if existing
+            .scan_id
+            .as_ref()
+            .is_some_and(|scan_id| before.contains_key(scan_id) && !current.contains_key(scan_id))
+        {
+            existing.status = "fixed".to_string();
+        }
+    }
+
+    let new_count = record
+        .changes
+        .iter()
+        .filter(|c| c.change_type == "vulnerability_new")
+        .count();
+    let fixed_count = record
+        .changes
+        .iter()
+        .filter(|c| c.change_type == "vulnerability_fixed")
+        .count();
+    security.security_scan_results = format!(
+        "WPScan {}: {} vulnerabilities ({} new, {} fixed), {} plugins, {} themes",
+        record.result.scan_date,
+        current.len(),
+        new_count,
+        fixed_count,
+        record.result.plugins.len(),
+        record.result.themes.len()
+    );
+}
+
+/// Scan a website and store the result, returning the updated website and scan record
+/// when it is stored. A scan that did not complete is an error and nothing is stored:
+/// it would mark every open vulnerability as fixed.
+pub async fn scan_stored_website(
+    wpscan: &WpscanService,
+    storage: &StorageService,
+    scans: &ScanStorageService,
+    website: &Website,
+) -> Result<(WpscanResult, Option<(Website, ScanRecord)>), String> {
+    let mut result = wpscan.scan_website(&website.url).await?;
+    let stored = apply_scan(storage, scans, website.id, &mut result)?;
+    Ok((result, stored))
+}
+
+/// Store a scan, without alerts: the updated website and the new scan record, or `None`
+/// if the website is not stored. The result is normalized first, the same way for every
+/// kind of scan, so switching between single and bulk scans does not show up as changes.
+pub fn apply_scan(
+    storage: &StorageService,
+    scans: &ScanStorageService,
+    website_id: i64,
+    result: &mut WpscanResult,
+) -> Result<Option<(Website, ScanRecord)>, String> {
+    let websites = storage.get_websites().map_err(|e| e.to_string())?;
+    let Some(website) = websites.iter().find(|w| w.id == website_id) else {
+        return Ok(None);
+    };
+
+    result.url = website.url.clone();
+    if result.wordpress_version.is_none() {
+        result.wordpress_version = Some("Unknown".to_string());
+    }
+
+    let previous = scans.get_comparable(website_id, result)?;
+    let record = scans.record_scan(website_id, result)?;
+    if !record.changes.is_empty() {
+        eprintln!(
+            "Scan of website {} found {} changes since the last scan",
+            website_id,
+            record.changes.len()
+        );
+    }
+
+    let updated = storage
+        .modify_website(website_id, |w| {
+            w.is_wordpress = Some(result.is_wordpress);
+            let notes = w.notes.get_or_insert_with(WebsiteNotes::default);
+            sync_security_notes(
+                &mut notes.security,
+                &record,
+                previous.as_ref().map(|p| &p.result),
+            );
+            notes.last_updated = chrono::Utc::now().to_rfc3339();
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(updated.map(|website| (website, record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wpscan::{Plugin, WpscanOptions};
+
+    fn vuln(id: &str, title: &str) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: None,
+            vuln_type: None,
+            severity: Some("High".to_string()),
+            fixed_in: Some("2.0".to_string()),
+            references: Vec::new(),
+            cve: None,
+        }
+    }
+
+    fn scan(plugins: Vec<Plugin>) -> WpscanResult {
+        WpscanResult {
+            url: "https://example.com".to_string(),
+            wordpress_version: Some("6.4".to_string()),
+            vulnerabilities: Vec::new(),
+            plugins,
+            themes: Vec::new(),
+            users: Vec::new(),
+            scan_date: "2026-01-01T00:00:00Z".to_string(),
+            is_wordpress: true,
+            api_requests_remaining: None,
+            findings: Vec::new(),
+            aggressive: false,
+        }
+    }
+
+    fn plugin(slug: &str, version: &str, vulnerabilities: Vec<Vulnerability>) -> Plugin {
+        Plugin {
+            name: slug.to_string(),
+            version: Some(version.to_string()),
+            vulnerabilities,
+            slug: slug.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_plugin_and_vulnerability_changes() {
+        let previous = scan(vec![
+            plugin("akismet", "1.0", vec![vuln("a1", "XSS")]),
+            plugin("hello-dolly", "1.6", vec![]),
+        ]);
+        let current = scan(vec![
+            plugin("akismet", "2.0", vec![]),
+            plugin("contact-form-7", "5.0", vec![vuln("c1", "SQLi")]),
+        ]);
+
+        let types: Vec<String> = diff_scans(&previous, &current)
+            .into_iter()
+            .map(|c| format!("{}:{}", c.change_type, c.component))
+            .collect();
+
+        assert!(types.contains(&"plugin_updated:akismet".to_string()));
+        assert!(types.contains(&"plugin_added:contact-form-7".to_string()));
+        assert!(types.contains(&"plugin_removed:hello-dolly".to_string()));
+        assert!(types.contains(&"vulnerability_new:contact-form-7".to_string()));
+        assert!(types.contains(&"vulnerability_fixed:akismet".to_string()));
+    }
+
+    #[test]
+    fn security_notes_track_open_and_fixed() {
+        let mut security = SecurityNotes::default();
+        security.vulnerabilities.push(SecurityVulnerability {
+            name: "Weak admin password".to_string(),
+            severity: "high".to_string(),
+            description: String::new(),
+            status: "open".to_string(),
+            discovered: String::new(),
+            scan_id: None,
+        });
+
+        let first = ScanRecord {
+            website_id: 1,
+            result: scan(vec![plugin("akismet", "1.0", vec![vuln("a1", "XSS")])]),
+            changes: Vec::new(),
+        };
+        sync_security_notes(&mut security, &first, None);
+        assert_eq!(security.vulnerabilities.len(), 2);
+        assert_eq!(security.vulnerabilities[1].status, "open");
+        assert_eq!(security.vulnerabilities[1].severity, "high");
+
+        let second = ScanRecord {
+            website_id: 1,
+            result: scan(vec![plugin("akismet", "2.0", vec![])]),
+            changes: Vec::new(),
+        };
+        sync_security_notes(&mut security, &second, Some(&first.result));
+        assert_eq!(security.vulnerabilities[0].status, "open");
+        assert_eq!(security.vulnerabilities[1].status, "fixed");
+    }
+
+    #[test]
+    fn passive_scans_are_not_compared_with_aggressive_ones() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-scans-modes-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let scans = ScanStorageService::new(dir.to_string_lossy().to_string());
+        let mut security = SecurityNotes::default();
+
+        let passive = scan(vec![plugin("akismet", "1.0", vec![])]);
+        let aggressive = WpscanResult {
+            aggressive: true,
+            ..scan(vec![
+                plugin("akismet", "1.0", vec![]),
+                plugin("wp-file-manager", "6.0", vec![vuln("f1", "RCE")]),
+            ])
+        };
+
+        for result in [&passive, &aggressive, &passive] {
+            let previous = scans.get_comparable(1, result).unwrap();
+            let record = scans.record_scan(1, result).unwrap();
+            sync_security_notes(&mut security, &record, previous.as_ref().map(|p| &p.result));
+            assert!(record.changes.is_empty(), "{:?}", record.changes);
+        }
+
+        // Only probing finds the plugin, so the passive scan cannot tell it was fixed
+        assert_eq!(security.vulnerabilities.len(), 1);
+        assert_eq!(security.vulnerabilities[0].status, "open");
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn unreachable_site_leaves_scan_history_and_notes_untouched() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-scan-unreachable-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let storage = StorageService::new(dir.join("websites.json").to_string_lossy().to_string());
+        let scans = ScanStorageService::new(dir.join("scans").to_string_lossy().to_string());
+
+        let mut notes = WebsiteNotes::default();
+        notes.security.vulnerabilities.push(SecurityVulnerability {
+            name: "akismet: XSS".to_string(),
+            severity: "high".to_string(),
+            description: String::new(),
+            status: "open".to_string(),
+            discovered: String::new(),
+            scan_id: Some("wpscan:plugin:akismet:a1".to_string()),
+        });
+        let website = Website {
+            id: 1,
+            // Nothing listens on the discard port
+            url: "http://127.0.0.1:9".to_string(),
+            is_wordpress: Some(true),
+            notes: Some(notes),
+            ..Website::default()
+        };
+        storage
+            .save_websites(std::slice::from_ref(&website))
+            .unwrap();
+
+        let options = WpscanOptions {
+            offline_db_path: Some(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/wpscan_vulndb.json"
+                )
+                .to_string(),
+            ),
+            ..WpscanOptions::default()
+        };
+        let wpscan = WpscanService::with_options(None, &options).unwrap();
+
+        assert!(
+            scan_stored_website(&wpscan, &storage, &scans, &website)
+                .await
+                .is_err()
+        );
+
+        let stored = storage.get_websites().unwrap().remove(0);
+        assert_eq!(stored.is_wordpress, Some(true));
+        assert_eq!(
+            stored.notes.unwrap().security.vulnerabilities[0].status,
+            "open"
+        );
+        assert!(scans.get_history(1).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn scans_are_stored_the_same_way_from_every_path() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-scan-normalize-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let storage = StorageService::new(dir.join("websites.json").to_string_lossy().to_string());
+        let scans = ScanStorageService::new(dir.join("scans").to_string_lossy().to_string());
+        let website = Website {
+            id: 1,
+            url: "https://shop.example".to_string(),
+            ..Website::default()
+        };
+        storage
+            .save_websites(std::slice::from_ref(&website))
+            .unwrap();
+
+        let scan = |wordpress_version: Option<&str>| WpscanResult {
+            url: "https://shop.example/".to_string(),
+            wordpress_version: wordpress_version.map(str::to_string),
+            vulnerabilities: Vec::new(),
+            plugins: Vec::new(),
+            themes: Vec::new(),
+            users: Vec::new(),
+            scan_date: chrono::Utc::now().to_rfc3339(),
+            is_wordpress: true,
+            api_requests_remaining: None,
+            findings: Vec::new(),
+            aggressive: false,
+        };
+
+        let mut first = scan(None);
+        apply_scan(&storage, &scans, 1, &mut first).unwrap();
+        assert_eq!(first.url, website.url);
+        assert_eq!(first.wordpress_version.as_deref(), Some("Unknown"));
+
+        let (_, record) = apply_scan(&storage, &scans, 1, &mut scan(Some("Unknown")))
+            .unwrap()
+            .unwrap();
+        assert!(record.changes.is_empty(), "{:?}", record.changes);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
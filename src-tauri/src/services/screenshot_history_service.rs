@@ -0,0 +1,398 @@
+// services/screenshot_history_service.rs
+use crate::models::screenshot::{ScreenshotCapture, ScreenshotDiff};
+use crate::services::file_lock::lock_file;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::fs::{self, File};
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+
+// A capture is flagged when more than 5% of its pixels changed...
+pub const DEFAULT_CHANGE_THRESHOLD: f64 = 0.05;
+// ...or when the perceptual hashes are this far apart (out of 64 bits)
+pub const DEFAULT_PERCEPTUAL_THRESHOLD: u32 = 10;
+// Per-channel difference below which pixels are treated as unchanged (antialiasing noise)
+const PIXEL_TOLERANCE: u8 = 32;
+// Captures kept per website; older ones are deleted with their image files
+pub const DEFAULT_MAX_CAPTURES: usize = 100;
+
+#[derive(Debug)]
+pub struct ScreenshotHistoryService {
+    base_dir: PathBuf,
+    change_threshold: f64,
+    perceptual_threshold: u32,
+    max_captures: usize,
+}
+
+impl ScreenshotHistoryService {
+    pub fn new(base_dir: String) -> Self {
+        Self {
+            base_dir: PathBuf::from(base_dir),
+            change_threshold: DEFAULT_CHANGE_THRESHOLD,
+            perceptual_threshold: DEFAULT_PERCEPTUAL_THRESHOLD,
+            max_captures: DEFAULT_MAX_CAPTURES,
+        }
+    }
+
+    /// Store a new capture for a website and diff it against the previous one. Captures
+    /// of one website are recorded one at a time, by this process or any other, so none
+    /// is lost from its history.
+    pub fn record_capture(
+        &self,
+        website_id: i64,
+        image_data: &[u8],
+    ) -> Result<ScreenshotCapture, String> {
+        let image = image::load_from_memory(image_data)
+            .map_err(|e| format!("Failed to decode screenshot: {}", e))?;
+        let extension = image::guess_format(image_data)
+            .ok()
+            .and_then(|format| format.extensions_str().first().copied())
+            .unwrap_or("png");
+
+        let site_dir = self.site_dir(website_id);
+        fs::create_dir_all(&site_dir)
+            .map_err(|e| format!("Failed to create screenshot directory: {}", e))?;
+
+        let index_path = site_dir.join("history.json");
+        let _lock = lock_file(&index_path)
+            .map_err(|e| format!("Failed to lock screenshot history: {}", e))?;
+        let mut history = self.get_history(website_id)?;
+
+        // Captures made within the same millisecond are numbered
+        let now = chrono::Utc::now();
+        let stamp = now.format("%Y%m%d_%H%M%S%3f").to_string();
+        let file_name = |id: &str| site_dir.join(format!("{}.{}", id, extension));
+        let mut id = stamp.clone();
+        let mut n = 0;
+        while file_name(&id).exists() || history.iter().any(|capture| capture.id == id) {
+            n += 1;
+            id = format!("{}_{}", stamp, n);
+        }
+        let file_path = file_name(&id);
+        fs::write(&file_path, image_data)
+            .map_err(|e| format!("Failed to write screenshot: {}", e))?;
+
+        let diff = match history.last() {
+            Some(previous) => match self.load_image(previous) {
+                Ok(previous_image) => Some(self.diff_images(&previous.id, &previous_image, &image)),
+                Err(e) => {
+                    eprintln!("Skipping diff for website {}: {}", website_id, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let capture = ScreenshotCapture {
+            id,
+            website_id,
+            captured_at: now.to_rfc3339(),
+            file_path: file_path.to_string_lossy().to_string(),
+            width: image.width(),
+            height: image.height(),
+            diff,
+        };
+
+        history.push(capture.clone());
+        let overflow = history.len().saturating_sub(self.max_captures);
+        let expired: Vec<ScreenshotCapture> = history.drain(..overflow).collect();
+        self.save_history(website_id, &history)?;
+
+        // Only once the history no longer points at them
+        for capture in expired {
+            if let Err(e) = fs::remove_file(&capture.file_path) {
+                eprintln!("Failed to delete screenshot {}: {}", capture.file_path, e);
+            }
+        }
+
+        Ok(capture)
+    }
+
+    /// All captures for a website, oldest first
+    pub fn get_history(&self, website_id: i64) -> Result<Vec<ScreenshotCapture>, String> {
+        let index_path = self.site_dir(website_id).join("history.json");
+        if !index_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&index_path)
+            .map_err(|e| format!("Failed to read screenshot history: {}", e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse screenshot history: {}", e))
+    }
+
+    pub fn get_capture(
+        &self,
+        website_id: i64,
+        capture_id: &str,
+    ) -> Result<ScreenshotCapture, String> {
+        self.get_history(website_id)?
+            .into_iter()
+            .find(|c| c.id == capture_id)
+            .ok_or_else(|| format!("Screenshot {} not found", capture_id))
+    }
+
+    /// Render a PNG highlighting in red the pixels that changed between two captures
+    pub fn diff_overlay(
+        &self,
+        website_id: i64,
+        from_id: &str,
+        to_id: &str,
+    ) -> Result<(ScreenshotDiff, Vec<u8>), String> {
+        let from_image = self.load_image(&self.get_capture(website_id, from_id)?)?;
+        let to_image = self.load_image(&self.get_capture(website_id, to_id)?)?;
+
+        let diff = self.diff_images(from_id, &from_image, &to_image);
+        let overlay = render_overlay(&from_image.to_rgba8(), &to_image.to_rgba8());
+
+        let mut png = Vec::new();
+        DynamicImage::ImageRgba8(overlay)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .map_err(|e| format!("Failed to encode diff overlay: {}", e))?;
+
+        Ok((diff, png))
+    }
+
+    fn diff_images(
+        &self,
+        previous_id: &str,
+        previous: &DynamicImage,
+        current: &DynamicImage,
+    ) -> ScreenshotDiff {
+        let changed_ratio = changed_pixel_ratio(&previous.to_rgba8(), &current.to_rgba8());
+        let perceptual_distance = (dhash(previous) ^ dhash(current)).count_ones();
+
+        ScreenshotDiff {
+            previous_id: previous_id.to_string(),
+            changed_ratio,
+            perceptual_distance,
+            significant: changed_ratio >= self.change_threshold
+                || perceptual_distance >= self.perceptual_threshold,
+        }
+    }
+
+    fn load_image(&self, capture: &ScreenshotCapture) -> Result<DynamicImage, String> {
+        let data = fs::read(&capture.file_path)
+            .map_err(|e| format!("Failed to read screenshot {}: {}", capture.id, e))?;
+        image::load_from_memory(&data)
+            .map_err(|e| format!("Failed to decode screenshot {}: {}", capture.id, e))
+    }
+
+    /// Written to a temporary file and renamed over the index, so the history is never
+    /// read half-written
+    fn save_history(&self, website_id: i64, history: &[ScreenshotCapture]) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(history)
+            .map_err(|e| format!("Failed to serialize screenshot history: {}", e))?;
+        let index_path = self.site_dir(website_id).join("history.json");
+        let temp_path = index_path.with_extension(format!("{}.tmp", std::process::id()));
+
+        File::create(&temp_path)
+            .and_then(|mut file| file.write_all(json.as_bytes()))
+            .and_then(|_| fs::rename(&temp_path, &index_path))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                format!("Failed to save screenshot history: {}", e)
+            })
+    }
+
+    fn site_dir(&self, website_id: i64) -> PathBuf {
+        self.base_dir.join(website_id.to_string())
+    }
+}
+
+fn pixel_changed(a: &Rgba<u8>, b: &Rgba<u8>) -> bool {
+    a.0.iter()
+        .zip(b.0.iter())
+        .any(|(x, y)| x.abs_diff(*y) > PIXEL_TOLERANCE)
+}
+
+/// Fraction of changed pixels; area outside the overlap of the two images counts as changed
+fn changed_pixel_ratio(previous: &RgbaImage, current: &RgbaImage) -> f64 {
+    let width = previous.width().min(current.width());
+    let height = previous.height().min(current.height());
+    let union_area = (previous.width().max(current.width()) as u64)
+        * (previous.height().max(current.height()) as u64);
+
+    if union_area == 0 {
+        return 0.0;
+    }
+
+    let mut changed = union_area - (width as u64 * height as u64);
+    for y in 0..height {
+        for x in 0..width {
+            if pixel_changed(previous.get_pixel(x, y), current.get_pixel(x, y)) {
+                changed += 1;
+            }
+        }
+    }
+
+    changed as f64 / union_area as f64
+}
+
+/// 64-bit difference hash: robust to scaling and small rendering noise
+fn dhash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y).0[0] > small.get_pixel(x + 1, y).0[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Faded copy of the newer capture with changed pixels painted red
+fn render_overlay(previous: &RgbaImage, current: &RgbaImage) -> RgbaImage {
+    let mut overlay = RgbaImage::new(current.width(), current.height());
+
+    for (x, y, pixel) in current.enumerate_pixels() {
+        let changed = if x < previous.width() && y < previous.height() {
+            pixel_changed(previous.get_pixel(x, y), pixel)
+        } else {
+            true
+        };
+
+        let out = if changed {
+            Rgba([255, 0, 0, 255])
+        } else {
+            let [r, g, b, _] = pixel.0;
+            Rgba([(r / 3) + 170, (g / 3) + 170, (b / 3) + 170, 255])
+        };
+        overlay.put_pixel(x, y, out);
+    }
+
+    overlay
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn solid(width: u32, height: u32, color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba(color))
+    }
+
+    fn encode(image: RgbaImage) -> Vec<u8> {
+        let mut png = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        png
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "whb-{}-{}",
+            name,
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ))
+    }
+
+    #[test]
+    fn identical_images_have_no_changes() {
+        let a = solid(20, 20, [10, 20, 30, 255]);
+        assert_eq!(changed_pixel_ratio(&a, &a.clone()), 0.0);
+    }
+
+    #[test]
+    fn changed_region_is_measured() {
+        let a = solid(10, 10, [255, 255, 255, 255]);
+        let mut b = a.clone();
+        for x in 0..10 {
+            b.put_pixel(x, 0, Rgba([0, 0, 0, 255]));
+        }
+        assert!((changed_pixel_ratio(&a, &b) - 0.1).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn size_difference_counts_as_change() {
+        let a = solid(10, 10, [255, 255, 255, 255]);
+        let b = solid(10, 20, [255, 255, 255, 255]);
+        assert!((changed_pixel_ratio(&a, &b) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn records_history_and_flags_defacement() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-screenshots-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let service = ScreenshotHistoryService::new(dir.to_string_lossy().to_string());
+
+        let first = service
+            .record_capture(1, &encode(solid(32, 32, [255, 255, 255, 255])))
+            .unwrap();
+        assert!(first.diff.is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = service
+            .record_capture(1, &encode(solid(32, 32, [0, 0, 0, 255])))
+            .unwrap();
+        let diff = second.diff.expect("second capture should be diffed");
+        assert_eq!(diff.previous_id, first.id);
+        assert!(diff.significant);
+
+        assert_eq!(service.get_history(1).unwrap().len(), 2);
+        assert!(service.diff_overlay(1, &first.id, &second.id).is_ok());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn keeps_only_the_newest_captures() {
+        let dir = temp_dir("screenshots-retention");
+        let mut service = ScreenshotHistoryService::new(dir.to_string_lossy().to_string());
+        service.max_captures = 3;
+
+        // Back to back, so several land in the same millisecond
+        let captures: Vec<ScreenshotCapture> = (0..5)
+            .map(|shade| {
+                service
+                    .record_capture(1, &encode(solid(8, 8, [shade * 50, 0, 0, 255])))
+                    .unwrap()
+            })
+            .collect();
+
+        let kept: Vec<String> = service
+            .get_history(1)
+            .unwrap()
+            .into_iter()
+            .map(|capture| capture.id)
+            .collect();
+        let newest: Vec<String> = captures[2..].iter().map(|c| c.id.clone()).collect();
+        assert_eq!(kept, newest);
+        for capture in &captures[..2] {
+            assert!(!Path::new(&capture.file_path).exists());
+        }
+        for capture in &captures[2..] {
+            assert!(Path::new(&capture.file_path).exists());
+        }
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn concurrent_captures_of_a_website_are_all_kept() {
+        let dir = temp_dir("screenshots-concurrent");
+        let service = ScreenshotHistoryService::new(dir.to_string_lossy().to_string());
+        let image = encode(solid(8, 8, [255, 255, 255, 255]));
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| service.record_capture(1, &image).unwrap());
+            }
+        });
+
+        let history = service.get_history(1).unwrap();
+        let ids: std::collections::HashSet<&str> =
+            history.iter().map(|capture| capture.id.as_str()).collect();
+        assert_eq!(history.len(), 8);
+        assert_eq!(ids.len(), 8);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,229 @@
+// services/screenshot_service.rs
+use crate::models::screenshot::ScreenshotOptions;
+use headless_chrome::protocol::Method;
+use headless_chrome::protocol::page::Viewport;
+use headless_chrome::{Browser, Tab};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Chrome refuses to capture surfaces taller than this
+const MAX_CAPTURE_HEIGHT: f64 = 16384.0;
+// The network counts as idle once no resource has finished loading for this long
+const NETWORK_IDLE_WINDOW: Duration = Duration::from_millis(500);
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct ScreenshotService;
+
+impl ScreenshotService {
+    /// Navigate a fresh tab to `url` and capture it according to `options`.
+    /// Blocks on the DevTools connection, so run it on a blocking thread.
+    pub fn capture(
+        browser: &Browser,
+        url: &str,
+        options: &ScreenshotOptions,
+    ) -> Result<Vec<u8>, String> {
+        Self::validate(options)?;
+
+        let tab = browser
+            .new_tab()
+            .map_err(|e| format!("Failed to create new tab: {}", e))?;
+
+        tab.set_default_timeout(Duration::from_secs(30));
+
+        // Closed whatever happened, or the shared browser piles up tabs of failed sites
+        let result = Self::load_and_capture(&tab, url, options);
+        let _ = tab.call_method(ClosePage {});
+        result
+    }
+
+    fn load_and_capture(
+        tab: &Arc<Tab>,
+        url: &str,
+        options: &ScreenshotOptions,
+    ) -> Result<Vec<u8>, String> {
+        tab.navigate_to(url)
+            .map_err(|e| format!("Failed to navigate to URL: {}", e))?;
+
+        tab.wait_until_navigated()
+            .map_err(|e| format!("Failed to wait for navigation: {}", e))?;
+
+        Self::wait_for_network_idle(tab, Duration::from_millis(options.settle_timeout_ms));
+
+        Self::capture_tab(tab, options)
+    }
+
+    pub fn validate(options: &ScreenshotOptions) -> Result<(), String> {
+        match options.format.as_str() {
+            "png" | "jpeg" | "webp" => {}
+            other => return Err(format!("Unsupported screenshot format: {}", other)),
+        }
+
+        if options.quality.is_some_and(|q| q > 100) {
+            return Err("Screenshot quality must be between 0 and 100".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn mime_type(format: &str) -> &'static str {
+        match format {
+            "jpeg" => "image/jpeg",
+            "webp" => "image/webp",
+            _ => "image/png",
+        }
+    }
+
+    /// Wait until the load event has fired and no resource finished loading for
+    /// `NETWORK_IDLE_WINDOW`, giving up after `max_wait`
+    fn wait_for_network_idle(tab: &Arc<Tab>, max_wait: Duration) {
+        let started = Instant::now();
+        let mut last_count = -1.0;
+        let mut stable_since = Instant::now();
+
+        while started.elapsed() < max_wait {
+            let script = "JSON.stringify([document.readyState === 'complete' ? 1 : 0, \
+                 performance.getEntriesByType('resource').length, 0, 0])";
+
+            if let Some([loaded, count, _, _]) = Self::evaluate_numbers(tab, script) {
+                if count != last_count {
+                    last_count = count;
+                    stable_since = Instant::now();
+                } else if loaded == 1.0 && stable_since.elapsed() >= NETWORK_IDLE_WINDOW {
+                    return;
+                }
+            }
+
+            std::thread::sleep(IDLE_POLL_INTERVAL);
+        }
+    }
+
+    fn capture_tab(tab: &Arc<Tab>, options: &ScreenshotOptions) -> Result<Vec<u8>, String> {
+        if !options.hide_selectors.is_empty() {
+            Self::hide_elements(tab, &options.hide_selectors)?;
+        }
+
+        let clip = if let Some(selector) = &options.selector {
+            Some(Self::element_viewport(tab, selector)?)
+        } else if options.full_page {
+            Some(Self::full_page_viewport(tab)?)
+        } else {
+            None
+        };
+
+        let capture_beyond_viewport = clip.is_some();
+        let response = tab
+            .call_method(CaptureScreenshot {
+                format: options.format.clone(),
+                quality: if options.format == "png" {
+                    None
+                } else {
+                    options.quality
+                },
+                clip,
+                from_surface: true,
+                capture_beyond_viewport,
+            })
+            .map_err(|e| format!("Failed to capture screenshot: {}", e))?;
+
+        base64::decode(&response.data).map_err(|e| format!("Failed to decode screenshot: {}", e))
+    }
+
+    fn hide_elements(tab: &Arc<Tab>, selectors: &[String]) -> Result<(), String> {
+        let css = format!(
+            "{} {{ visibility: hidden !important; }}",
+            selectors.join(", ")
+        );
+        let css = serde_json::to_string(&css).map_err(|e| e.to_string())?;
+        let script = format!(
+            "(() => {{ const s = document.createElement('style'); s.textContent = {}; document.head.appendChild(s); }})()",
+            css
+        );
+
+        tab.evaluate(&script, false)
+            .map_err(|e| format!("Failed to hide elements: {}", e))?;
+        Ok(())
+    }
+
+    fn element_viewport(tab: &Arc<Tab>, selector: &str) -> Result<Viewport, String> {
+        let selector_literal = serde_json::to_string(selector).map_err(|e| e.to_string())?;
+        let script = format!(
+            "(() => {{ const el = document.querySelector({}); if (!el) return null; \
+             const r = el.getBoundingClientRect(); \
+             return JSON.stringify([r.left + window.scrollX, r.top + window.scrollY, r.width, r.height]); }})()",
+            selector_literal
+        );
+
+        let [x, y, width, height] = Self::evaluate_numbers(tab, &script)
+            .ok_or_else(|| format!("No element matches selector: {}", selector))?;
+
+        if width <= 0.0 || height <= 0.0 {
+            return Err(format!("Element {} has no visible area", selector));
+        }
+
+        Ok(Viewport {
+            x,
+            y,
+            width,
+            height: height.min(MAX_CAPTURE_HEIGHT),
+            scale: 1.0,
+        })
+    }
+
+    fn full_page_viewport(tab: &Arc<Tab>) -> Result<Viewport, String> {
+        let script = "JSON.stringify([0, 0, \
+             Math.max(document.documentElement.scrollWidth, document.body ? document.body.scrollWidth : 0), \
+             Math.max(document.documentElement.scrollHeight, document.body ? document.body.scrollHeight : 0)])";
+
+        let [_, _, width, height] = Self::evaluate_numbers(tab, script)
+            .ok_or_else(|| "Failed to measure page size".to_string())?;
+
+        Ok(Viewport {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height: height.min(MAX_CAPTURE_HEIGHT),
+            scale: 1.0,
+        })
+    }
+
+    /// Evaluate a script returning a JSON-encoded array of four numbers
+    fn evaluate_numbers(tab: &Arc<Tab>, script: &str) -> Option<[f64; 4]> {
+        let result = tab.evaluate(script, false).ok()?;
+        let json = result.value?.as_str()?.to_string();
+        serde_json::from_str(&json).ok()
+    }
+}
+
+// headless_chrome 0.9 only knows PNG/JPEG and cannot capture beyond the
+// viewport, so we issue Page.captureScreenshot ourselves.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CaptureScreenshot {
+    format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clip: Option<Viewport>,
+    from_surface: bool,
+    capture_beyond_viewport: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct CaptureScreenshotResponse {
+    data: String,
+}
+
+impl Method for CaptureScreenshot {
+    const NAME: &'static str = "Page.captureScreenshot";
+    type ReturnObject = CaptureScreenshotResponse;
+}
+
+// Tabs are never closed by headless_chrome 0.9 either
+#[derive(Serialize, Debug)]
+struct ClosePage {}
+
+impl Method for ClosePage {
+    const NAME: &'static str = "Page.close";
+    type ReturnObject = serde_json::Value;
+}
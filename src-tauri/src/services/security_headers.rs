@@ -0,0 +1,156 @@
+// services/security_headers.rs
+use crate::models::wpscan::Finding;
+use crate::services::page_analysis::FetchedPage;
+
+/// Security-relevant response headers that are missing or leak information, reported
+/// in the same shape as the WordPress hardening findings
+pub fn check_security_headers(page: &FetchedPage) -> Vec<Finding> {
+    let header = |name: &'static str| page.header_values(name).next().map(str::trim);
+    let finding = |id: &str, title: &str, severity: &str, description: String| Finding {
+        id: id.to_string(),
+        title: title.to_string(),
+        severity: severity.to_string(),
+        description,
+        url: Some(page.url.clone()),
+    };
+    let mut findings = Vec::new();
+
+    if page.url.starts_with("https://") && header("strict-transport-security").is_none() {
+        findings.push(finding(
+            "missing_hsts",
+            "Strict-Transport-Security header missing",
+            "medium",
+            "Browsers may still reach the site over plain HTTP, where traffic can be intercepted."
+                .to_string(),
+        ));
+    }
+
+    let csp = header("content-security-policy");
+    if csp.is_none() {
+        findings.push(finding(
+            "missing_csp",
+            "Content-Security-Policy header missing",
+            "medium",
+            "Without a content security policy, injected scripts run with full access to the page."
+                .to_string(),
+        ));
+    }
+
+    let frame_ancestors = csp.is_some_and(|csp| csp.to_lowercase().contains("frame-ancestors"));
+    if header("x-frame-options").is_none() && !frame_ancestors {
+        findings.push(finding(
+            "clickjacking",
+            "Pages can be framed by other sites",
+            "medium",
+            "Neither X-Frame-Options nor a frame-ancestors policy is set, which allows clickjacking."
+                .to_string(),
+        ));
+    }
+
+    if !header("x-content-type-options").is_some_and(|value| value.eq_ignore_ascii_case("nosniff"))
+    {
+        findings.push(finding(
+            "missing_nosniff",
+            "X-Content-Type-Options: nosniff missing",
+            "low",
+            "Browsers may guess content types and run uploaded files as scripts.".to_string(),
+        ));
+    }
+
+    if header("referrer-policy").is_none() {
+        findings.push(finding(
+            "missing_referrer_policy",
+            "Referrer-Policy header missing",
+            "low",
+            "Full page URLs, including query strings, are sent to every linked site.".to_string(),
+        ));
+    }
+
+    let disclosed: Vec<String> = [
+        ("server", header("server")),
+        ("x-powered-by", header("x-powered-by")),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        // A bare "nginx" or "cloudflare" says little; versions help attackers
+        value
+            .filter(|value| name == "x-powered-by" || value.chars().any(|c| c.is_ascii_digit()))
+            .map(|value| format!("{}: {}", name, value))
+    })
+    .collect();
+    if !disclosed.is_empty() {
+        findings.push(finding(
+            "software_disclosure",
+            "Server software versions disclosed",
+            "low",
+            format!(
+                "Response headers reveal the software in use ({}), which helps target known vulnerabilities.",
+                disclosed.join(", ")
+            ),
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(url: &str, headers: &[(&str, &str)]) -> FetchedPage {
+        FetchedPage {
+            url: url.to_string(),
+            status: 200,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.id.as_str()).collect()
+    }
+
+    #[test]
+    fn reports_missing_and_leaking_headers() {
+        let bare = page(
+            "https://shop.example/",
+            &[
+                ("server", "Apache/2.4.41 (Ubuntu)"),
+                ("x-powered-by", "PHP/7.4"),
+            ],
+        );
+        assert_eq!(
+            ids(&check_security_headers(&bare)),
+            [
+                "missing_hsts",
+                "missing_csp",
+                "clickjacking",
+                "missing_nosniff",
+                "missing_referrer_policy",
+                "software_disclosure"
+            ]
+        );
+
+        let hardened = page(
+            "https://shop.example/",
+            &[
+                ("strict-transport-security", "max-age=31536000"),
+                (
+                    "content-security-policy",
+                    "default-src 'self'; frame-ancestors 'none'",
+                ),
+                ("x-content-type-options", "nosniff"),
+                ("referrer-policy", "strict-origin-when-cross-origin"),
+                ("server", "nginx"),
+            ],
+        );
+        assert!(check_security_headers(&hardened).is_empty());
+
+        // HSTS only means something over HTTPS
+        let plain = page("http://shop.example/", &[]);
+        assert!(!ids(&check_security_headers(&plain)).contains(&"missing_hsts"));
+    }
+}
@@ -0,0 +1,84 @@
+// src-tauri/src/services/settings_service.rs
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKeys {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wappalyzer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "screenshotApi")]
+    pub screenshot_api: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "googleDriveClientId")]
+    pub google_drive_client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "googleDriveClientSecret")]
+    pub google_drive_client_secret: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloudSettings {
+    pub provider: String,
+    #[serde(rename = "autoBackup")]
+    pub auto_backup: bool,
+    #[serde(rename = "backupFrequency")]
+    pub backup_frequency: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "lastBackup")]
+    pub last_backup: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppSettings {
+    #[serde(rename = "apiKeys")]
+    pub api_keys: ApiKeys,
+    #[serde(rename = "cloudSettings")]
+    pub cloud_settings: CloudSettings,
+    pub theme: String,
+    #[serde(rename = "enableNotifications")]
+    pub enable_notifications: bool,
+}
+
+/// Get the path to the settings file
+pub fn get_settings_path() -> Result<PathBuf, String> {
+    // Get the app's config directory
+    let config_dir =
+        dirs::config_dir().ok_or_else(|| "Failed to get config directory".to_string())?;
+
+    // Create app-specific directory
+    let app_dir = config_dir.join("webhealth-monitor");
+
+    // Create directory if it doesn't exist
+    if !app_dir.exists() {
+        fs::create_dir_all(&app_dir)
+            .map_err(|e| format!("Failed to create app directory: {}", e))?;
+    }
+
+    Ok(app_dir.join("settings.json"))
+}
+
+/// The saved application settings, or None before they are first saved
+pub fn read_settings() -> Result<Option<AppSettings>, String> {
+    eprintln!("📖 Loading settings...");
+
+    let settings_path = get_settings_path()?;
+
+    // Check if settings file exists
+    if !settings_path.exists() {
+        eprintln!("ℹ️ Settings file doesn't exist yet, returning None");
+        return Ok(None);
+    }
+
+    // Read file contents
+    let contents = fs::read_to_string(&settings_path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+
+    // Parse JSON
+    let settings: AppSettings = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse settings JSON: {}", e))?;
+
+    eprintln!("✅ Settings loaded successfully from: {:?}", settings_path);
+    Ok(Some(settings))
+}
@@ -0,0 +1,481 @@
+// services/status_page.rs
+use crate::models::check::CheckResult;
+use crate::models::status_page::{
+    Incident, StatusGroup, StatusPage, StatusPageOptions, StatusPageWebsite, StatusSummary,
+    UptimeDay,
+};
+use crate::models::website::Website;
+use crate::services::check_history_service::CheckHistoryService;
+use crate::services::maintenance_service::uptime_summary;
+use crate::services::search_service::search_stats;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// The check history keeps a little over 90 days
+pub const MAX_DAYS: u32 = 90;
+const MAX_INCIDENTS: usize = 20;
+const UNGROUPED: &str = "Other";
+const GROUPINGS: &[&str] = &["tag", "industry", "none"];
+
+pub fn validate_options(options: &StatusPageOptions) -> Result<(), String> {
+    if options.output_dir.trim().is_empty() {
+        return Err("Choose a directory for the status page".to_string());
+    }
+    if !GROUPINGS.contains(&options.group_by.as_str()) {
+        return Err(format!(
+            "Unknown status page grouping: {}",
+            options.group_by
+        ));
+    }
+    if options.days == 0 || options.days > MAX_DAYS {
+        return Err(format!(
+            "The status page can show 1 to {} days of uptime",
+            MAX_DAYS
+        ));
+    }
+    Ok(())
+}
+
+/// Build the page from the history of the given websites and write it to the output
+/// directory. Returns the page and the files written.
+pub fn publish_status_page(
+    websites: &[Website],
+    history: &CheckHistoryService,
+    options: &StatusPageOptions,
+    now: DateTime<Utc>,
+) -> Result<(StatusPage, Vec<String>), String> {
+    let since = now - Duration::days(options.days as i64);
+    let mut histories = HashMap::new();
+    for website in websites {
+        histories.insert(website.id, history.get_history(website.id, Some(since))?);
+    }
+
+    let page = build_status_page(websites, &histories, options, now);
+    let files = write_status_page(&page, Path::new(&options.output_dir))?;
+    Ok((page, files))
+}
+
+pub fn build_status_page(
+    websites: &[Website],
+    histories: &HashMap<i64, Vec<CheckResult>>,
+    options: &StatusPageOptions,
+    now: DateTime<Utc>,
+) -> StatusPage {
+    let days = options.days.clamp(1, MAX_DAYS);
+    let dates: Vec<NaiveDate> = (0..days)
+        .rev()
+        .map(|ago| (now - Duration::days(ago as i64)).date_naive())
+        .collect();
+
+    let mut groups: BTreeMap<String, Vec<StatusPageWebsite>> = BTreeMap::new();
+    let mut incidents = Vec::new();
+    for website in websites {
+        let checks = histories
+            .get(&website.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        incidents.extend(find_incidents(website, checks));
+
+        let entry = StatusPageWebsite {
+            id: website.id,
+            name: website.name.clone(),
+            url: website.url.clone(),
+            status: current_status(website, checks),
+            last_checked: website.last_checked.clone(),
+            uptime_percent: uptime_summary(website.id, days, checks).uptime_percent,
+            uptime_days: uptime_days(website.id, &dates, checks),
+        };
+        for group in group_names(website, &options.group_by) {
+            groups.entry(group).or_default().push(entry.clone());
+        }
+    }
+
+    let mut groups: Vec<StatusGroup> = groups
+        .into_iter()
+        .map(|(name, mut websites)| {
+            websites.sort_by_key(|w| w.name.to_lowercase());
+            StatusGroup { name, websites }
+        })
+        .collect();
+    // Websites without a tag or industry go last
+    groups.sort_by_key(|group| group.name == UNGROUPED);
+
+    incidents.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    incidents.truncate(MAX_INCIDENTS);
+
+    let stats = search_stats(websites);
+    StatusPage {
+        title: options.title.clone(),
+        generated_at: now.to_rfc3339(),
+        days,
+        overall_status: overall_status(stats.online_count, stats.offline_count).to_string(),
+        summary: StatusSummary {
+            total_websites: stats.total_websites,
+            online_count: stats.online_count,
+            offline_count: stats.offline_count,
+            unknown_count: stats.unknown_count,
+        },
+        groups,
+        incidents,
+    }
+}
+
+fn group_names(website: &Website, group_by: &str) -> Vec<String> {
+    let names = match group_by {
+        "tag" => website.tags.clone().unwrap_or_default(),
+        "industry" => vec![website.industry.clone()],
+        _ => return vec![String::new()],
+    };
+    let names: Vec<String> = names.into_iter().filter(|n| !n.trim().is_empty()).collect();
+    if names.is_empty() {
+        vec![UNGROUPED.to_string()]
+    } else {
+        names
+    }
+}
+
+fn current_status(website: &Website, checks: &[CheckResult]) -> String {
+    if checks.last().is_some_and(|check| check.maintenance) {
+        return "maintenance".to_string();
+    }
+    match website.status {
+        Some(200) => "up",
+        Some(_) => "down",
+        None => "unknown",
+    }
+    .to_string()
+}
+
+fn overall_status(online: usize, offline: usize) -> &'static str {
+    match (online, offline) {
+        (0, 0) => "unknown",
+        (_, 0) => "operational",
+        (0, _) => "major_outage",
+        _ => "partial_outage",
+    }
+}
+
+fn checked_at(check: &CheckResult) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&check.checked_at)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+fn uptime_days(website_id: i64, dates: &[NaiveDate], checks: &[CheckResult]) -> Vec<UptimeDay> {
+    let mut by_date: HashMap<NaiveDate, Vec<CheckResult>> = HashMap::new();
+    for check in checks {
+        if let Some(at) = checked_at(check) {
+            by_date
+                .entry(at.date_naive())
+                .or_default()
+                .push(check.clone());
+        }
+    }
+
+    dates
+        .iter()
+        .map(|date| {
+            let checks = by_date.get(date).map(Vec::as_slice).unwrap_or_default();
+            let summary = uptime_summary(website_id, 1, checks);
+            UptimeDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                checks: summary.checks,
+                up_checks: summary.up_checks,
+                maintenance_checks: summary.maintenance_checks,
+                uptime_percent: summary.uptime_percent,
+            }
+        })
+        .collect()
+}
+
+/// Runs of failed checks, each resolved by the next successful one. Checks during
+/// maintenance neither start nor resolve an incident.
+pub fn find_incidents(website: &Website, checks: &[CheckResult]) -> Vec<Incident> {
+    let mut incidents = Vec::new();
+    let mut current: Option<Incident> = None;
+    for check in checks.iter().filter(|check| !check.maintenance) {
+        if !check.is_up() {
+            let incident = current.get_or_insert_with(|| Incident {
+                website_id: website.id,
+                website_name: website.name.clone(),
+                started_at: check.checked_at.clone(),
+                resolved_at: None,
+                duration_minutes: None,
+                status: check.status,
+            });
+            incident.status = check.status;
+        } else if let Some(mut incident) = current.take() {
+            let started_at = DateTime::parse_from_rfc3339(&incident.started_at).ok();
+            incident.duration_minutes = started_at
+                .zip(checked_at(check))
+                .map(|(started_at, resolved_at)| (resolved_at - started_at.to_utc()).num_minutes());
+            incident.resolved_at = Some(check.checked_at.clone());
+            incidents.push(incident);
+        }
+    }
+    incidents.extend(current);
+    incidents
+}
+
+/// Write index.html and the status.json feed
+pub fn write_status_page(page: &StatusPage, output_dir: &Path) -> Result<Vec<String>, String> {
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create status page directory: {}", e))?;
+
+    let json = serde_json::to_string_pretty(page)
+        .map_err(|e| format!("Failed to serialize status page: {}", e))?;
+    let files = [
+        (output_dir.join("index.html"), render_status_page(page)),
+        (output_dir.join("status.json"), json),
+    ];
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:0;background:#f6f7f9;color:#1f2933}
+main{max-width:900px;margin:0 auto;padding:32px 16px}
+h1{margin:0 0 16px}h2{font-size:1.1em;margin:32px 0 8px}
+.banner{padding:16px;border-radius:8px;color:#fff;font-weight:600}
+.operational{background:#2f9e44}.partial_outage{background:#f08c00}
+.major_outage{background:#e03131}.unknown{background:#868e96}
+.site{background:#fff;border-radius:8px;padding:12px 16px;margin-bottom:8px}
+.site header{display:flex;justify-content:space-between;gap:8px}
+.state{font-weight:600}.state.up{color:#2f9e44}.state.down{color:#e03131}
+.state.maintenance{color:#1c7ed6}.state.unknown{color:#868e96}
+.bars{display:flex;gap:2px;margin:8px 0 4px;height:28px}
+.bars span{flex:1;border-radius:2px;background:#dee2e6}
+.bars .good{background:#2f9e44}.bars .partial{background:#fab005}
+.bars .bad{background:#e03131}.bars .maintenance{background:#74c0fc}
+.legend,footer{font-size:.85em;color:#616e7c}
+ul.incidents{list-style:none;padding:0}ul.incidents li{background:#fff;border-radius:8px;padding:8px 16px;margin-bottom:4px}
+";
+
+pub fn render_status_page(page: &StatusPage) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>\n{style}</style>\n</head>\n<body>\n<main>\n\
+         <h1>{title}</h1>\n<div class=\"banner {status}\">{banner}</div>\n",
+        title = escape_html(&page.title),
+        style = STYLE,
+        status = page.overall_status,
+        banner = match page.overall_status.as_str() {
+            "operational" => "All systems operational",
+            "partial_outage" => "Some systems are down",
+            "major_outage" => "All systems are down",
+            _ => "Status unknown",
+        },
+    );
+
+    for group in &page.groups {
+        if !group.name.is_empty() {
+            let _ = writeln!(html, "<h2>{}</h2>", escape_html(&group.name));
+        }
+        for website in &group.websites {
+            render_website(&mut html, website, page.days);
+        }
+    }
+
+    let _ = writeln!(html, "<h2>Recent incidents</h2>");
+    if page.incidents.is_empty() {
+        let _ = writeln!(
+            html,
+            "<p class=\"legend\">No incidents in the last {} days.</p>",
+            page.days
+        );
+    } else {
+        let _ = writeln!(html, "<ul class=\"incidents\">");
+        for incident in &page.incidents {
+            let resolution = match (&incident.resolved_at, incident.duration_minutes) {
+                (Some(resolved_at), Some(minutes)) => format!(
+                    "resolved {} after {}",
+                    escape_html(resolved_at),
+                    format_minutes(minutes)
+                ),
+                (Some(resolved_at), None) => format!("resolved {}", escape_html(resolved_at)),
+                _ => "ongoing".to_string(),
+            };
+            let _ = writeln!(
+                html,
+                "<li><strong>{}</strong> down since {}, {}</li>",
+                escape_html(&incident.website_name),
+                escape_html(&incident.started_at),
+                resolution
+            );
+        }
+        let _ = writeln!(html, "</ul>");
+    }
+
+    let _ = write!(
+        html,
+        "<footer>Updated {} (UTC). Machine-readable: <a href=\"status.json\">status.json</a>\
+         </footer>\n</main>\n</body>\n</html>\n",
+        escape_html(&page.generated_at)
+    );
+    html
+}
+
+fn render_website(html: &mut String, website: &StatusPageWebsite, days: u32) {
+    let uptime = website
+        .uptime_percent
+        .map(|percent| format!("{:.2}% uptime", percent))
+        .unwrap_or_else(|| "No data".to_string());
+    let _ = write!(
+        html,
+        "<section class=\"site\">\n<header><a href=\"{url}\">{name}</a>\
+         <span class=\"state {status}\">{status}</span></header>\n<div class=\"bars\">",
+        url = escape_html(&website.url),
+        name = escape_html(&website.name),
+        status = escape_html(&website.status),
+    );
+    for day in &website.uptime_days {
+        let (class, label) = match day.uptime_percent {
+            Some(percent) if percent >= 99.9 => ("good", format!("{:.2}%", percent)),
+            Some(percent) if percent >= 95.0 => ("partial", format!("{:.2}%", percent)),
+            Some(percent) => ("bad", format!("{:.2}%", percent)),
+            None if day.maintenance_checks > 0 => ("maintenance", "Maintenance".to_string()),
+            None => ("none", "No data".to_string()),
+        };
+        let _ = write!(
+            html,
+            "<span class=\"{}\" title=\"{}: {}\"></span>",
+            class, day.date, label
+        );
+    }
+    let _ = writeln!(
+        html,
+        "</div>\n<div class=\"legend\">{} days ago &middot; {} &middot; today</div>\n</section>",
+        days, uptime
+    );
+}
+
+fn format_minutes(minutes: i64) -> String {
+    match minutes {
+        m if m < 60 => format!("{} min", m),
+        m if m < 24 * 60 => format!("{} h {} min", m / 60, m % 60),
+        m => format!("{} d {} h", m / (24 * 60), m % (24 * 60) / 60),
+    }
+}
+
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(at: &str, status: u16, maintenance: bool) -> CheckResult {
+        CheckResult {
+            website_id: 1,
+            checked_at: at.to_string(),
+            status,
+            response_time_ms: Some(120),
+            maintenance,
+            vantage: None,
+            unconfirmed: false,
+        }
+    }
+
+    fn website(id: i64, name: &str, tags: &[&str], status: Option<u16>) -> Website {
+        Website {
+            id,
+            name: name.to_string(),
+            url: format!("https://{}.example", name.to_lowercase()),
+            status,
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn incidents_span_failed_checks_outside_maintenance() {
+        let site = website(1, "Shop", &[], Some(200));
+        let checks = [
+            check("2026-10-17T10:00:00+00:00", 200, false),
+            check("2026-10-17T10:05:00+00:00", 503, false),
+            check("2026-10-17T10:10:00+00:00", 0, true),
+            check("2026-10-17T10:15:00+00:00", 0, false),
+            check("2026-10-17T10:50:00+00:00", 200, false),
+            check("2026-10-18T08:00:00+00:00", 0, true),
+            check("2026-10-18T08:05:00+00:00", 200, false),
+            check("2026-10-18T09:00:00+00:00", 502, false),
+        ];
+
+        let incidents = find_incidents(&site, &checks);
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].started_at, "2026-10-17T10:05:00+00:00");
+        assert_eq!(
+            incidents[0].resolved_at.as_deref(),
+            Some("2026-10-17T10:50:00+00:00")
+        );
+        assert_eq!(incidents[0].duration_minutes, Some(45));
+        assert_eq!(incidents[0].status, 0);
+        assert_eq!(incidents[1].resolved_at, None);
+        assert_eq!(incidents[1].status, 502);
+    }
+
+    #[test]
+    fn builds_grouped_page_with_daily_uptime() {
+        let now = DateTime::parse_from_rfc3339("2026-10-18T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let websites = [
+            website(1, "Shop", &["Client A"], Some(200)),
+            website(2, "Blog", &["Client A", "Client B"], Some(503)),
+            website(3, "Intranet", &[], None),
+        ];
+        let histories = HashMap::from([(
+            1,
+            vec![
+                check("2026-10-17T10:00:00+00:00", 200, false),
+                check("2026-10-17T11:00:00+00:00", 503, false),
+                check("2026-10-18T10:00:00+00:00", 0, true),
+            ],
+        )]);
+        let options = StatusPageOptions {
+            title: "Client <status>".to_string(),
+            group_by: "tag".to_string(),
+            days: 3,
+            ..Default::default()
+        };
+
+        let page = build_status_page(&websites, &histories, &options, now);
+        assert_eq!(page.overall_status, "partial_outage");
+        assert_eq!(page.summary.unknown_count, 1);
+        let names: Vec<&str> = page.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Client A", "Client B", "Other"]);
+        assert_eq!(page.groups[0].websites[0].name, "Blog");
+
+        let shop = &page.groups[0].websites[1];
+        assert_eq!(shop.status, "maintenance");
+        assert_eq!(shop.uptime_percent, Some(50.0));
+        let dates: Vec<&str> = shop.uptime_days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2026-10-16", "2026-10-17", "2026-10-18"]);
+        assert_eq!(shop.uptime_days[0].uptime_percent, None);
+        assert_eq!(shop.uptime_days[1].uptime_percent, Some(50.0));
+        assert_eq!(shop.uptime_days[2].maintenance_checks, 1);
+        assert_eq!(page.incidents.len(), 1);
+
+        let html = render_status_page(&page);
+        assert!(html.contains("<title>Client &lt;status&gt;</title>"));
+        assert!(html.contains("title=\"2026-10-18: Maintenance\""));
+        assert!(html.contains("Some systems are down"));
+    }
+}
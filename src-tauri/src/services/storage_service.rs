@@ -1,49 +1,144 @@
 // services/storage_service.rs
 use crate::models::website::Website;
-use std::fs::{File, OpenOptions};
+use crate::services::file_lock::lock_file;
+use std::fs::{self, File};
 use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct StorageService {
     file_path: String,
+    write_lock: Mutex<()>, // Serializes writes in this process, lock_file those of other processes
 }
 
 impl StorageService {
     pub fn new(file_path: String) -> Self {
-        Self { file_path }
+        Self {
+            file_path,
+            write_lock: Mutex::new(()),
+        }
     }
 
+    /// The stored websites. A file that does not parse is an error rather than an empty
+    /// list, so nothing reads it as "no websites" and saves over it.
     pub fn get_websites(&self) -> Result<Vec<Website>, std::io::Error> {
         let mut file = File::open(&self.file_path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
+        if contents.trim().is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let websites: Vec<Website> = serde_json::from_str(&contents).unwrap_or_else(|_| Vec::new());
-
-        Ok(websites)
+        serde_json::from_str(&contents).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Failed to parse {}: {}", self.file_path, e),
+            )
+        })
     }
 
     pub fn save_websites(&self, websites: &[Website]) -> Result<(), std::io::Error> {
-        let json = serde_json::to_string_pretty(websites)?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&self.file_path)?;
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = lock_file(&self.file_path)?;
+        self.write_websites(websites)
+    }
 
+    /// Write a temporary file and rename it over the stored one, so readers see either
+    /// the old or the new list and never a half-written file
+    fn write_websites(&self, websites: &[Website]) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(websites)?;
+        // Per process, as the whb CLI may write next to the app
+        let temp_path = format!("{}.{}.tmp", self.file_path, std::process::id());
+        let mut file = File::create(&temp_path)?;
         file.write_all(json.as_bytes())?;
-        Ok(())
+        file.sync_all()?;
+
+        fs::rename(&temp_path, &self.file_path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
     }
 
     pub fn update_website(&self, website: &Website) -> Result<(), Box<dyn std::error::Error>> {
+        self.modify_website(website.id, |w| *w = website.clone())?;
+        Ok(())
+    }
+
+    /// Apply `change` to the freshly loaded list of websites and save it, while no
+    /// other write can happen in between
+    pub fn modify_websites<F, T>(&self, change: F) -> Result<T, Box<dyn std::error::Error>>
+    where
+        F: FnOnce(&mut Vec<Website>) -> T,
+    {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = lock_file(&self.file_path)?;
         let mut websites = self.get_websites()?;
 
-        // Find the index of the website to update
-        if let Some(index) = websites.iter().position(|w| w.id == website.id) {
-            websites[index] = website.clone();
-            self.save_websites(&websites)?;
-        }
+        let result = change(&mut websites);
+        self.write_websites(&websites)?;
 
-        Ok(())
+        Ok(result)
+    }
+
+    /// Apply `change` to the freshly loaded website so fields written by other
+    /// jobs in the meantime are kept. Returns `None` if the website is gone.
+    pub fn modify_website<F>(
+        &self,
+        id: i64,
+        change: F,
+    ) -> Result<Option<Website>, Box<dyn std::error::Error>>
+    where
+        F: FnOnce(&mut Website),
+    {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = lock_file(&self.file_path)?;
+        let mut websites = self.get_websites()?;
+
+        let Some(website) = websites.iter_mut().find(|w| w.id == id) else {
+            return Ok(None);
+        };
+
+        change(website);
+        let updated = website.clone();
+        self.write_websites(&websites)?;
+
+        Ok(Some(updated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreadable_files_are_not_taken_for_an_empty_list() {
+        let path = std::env::temp_dir().join(format!(
+            "whb-websites-{}.json",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let storage = StorageService::new(path.to_string_lossy().to_string());
+
+        let website = Website {
+            id: 1,
+            name: "Shop".to_string(),
+            url: "https://shop.example".to_string(),
+            ..Default::default()
+        };
+        storage
+            .save_websites(std::slice::from_ref(&website))
+            .unwrap();
+        assert_eq!(storage.get_websites().unwrap().len(), 1);
+
+        // A torn write used to read as no websites at all
+        fs::write(&path, "[{\"id\": 1, \"url\": \"https://sh").unwrap();
+        let error = storage.get_websites().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(storage.modify_website(1, |w| w.favorite = true).is_err());
+
+        fs::write(&path, "").unwrap();
+        assert!(storage.get_websites().unwrap().is_empty());
+
+        let _ = fs::remove_file(format!("{}.lock", path.display()));
+        let _ = fs::remove_file(path);
     }
 }
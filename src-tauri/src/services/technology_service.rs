@@ -0,0 +1,325 @@
+// services/technology_service.rs
+use crate::models::technology::Technology;
+use crate::services::page_analysis::FetchedPage;
+use regex::{Regex, RegexBuilder};
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const BUNDLED_RULES: &str = include_str!("../../resources/technologies.json");
+
+/// One entry of the rules file. Every pattern is a case-insensitive regex whose first
+/// capture group, when present, is the version; an empty pattern only checks presence.
+#[derive(Debug, Deserialize)]
+struct TechnologyRule {
+    name: String,
+    category: String,
+    #[serde(default)]
+    headers: HashMap<String, String>, // Header name -> value pattern
+    #[serde(default)]
+    meta: HashMap<String, String>, // <meta name|property> -> content pattern
+    #[serde(default)]
+    script_src: Vec<String>,
+    #[serde(default)]
+    cookies: HashMap<String, String>, // Cookie name pattern -> value pattern
+    #[serde(default)]
+    html: Vec<String>,
+    #[serde(default)]
+    implies: Vec<String>,
+}
+
+struct CompiledRule {
+    name: String,
+    category: String,
+    headers: Vec<(String, Regex)>,
+    meta: Vec<(String, Regex)>,
+    script_src: Vec<Regex>,
+    cookies: Vec<(Regex, Regex)>,
+    html: Vec<Regex>,
+    implies: Vec<String>,
+}
+
+/// Local replacement for the Wappalyzer API: detects CMS, frameworks, analytics,
+/// CDN and server software from a rules file. Rules are compiled once; clones share them.
+#[derive(Clone)]
+pub struct TechnologyService {
+    rules: Arc<Vec<CompiledRule>>,
+}
+
+impl Default for TechnologyService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TechnologyService {
+    /// Use `technologies.json` from the app config directory when present,
+    /// otherwise the rules bundled with the app
+    pub fn new() -> Self {
+        let user_rules = Self::user_rules_path().filter(|path| path.exists());
+        Self::load(user_rules.as_deref())
+    }
+
+    /// Rules from `path`, falling back to the bundled rules with a warning when the
+    /// file cannot be read or parsed, so a broken file never stops detection
+    fn load(path: Option<&Path>) -> Self {
+        if let Some(path) = path {
+            let rules = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read technology rules: {}", e))
+                .and_then(|rules| Self::with_rules(&rules));
+            match rules {
+                Ok(service) => {
+                    eprintln!("Using technology rules from {:?}", path);
+                    return service;
+                }
+                Err(e) => eprintln!("{} ({:?}), using the bundled rules instead", e, path),
+            }
+        }
+
+        Self::with_rules(BUNDLED_RULES).expect("bundled technology rules are valid")
+    }
+
+    pub fn with_rules(rules_json: &str) -> Result<Self, String> {
+        let rules: Vec<TechnologyRule> = serde_json::from_str(rules_json)
+            .map_err(|e| format!("Failed to parse technology rules: {}", e))?;
+
+        Ok(Self {
+            rules: Arc::new(
+                rules
+                    .into_iter()
+                    .map(compile_rule)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    fn user_rules_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("webhealth-monitor").join("technologies.json"))
+    }
+
+    /// Match every rule against a page, adding implied technologies (Next.js -> React).
+    /// `document` is the already parsed `page.html`.
+    pub fn analyze(&self, page: &FetchedPage, document: &Html) -> Vec<Technology> {
+        let meta = collect_meta(document);
+        let scripts = collect_script_srcs(document);
+
+        let mut detected: Vec<Technology> = Vec::new();
+
+        for rule in self.rules.iter() {
+            let mut matched = false;
+            let mut version = None;
+            let mut record = |captures: Option<regex::Captures>| {
+                if let Some(captures) = captures {
+                    matched = true;
+                    if version.is_none() {
+                        version = captures
+                            .get(1)
+                            .map(|m| m.as_str().to_string())
+                            .filter(|v| !v.is_empty());
+                    }
+                }
+            };
+
+            for (name, pattern) in &rule.headers {
+                for (_, value) in page.headers.iter().filter(|(header, _)| header == name) {
+                    record(pattern.captures(value));
+                }
+            }
+            for (name, pattern) in &rule.meta {
+                for (_, content) in meta.iter().filter(|(meta_name, _)| meta_name == name) {
+                    record(pattern.captures(content));
+                }
+            }
+            for pattern in &rule.script_src {
+                for src in &scripts {
+                    record(pattern.captures(src));
+                }
+            }
+            for (name_pattern, value_pattern) in &rule.cookies {
+                for (name, value) in &page.cookies {
+                    if name_pattern.is_match(name) {
+                        record(value_pattern.captures(value));
+                    }
+                }
+            }
+            for pattern in &rule.html {
+                record(pattern.captures(&page.html));
+            }
+
+            if matched {
+                detected.push(Technology {
+                    name: rule.name.clone(),
+                    category: rule.category.clone(),
+                    version,
+                });
+            }
+        }
+
+        // Implied technologies are added once, without a version
+        let mut index = 0;
+        while index < detected.len() {
+            let implies = self
+                .rules
+                .iter()
+                .find(|rule| rule.name == detected[index].name)
+                .map(|rule| rule.implies.clone())
+                .unwrap_or_default();
+
+            for implied in implies {
+                if detected.iter().any(|t| t.name == implied) {
+                    continue;
+                }
+                if let Some(rule) = self.rules.iter().find(|rule| rule.name == implied) {
+                    detected.push(Technology {
+                        name: rule.name.clone(),
+                        category: rule.category.clone(),
+                        version: None,
+                    });
+                }
+            }
+            index += 1;
+        }
+
+        detected.sort_by(|a, b| a.category.cmp(&b.category).then(a.name.cmp(&b.name)));
+        detected
+    }
+}
+
+fn compile_rule(rule: TechnologyRule) -> Result<CompiledRule, String> {
+    let compile = |pattern: &str| {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| format!("Invalid pattern for {}: {}", rule.name, e))
+    };
+
+    Ok(CompiledRule {
+        headers: rule
+            .headers
+            .iter()
+            .map(|(name, pattern)| Ok((name.to_lowercase(), compile(pattern)?)))
+            .collect::<Result<_, String>>()?,
+        meta: rule
+            .meta
+            .iter()
+            .map(|(name, pattern)| Ok((name.to_lowercase(), compile(pattern)?)))
+            .collect::<Result<_, String>>()?,
+        script_src: rule
+            .script_src
+            .iter()
+            .map(|pattern| compile(pattern))
+            .collect::<Result<_, _>>()?,
+        cookies: rule
+            .cookies
+            .iter()
+            .map(|(name, pattern)| Ok((compile(name)?, compile(pattern)?)))
+            .collect::<Result<_, String>>()?,
+        html: rule
+            .html
+            .iter()
+            .map(|pattern| compile(pattern))
+            .collect::<Result<_, _>>()?,
+        name: rule.name,
+        category: rule.category,
+        implies: rule.implies,
+    })
+}
+
+/// (name or property, content) of every meta tag, names lowercased
+fn collect_meta(document: &Html) -> Vec<(String, String)> {
+    let selector = Selector::parse("meta[content]").expect("valid selector");
+    document
+        .select(&selector)
+        .filter_map(|element| {
+            let element = element.value();
+            let name = element.attr("name").or_else(|| element.attr("property"))?;
+            Some((name.to_lowercase(), element.attr("content")?.to_string()))
+        })
+        .collect()
+}
+
+fn collect_script_srcs(document: &Html) -> Vec<String> {
+    let selector = Selector::parse("script[src]").expect("valid selector");
+    document
+        .select(&selector)
+        .filter_map(|element| element.value().attr("src").map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_rules_compile() {
+        assert!(TechnologyService::with_rules(BUNDLED_RULES).is_ok());
+    }
+
+    #[test]
+    fn invalid_user_rules_fall_back_to_bundled_ones() {
+        let path = std::env::temp_dir().join(format!(
+            "whb-technologies-{}.json",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::write(&path, "[{\"name\": \"Broken\"").unwrap();
+
+        let service = TechnologyService::load(Some(&path));
+        let bundled = TechnologyService::with_rules(BUNDLED_RULES).unwrap();
+        assert_eq!(service.rules.len(), bundled.rules.len());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn detects_stack_from_headers_meta_scripts_and_cookies() {
+        let service = TechnologyService::with_rules(BUNDLED_RULES).unwrap();
+        let page = FetchedPage {
+            url: "https://example.com/".to_string(),
+            status: 200,
+            headers: vec![
+                ("server".to_string(), "nginx/1.25.3".to_string()),
+                ("cf-ray".to_string(), "8a1b2c3d4e5f-AMS".to_string()),
+                (
+                    "x-generator".to_string(),
+                    "Drupal 10 (https://www.drupal.org)".to_string(),
+                ),
+            ],
+            cookies: vec![("_ga".to_string(), "GA1.1.123".to_string())],
+            html:
+                r#"<html><head><meta name="Generator" content="Drupal 10 (https://www.drupal.org)">
+                <script src="/core/assets/vendor/jquery/jquery.min.js?v=3.7.1"></script>
+                <script src="https://www.googletagmanager.com/gtag/js?id=G-XYZ"></script>
+                </head><body></body></html>"#
+                    .to_string(),
+        };
+
+        let detected = service.analyze(&page, &Html::parse_document(&page.html));
+        let find = |name: &str| detected.iter().find(|t| t.name == name);
+
+        assert_eq!(
+            find("Nginx").and_then(|t| t.version.as_deref()),
+            Some("1.25.3")
+        );
+        assert_eq!(
+            find("Drupal").and_then(|t| t.version.as_deref()),
+            Some("10")
+        );
+        assert_eq!(find("Drupal").map(|t| t.category.as_str()), Some("cms"));
+        assert!(find("Cloudflare").is_some());
+        assert!(find("Google Analytics").is_some());
+        assert!(find("jQuery").is_some());
+        // Implied by Drupal
+        assert!(find("PHP").is_some());
+        assert!(find("WordPress").is_none());
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported() {
+        let rules = r#"[{"name": "Broken", "category": "cms", "html": ["(unclosed"]}]"#;
+        let error = TechnologyService::with_rules(rules).err().unwrap();
+        assert!(error.contains("Broken"));
+    }
+}
@@ -0,0 +1,414 @@
+// services/wordpress_fingerprint.rs
+use scraper::{Html, Node};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use url::Url;
+
+// Attributes that commonly reference theme/plugin assets
+const ASSET_ATTRIBUTES: &[&str] = &[
+    "src",
+    "href",
+    "data-src",
+    "srcset",
+    "data-srcset",
+    "content",
+];
+
+// Generator tags and HTML comments left behind by popular plugins: (marker, slug).
+// The version, when present, directly follows the marker.
+const PLUGIN_SIGNATURES: &[(&str, &str)] = &[
+    ("optimized with the Yoast SEO plugin", "wordpress-seo"),
+    (
+        "optimized with the Yoast SEO Premium plugin",
+        "wordpress-seo-premium",
+    ),
+    (
+        "Search Engine Optimization by Rank Math",
+        "seo-by-rank-math",
+    ),
+    ("All in One SEO", "all-in-one-seo-pack"),
+    (
+        "Google Analytics by MonsterInsights plugin",
+        "google-analytics-for-wordpress",
+    ),
+    ("Site Kit by Google", "google-site-kit"),
+    ("Elementor", "elementor"),
+    ("WooCommerce", "woocommerce"),
+    ("Powered by WPBakery Page Builder", "js_composer"),
+    ("Powered by Slider Revolution", "revslider"),
+    ("Performance optimized by W3 Total Cache", "w3-total-cache"),
+    ("generated by WP-Super-Cache", "wp-super-cache"),
+    ("Page optimized by LiteSpeed Cache", "litespeed-cache"),
+    ("WPML ver:", "sitepress-multilingual-cms"),
+];
+
+// REST API namespaces whose name differs from the plugin slug
+const NAMESPACE_SLUGS: &[(&str, &str)] = &[
+    ("yoast", "wordpress-seo"),
+    ("wc", "woocommerce"),
+    ("wc-admin", "woocommerce"),
+    ("wc-analytics", "woocommerce"),
+    ("wc-telemetry", "woocommerce"),
+    ("rankmath", "seo-by-rank-math"),
+    ("aioseo", "all-in-one-seo-pack"),
+    ("litespeed", "litespeed-cache"),
+    ("mc4wp", "mailchimp-for-wp"),
+    ("complianz", "complianz-gdpr"),
+    ("monsterinsights", "google-analytics-for-wordpress"),
+    ("jetpack", "jetpack"),
+    ("contact-form-7", "contact-form-7"),
+    ("elementor", "elementor"),
+    ("wordfence", "wordfence"),
+    ("akismet", "akismet"),
+    ("redirection", "redirection"),
+    ("google-site-kit", "google-site-kit"),
+    ("updraftplus", "updraftplus"),
+];
+
+// Namespaces registered by WordPress itself
+const CORE_NAMESPACES: &[&str] = &[
+    "oembed",
+    "wp",
+    "wp-site-health",
+    "wp-block-editor",
+    "wp-abilities",
+];
+
+/// Everything a single homepage fetch reveals about a WordPress install
+#[derive(Debug, Default, Clone)]
+pub struct PageFingerprint {
+    pub generator_version: Option<String>, // From <meta name="generator" content="WordPress x.y">
+    pub core_version_hint: Option<String>, // Most common ?ver= on wp-includes assets
+    pub plugins_dir: Option<String>,       // e.g. "/wp-content/plugins" or "/app/plugins"
+    pub themes_dir: Option<String>,
+    pub plugins: BTreeMap<String, Vec<String>>, // Slug -> ?ver= hints in order of appearance
+    pub themes: BTreeMap<String, Vec<String>>,
+    pub signature_plugins: BTreeMap<String, Option<String>>, // Slug -> version from generator/comment
+    pub rest_api_url: Option<String>,
+    pub has_wp_includes: bool,
+}
+
+impl PageFingerprint {
+    pub fn is_wordpress(&self) -> bool {
+        self.generator_version.is_some()
+            || self.has_wp_includes
+            || self.rest_api_url.is_some()
+            || self.plugins_dir.is_some()
+            || self.themes_dir.is_some()
+    }
+
+    /// A `?ver=` hint for a plugin or theme, ignoring values that are just the core
+    /// version WordPress appends to assets enqueued without their own version
+    pub fn version_hint(&self, hints: &[String], core_version: Option<&str>) -> Option<String> {
+        let core = core_version.or(self.core_version_hint.as_deref());
+        hints
+            .iter()
+            .find(|hint| Some(hint.as_str()) != core)
+            .cloned()
+    }
+}
+
+struct AssetRef {
+    prefix: String, // Path before the plugins/themes segment
+    kind: &'static str,
+    slug: String,
+    version: Option<String>,
+}
+
+/// Collect WordPress evidence from a parsed homepage: asset URLs, generator tags,
+/// comments and the REST API discovery link. Handles minified HTML, renamed content
+/// directories and JSON-escaped URLs in inline scripts.
+pub fn fingerprint_document(document: &Html, base_url: &str) -> PageFingerprint {
+    let base = Url::parse(base_url).ok();
+    let mut fingerprint = PageFingerprint::default();
+
+    let mut candidates: Vec<String> = Vec::new();
+    let mut generators: Vec<String> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+
+    for node in document.tree.nodes() {
+        match node.value() {
+            Node::Element(element) => {
+                for attribute in ASSET_ATTRIBUTES {
+                    if let Some(value) = element.attr(attribute) {
+                        candidates.extend(split_references(value));
+                    }
+                }
+
+                if element.name() == "meta"
+                    && element
+                        .attr("name")
+                        .is_some_and(|name| name.eq_ignore_ascii_case("generator"))
+                {
+                    generators.extend(element.attr("content").map(str::to_string));
+                }
+
+                if element.name() == "link" && element.attr("rel") == Some("https://api.w.org/") {
+                    fingerprint.rest_api_url = element.attr("href").map(str::to_string);
+                }
+            }
+            // Inline scripts and styles, e.g. wp_localize_script JSON
+            Node::Text(text) => candidates.extend(split_references(text)),
+            Node::Comment(comment) => comments.push(comment.to_string()),
+            _ => {}
+        }
+    }
+
+    let mut refs = Vec::new();
+    let mut core_hints: HashMap<String, usize> = HashMap::new();
+
+    for candidate in &candidates {
+        let Some(parsed) = resolve(base.as_ref(), candidate) else {
+            continue;
+        };
+        let version = parsed
+            .query_pairs()
+            .find(|(key, _)| key == "ver" || key == "version")
+            .map(|(_, value)| value.to_string())
+            .filter(|value| !value.is_empty());
+        let segments: Vec<&str> = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        if segments.contains(&"wp-includes") {
+            fingerprint.has_wp_includes = true;
+            if let Some(version) = &version {
+                *core_hints.entry(version.clone()).or_default() += 1;
+            }
+            continue;
+        }
+
+        // ".../<prefix>/plugins/<slug>/<file...>": the slug must be a directory
+        for (index, segment) in segments.iter().enumerate() {
+            let kind = match *segment {
+                "plugins" => "plugins",
+                "themes" => "themes",
+                _ => continue,
+            };
+            if index == 0 || index + 2 >= segments.len() {
+                continue;
+            }
+            let slug = segments[index + 1].to_lowercase();
+            if !is_valid_slug(&slug) {
+                continue;
+            }
+
+            refs.push(AssetRef {
+                prefix: format!("/{}", segments[..index].join("/")),
+                kind,
+                slug,
+                version: version.clone(),
+            });
+            break;
+        }
+    }
+
+    // A directory counts as a content dir if it is wp-content, or if it holds both
+    // plugins and themes (renamed content dirs such as Bedrock's /app)
+    let prefixes_by_kind = |kind: &str| -> BTreeSet<String> {
+        refs.iter()
+            .filter(|r| r.kind == kind)
+            .map(|r| r.prefix.clone())
+            .collect()
+    };
+    let plugin_prefixes = prefixes_by_kind("plugins");
+    let theme_prefixes = prefixes_by_kind("themes");
+    let is_content_dir = |prefix: &str| {
+        prefix.split('/').any(|s| s == "wp-content")
+            || (plugin_prefixes.contains(prefix) && theme_prefixes.contains(prefix))
+    };
+
+    for asset in refs.iter().filter(|r| is_content_dir(&r.prefix)) {
+        let (dir, found) = if asset.kind == "plugins" {
+            (&mut fingerprint.plugins_dir, &mut fingerprint.plugins)
+        } else {
+            (&mut fingerprint.themes_dir, &mut fingerprint.themes)
+        };
+
+        dir.get_or_insert_with(|| format!("{}/{}", asset.prefix.trim_end_matches('/'), asset.kind));
+        let hints = found.entry(asset.slug.clone()).or_default();
+        if let Some(version) = asset.version.as_ref().filter(|v| !hints.contains(v)) {
+            hints.push(version.clone());
+        }
+    }
+
+    fingerprint.core_version_hint = core_hints
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(version, _)| version);
+
+    for generator in &generators {
+        if let Some(version) = generator.strip_prefix("WordPress ") {
+            fingerprint.generator_version = Some(version.trim().to_string());
+        }
+    }
+
+    for text in generators.iter().chain(comments.iter()) {
+        for (marker, slug) in PLUGIN_SIGNATURES {
+            if let Some(position) = text.find(marker) {
+                let version = leading_version(&text[position + marker.len()..]);
+                let entry = fingerprint
+                    .signature_plugins
+                    .entry(slug.to_string())
+                    .or_default();
+                if entry.is_none() {
+                    *entry = version;
+                }
+            }
+        }
+    }
+
+    fingerprint
+}
+
+/// Plugin slugs implied by REST API namespaces such as "yoast/v1" or "contact-form-7/v1".
+/// Returns the known slugs and, separately, unknown namespaces worth probing.
+pub fn plugins_from_namespaces(namespaces: &[String]) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut known = BTreeSet::new();
+    let mut unknown = BTreeSet::new();
+
+    for namespace in namespaces {
+        let head = namespace
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if head.is_empty() || CORE_NAMESPACES.contains(&head.as_str()) {
+            continue;
+        }
+
+        match NAMESPACE_SLUGS.iter().find(|(name, _)| *name == head) {
+            Some((_, slug)) => {
+                known.insert(slug.to_string());
+            }
+            None if is_valid_slug(&head) => {
+                unknown.insert(head);
+            }
+            None => {}
+        }
+    }
+
+    (known, unknown)
+}
+
+/// Split an attribute value or inline text into URL-ish tokens worth resolving
+fn split_references(text: &str) -> Vec<String> {
+    if !text.contains("plugins") && !text.contains("themes") && !text.contains("wp-includes") {
+        return Vec::new();
+    }
+
+    text.replace("\\/", "/")
+        .split(|c: char| c.is_whitespace() || "\"'(),;<>`".contains(c))
+        .filter(|token| {
+            token.contains("/plugins/")
+                || token.contains("/themes/")
+                || token.contains("/wp-includes/")
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn resolve(base: Option<&Url>, reference: &str) -> Option<Url> {
+    match base {
+        Some(base) => base.join(reference).ok(),
+        None => Url::parse(reference).ok(),
+    }
+}
+
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= 100
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !slug.starts_with('.')
+}
+
+/// "v21.5 - https://yoast.com" -> "21.5"
+fn leading_version(text: &str) -> Option<String> {
+    let text = text
+        .trim_start_matches([' ', ':', '-'])
+        .trim_start_matches(['v', 'V']);
+    let version: String = text
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let version = version.trim_end_matches('.');
+
+    if version.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_asset_in_minified_html() {
+        let html = r#"<html><head><meta name="generator" content="WordPress 6.4.1"><link rel="https://api.w.org/" href="https://example.com/wp-json/"><link rel="stylesheet" href="https://example.com/wp-content/plugins/contact-form-7/includes/css/styles.css?ver=5.8.4"><link rel="stylesheet" href="/wp-content/plugins/akismet/_inc/form.css?ver=6.4.1"><script src="/wp-includes/js/jquery/jquery.min.js?ver=6.4.1"></script><link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=4.5.2"></head><body><script>var cfg = {"url":"https:\/\/example.com\/wp-content\/plugins\/woocommerce\/assets\/js\/x.js"};</script><!-- This site is optimized with the Yoast SEO plugin v21.5 - https://yoast.com/wordpress/plugins/seo/ --></body></html>"#;
+
+        let fingerprint = fingerprint_document(&Html::parse_document(html), "https://example.com/");
+
+        assert!(fingerprint.is_wordpress());
+        assert_eq!(fingerprint.generator_version.as_deref(), Some("6.4.1"));
+        assert_eq!(fingerprint.core_version_hint.as_deref(), Some("6.4.1"));
+        assert_eq!(
+            fingerprint.plugins_dir.as_deref(),
+            Some("/wp-content/plugins")
+        );
+        assert_eq!(
+            fingerprint.plugins.keys().collect::<Vec<_>>(),
+            vec!["akismet", "contact-form-7", "woocommerce"]
+        );
+        assert!(fingerprint.themes.contains_key("astra"));
+        assert_eq!(
+            fingerprint.signature_plugins.get("wordpress-seo"),
+            Some(&Some("21.5".to_string()))
+        );
+
+        // Akismet's ?ver= is just the core version
+        assert_eq!(
+            fingerprint.version_hint(&fingerprint.plugins["akismet"], None),
+            None
+        );
+        assert_eq!(
+            fingerprint
+                .version_hint(&fingerprint.plugins["contact-form-7"], None)
+                .as_deref(),
+            Some("5.8.4")
+        );
+    }
+
+    #[test]
+    fn detects_renamed_content_directory() {
+        let html = r#"<link href="/app/themes/sage/dist/main.css"><script src="/app/plugins/wordfence/js/a.js?ver=7.11.0"></script><script src="/static/plugins/jquery/ui.js"></script>"#;
+
+        let fingerprint = fingerprint_document(&Html::parse_document(html), "https://example.com");
+
+        assert_eq!(fingerprint.plugins_dir.as_deref(), Some("/app/plugins"));
+        assert_eq!(fingerprint.themes_dir.as_deref(), Some("/app/themes"));
+        assert_eq!(
+            fingerprint.plugins.keys().collect::<Vec<_>>(),
+            vec!["wordfence"]
+        );
+    }
+
+    #[test]
+    fn maps_rest_namespaces_to_plugins() {
+        let namespaces: Vec<String> = ["oembed/1.0", "wp/v2", "yoast/v1", "wc/v3", "my-plugin/v1"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let (known, unknown) = plugins_from_namespaces(&namespaces);
+
+        assert_eq!(
+            known.into_iter().collect::<Vec<_>>(),
+            vec!["woocommerce", "wordpress-seo"]
+        );
+        assert_eq!(unknown.into_iter().collect::<Vec<_>>(), vec!["my-plugin"]);
+    }
+}
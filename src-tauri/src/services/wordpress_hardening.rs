@@ -0,0 +1,308 @@
+// services/wordpress_hardening.rs
+use crate::models::wpscan::{Finding, User};
+use reqwest::{Client, redirect};
+use std::time::Duration;
+
+// Author ids probed through ?author=N
+const AUTHOR_PROBES: u32 = 5;
+const CONFIG_BACKUPS: &[&str] = &[
+    "wp-config.php.bak",
+    "wp-config.php~",
+    "wp-config.bak",
+    "wp-config.php.old",
+    "wp-config.php.save",
+    "wp-config.php.orig",
+    "wp-config.txt",
+    ".wp-config.php.swp",
+];
+const XMLRPC_LIST_METHODS: &str = "<?xml version=\"1.0\"?><methodCall><methodName>system.listMethods</methodName><params></params></methodCall>";
+
+/// Configuration checks that go beyond known vulnerabilities: exposed files and
+/// endpoints that make a WordPress site easier to attack.
+pub struct HardeningChecker {
+    client: Client,
+    site_url: String,
+    content_url: String, // e.g. "https://example.com/wp-content"
+}
+
+#[derive(Debug, Default)]
+pub struct HardeningReport {
+    pub findings: Vec<Finding>,
+    pub users: Vec<User>, // Usernames leaked by ?author=N redirects
+}
+
+impl HardeningChecker {
+    pub fn new(site_url: &str, content_url: &str) -> Result<Self, String> {
+        // Redirects are inspected, not followed: ?author=N leaks usernames through them
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(redirect::Policy::none())
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            site_url: site_url.trim_end_matches('/').to_string(),
+            content_url: content_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Run every check. `rest_users` are the users already listed by `/wp-json/wp/v2/users`.
+    pub async fn run(&self, rest_users: &[User]) -> HardeningReport {
+        let mut report = HardeningReport::default();
+
+        report.findings.extend(self.check_xmlrpc().await);
+        report.findings.extend(self.check_uploads_listing().await);
+        report.findings.extend(self.check_debug_log().await);
+        report.findings.extend(self.check_config_backups().await);
+
+        if !rest_users.is_empty() {
+            report.findings.push(Finding {
+                id: "rest_user_enumeration".to_string(),
+                title: "Usernames listed by the REST API".to_string(),
+                severity: "low".to_string(),
+                description: format!(
+                    "/wp-json/wp/v2/users exposes {} account names to anonymous visitors, which helps brute-force attacks.",
+                    rest_users.len()
+                ),
+                url: Some(self.url("wp-json/wp/v2/users")),
+            });
+        }
+
+        let (finding, users) = self.check_author_enumeration().await;
+        report.findings.extend(finding);
+        report.users = users;
+
+        report.findings.extend(self.check_login_page().await);
+        report
+    }
+
+    async fn check_xmlrpc(&self) -> Option<Finding> {
+        let url = self.url("xmlrpc.php");
+        let body = self
+            .client
+            .post(&url)
+            .header("Content-Type", "text/xml")
+            .body(XMLRPC_LIST_METHODS)
+            .send()
+            .await
+            .ok()
+            .filter(|r| r.status().is_success())?
+            .text()
+            .await
+            .ok()?;
+
+        if !is_xmlrpc_response(&body) {
+            return None;
+        }
+
+        let pingback = body.contains("pingback.ping");
+        Some(Finding {
+            id: "xmlrpc_enabled".to_string(),
+            title: "XML-RPC is enabled".to_string(),
+            severity: "medium".to_string(),
+            description: format!(
+                "xmlrpc.php answers anonymous requests, allowing password brute-forcing through system.multicall{}.",
+                if pingback {
+                    " and pingback-based DDoS amplification"
+                } else {
+                    ""
+                }
+            ),
+            url: Some(url),
+        })
+    }
+
+    async fn check_uploads_listing(&self) -> Option<Finding> {
+        let url = format!("{}/uploads/", self.content_url);
+        let body = self.get_ok(&url).await?;
+
+        is_directory_listing(&body).then(|| Finding {
+            id: "uploads_listing".to_string(),
+            title: "Directory listing enabled on uploads".to_string(),
+            severity: "medium".to_string(),
+            description: "The uploads directory can be browsed, exposing every uploaded file including private documents.".to_string(),
+            url: Some(url),
+        })
+    }
+
+    async fn check_debug_log(&self) -> Option<Finding> {
+        let url = format!("{}/debug.log", self.content_url);
+        let body = self.get_ok(&url).await?;
+
+        looks_like_debug_log(&body).then(|| Finding {
+            id: "debug_log_exposed".to_string(),
+            title: "debug.log is publicly readable".to_string(),
+            severity: "high".to_string(),
+            description: "The PHP debug log is downloadable and typically leaks file paths, queries and plugin internals. Disable WP_DEBUG_LOG or move the log outside the web root.".to_string(),
+            url: Some(url),
+        })
+    }
+
+    async fn check_config_backups(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for file in CONFIG_BACKUPS {
+            let url = self.url(file);
+            let Some(body) = self.get_ok(&url).await else {
+                continue;
+            };
+
+            if looks_like_wp_config(&body) {
+                findings.push(Finding {
+                    id: "config_backup_exposed".to_string(),
+                    title: format!("wp-config.php backup {} is downloadable", file),
+                    severity: "critical".to_string(),
+                    description: "A copy of wp-config.php is served as plain text, exposing database credentials and secret keys. Delete it and rotate the credentials.".to_string(),
+                    url: Some(url),
+                });
+            }
+        }
+
+        findings
+    }
+
+    async fn check_author_enumeration(&self) -> (Option<Finding>, Vec<User>) {
+        let mut users = Vec::new();
+
+        for id in 1..=AUTHOR_PROBES {
+            let url = format!("{}/?author={}", self.site_url, id);
+            let Ok(response) = self.client.get(&url).send().await else {
+                continue;
+            };
+
+            let location = response
+                .headers()
+                .get("location")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            if let Some(login) = location.as_deref().and_then(author_slug_from_location) {
+                users.push(User {
+                    id,
+                    login,
+                    display_name: None,
+                });
+            }
+        }
+
+        if users.is_empty() {
+            return (None, users);
+        }
+
+        let finding = Finding {
+            id: "author_user_enumeration".to_string(),
+            title: "Usernames leaked through author archives".to_string(),
+            severity: "low".to_string(),
+            description: format!(
+                "?author=N redirects reveal account names ({}), which helps brute-force attacks.",
+                users
+                    .iter()
+                    .map(|u| u.login.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            url: Some(format!("{}/?author=1", self.site_url)),
+        };
+        (Some(finding), users)
+    }
+
+    async fn check_login_page(&self) -> Option<Finding> {
+        let url = self.url("wp-login.php");
+        let body = self.get_ok(&url).await?;
+
+        is_login_page(&body).then(|| Finding {
+            id: "login_page_exposed".to_string(),
+            title: "Login page is publicly reachable".to_string(),
+            severity: "low".to_string(),
+            description: "wp-login.php is reachable from anywhere. Consider restricting it by IP, adding two-factor authentication or rate limiting logins.".to_string(),
+            url: Some(url),
+        })
+    }
+
+    /// Body of a successful GET, `None` for errors, redirects and non-2xx answers
+    async fn get_ok(&self, url: &str) -> Option<String> {
+        let response = self.client.get(url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().await.ok()
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.site_url, path)
+    }
+}
+
+fn is_xmlrpc_response(body: &str) -> bool {
+    body.contains("<methodResponse>")
+}
+
+fn is_directory_listing(body: &str) -> bool {
+    body.contains("<title>Index of /") || body.contains("<h1>Index of /")
+}
+
+fn looks_like_debug_log(body: &str) -> bool {
+    [
+        "PHP Notice:",
+        "PHP Warning:",
+        "PHP Fatal error:",
+        "PHP Deprecated:",
+        "PHP Parse error:",
+    ]
+    .iter()
+    .any(|marker| body.contains(marker))
+}
+
+fn looks_like_wp_config(body: &str) -> bool {
+    body.contains("DB_PASSWORD") || body.contains("DB_NAME")
+}
+
+fn is_login_page(body: &str) -> bool {
+    body.contains("id=\"loginform\"") || body.contains("name=\"log\"")
+}
+
+/// "https://example.com/author/jane/" -> "jane"
+fn author_slug_from_location(location: &str) -> Option<String> {
+    let rest = &location[location.find("/author/")? + "/author/".len()..];
+    let slug = rest.split(['/', '?', '#']).next()?;
+    (!slug.is_empty()).then(|| slug.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_exposed_resources() {
+        assert!(is_directory_listing(
+            "<html><head><title>Index of /wp-content/uploads</title>"
+        ));
+        assert!(!is_directory_listing("<title>Uploads | My Blog</title>"));
+
+        assert!(looks_like_debug_log(
+            "[01-Jan-2026 10:00:00 UTC] PHP Warning:  Undefined variable $x in /var/www/..."
+        ));
+        assert!(!looks_like_debug_log("<html>Page not found</html>"));
+
+        assert!(looks_like_wp_config(
+            "<?php define( 'DB_PASSWORD', 'secret' );"
+        ));
+        assert!(is_xmlrpc_response(
+            "<?xml version=\"1.0\"?><methodResponse><params>"
+        ));
+        assert!(is_login_page("<form name=\"loginform\" id=\"loginform\""));
+    }
+
+    #[test]
+    fn extracts_author_from_redirect() {
+        assert_eq!(
+            author_slug_from_location("https://example.com/author/jane-doe/").as_deref(),
+            Some("jane-doe")
+        );
+        assert_eq!(
+            author_slug_from_location("/blog/author/admin").as_deref(),
+            Some("admin")
+        );
+        assert_eq!(author_slug_from_location("https://example.com/"), None);
+    }
+}
@@ -1,28 +1,111 @@
 // services/wpscan_service.rs
-use crate::models::wpscan::{Plugin, Theme, User, Vulnerability, WpscanResult};
-use reqwest::Client;
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::models::wpscan::{
+    DEFAULT_WPSCAN_API_URL, MAX_PROBE_CONCURRENCY, MAX_PROBES_PER_SECOND, Plugin, Theme, User,
+    Vulnerability, WpscanOptions, WpscanResult,
+};
+use crate::services::page_analysis::{FetchedPage, analyze_page};
+use crate::services::wordpress_fingerprint::{PageFingerprint, plugins_from_namespaces};
+use crate::services::wordpress_hardening::HardeningChecker;
+use once_cell::sync::Lazy;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tokio::time::MissedTickBehavior;
+use url::Url;
+
+// Vulnerability data changes slowly; one lookup per slug per day keeps us within quota
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+// Once the quota hits zero we stop calling the API for this long before probing again
+const QUOTA_RECHECK: Duration = Duration::from_secs(60 * 60);
+const RATE_LIMIT_HEADERS: &[&str] = &["x-ratelimit-remaining", "ratelimit-remaining"];
+const DEFAULT_PLUGINS_DIR: &str = "wp-content/plugins";
+const DEFAULT_THEMES_DIR: &str = "wp-content/themes";
+const PLUGIN_WORDLIST: &str = include_str!("../../resources/wordlists/popular-plugins.txt");
+
+// Shared by every WpscanService instance since the quota is per API key, not per scan.
+// Holds the remaining request count and when it was reported.
+static REQUESTS_REMAINING: Lazy<Mutex<Option<(u32, Instant)>>> = Lazy::new(|| Mutex::new(None));
 
 pub struct WpscanService {
     client: Client,
     api_key: String,
+    api_base_url: String,
+    cache: WpscanCache,
+    offline_db: Option<OfflineDatabase>,
+    aggressive: bool,
+    probe_concurrency: usize,
+    probes_per_second: u32,
 }
 
 impl WpscanService {
     pub fn new(api_key: String) -> Self {
-        println!(
+        eprintln!(
             "[v0] Creating WpscanService with API key: {}...",
             &api_key[..std::cmp::min(8, api_key.len())]
         );
         Self {
             client: Client::new(),
             api_key,
+            api_base_url: DEFAULT_WPSCAN_API_URL.to_string(),
+            cache: WpscanCache::for_api(DEFAULT_WPSCAN_API_URL),
+            offline_db: None,
+            aggressive: false,
+            probe_concurrency: 1,
+            probes_per_second: 1,
         }
     }
 
+    /// Create a service for a custom API endpoint or backed by an offline database.
+    /// An API key is only required when vulnerabilities are looked up online.
+    pub fn with_options(api_key: Option<String>, options: &WpscanOptions) -> Result<Self, String> {
+        if !(1..=MAX_PROBE_CONCURRENCY).contains(&options.probe_concurrency) {
+            return Err(format!(
+                "Probe concurrency must be between 1 and {}",
+                MAX_PROBE_CONCURRENCY
+            ));
+        }
+        if !(1..=MAX_PROBES_PER_SECOND).contains(&options.probes_per_second) {
+            return Err(format!(
+                "Probes per second must be between 1 and {}",
+                MAX_PROBES_PER_SECOND
+            ));
+        }
+
+        let offline_db = options
+            .offline_db_path
+            .as_deref()
+            .map(OfflineDatabase::load)
+            .transpose()?;
+
+        let api_key = api_key.filter(|key| !key.trim().is_empty());
+        if api_key.is_none() && offline_db.is_none() {
+            return Err(
+                "A WPScan API key is required unless an offline database is configured".to_string(),
+            );
+        }
+
+        let mut service = Self::new(api_key.unwrap_or_default());
+        service.api_base_url = options.api_base_url.trim_end_matches('/').to_string();
+        service.cache = WpscanCache::for_api(&service.api_base_url);
+        service.offline_db = offline_db;
+        service.aggressive = options.aggressive;
+        service.probe_concurrency = options.probe_concurrency;
+        service.probes_per_second = options.probes_per_second;
+        Ok(service)
+    }
+
     pub async fn scan_website(&self, url: &str) -> Result<WpscanResult, String> {
-        println!("[v0] Starting WordPress scan for: {}", url);
+        eprintln!("[v0] Starting WordPress scan for: {}", url);
 
         let mut result = WpscanResult {
             url: url.to_string(), // FIX: Changed from Option<String> to String
@@ -33,62 +116,114 @@ impl WpscanService {
             users: Vec::new(),
             scan_date: chrono::Utc::now().to_rfc3339(),
             is_wordpress: false,
+            api_requests_remaining: None,
+            findings: Vec::new(),
+            aggressive: self.aggressive,
         };
 
         // Step 1: Detect if it's WordPress
-        println!("[v0] Step 1: Detecting if site is WordPress...");
-        result.is_wordpress = self.detect_wordpress(url).await.unwrap_or(false);
+        eprintln!("[v0] Step 1: Detecting if site is WordPress...");
+        // An unreachable homepage says nothing about the site, so the scan fails
+        let homepage = FetchedPage::fetch(&self.client, url).await?;
+        let analysis = analyze_page(&homepage, None);
+        result.is_wordpress = analysis.is_wordpress;
+        let fingerprint = analysis.fingerprint;
 
         if !result.is_wordpress {
-            println!("[v0] Site is not WordPress, skipping scan");
+            eprintln!("[v0] Site is not WordPress, skipping scan");
             return Ok(result);
         }
 
-        println!("[v0] Confirmed WordPress site");
+        eprintln!("[v0] Confirmed WordPress site");
 
         // Step 2: Detect WordPress version
-        println!("[v0] Step 2: Detecting WordPress version...");
-        result.wordpress_version = self.detect_wordpress_version(url).await.ok();
-        println!("[v0] WordPress version: {:?}", result.wordpress_version);
+        eprintln!("[v0] Step 2: Detecting WordPress version...");
+        result.wordpress_version = self.detect_wordpress_version(url, &fingerprint).await.ok();
+        eprintln!("[v0] WordPress version: {:?}", result.wordpress_version);
 
         // Step 3: Enumerate plugins
-        println!("[v0] Step 3: Enumerating plugins...");
-        result.plugins = self.enumerate_plugins(url).await.unwrap_or_default();
-        println!("[v0] Found {} plugins", result.plugins.len());
+        eprintln!("[v0] Step 3: Enumerating plugins...");
+        result.plugins = self
+            .enumerate_plugins(url, &fingerprint, result.wordpress_version.as_deref())
+            .await;
+        if self.aggressive {
+            eprintln!("[v0] Step 3b: Probing wordlist plugins (aggressive mode)...");
+            let plugins_dir = fingerprint
+                .plugins_dir
+                .as_deref()
+                .unwrap_or(DEFAULT_PLUGINS_DIR);
+            let probed = self
+                .enumerate_plugins_aggressively(url, plugins_dir, &result.plugins)
+                .await;
+            result.plugins.extend(probed);
+        }
+        eprintln!("[v0] Found {} plugins", result.plugins.len());
 
         // Step 4: Enumerate themes
-        println!("[v0] Step 4: Enumerating themes...");
-        result.themes = self.enumerate_themes(url).await.unwrap_or_default();
-        println!("[v0] Found {} themes", result.themes.len());
+        eprintln!("[v0] Step 4: Enumerating themes...");
+        result.themes = self
+            .enumerate_themes(url, &fingerprint, result.wordpress_version.as_deref())
+            .await;
+        eprintln!("[v0] Found {} themes", result.themes.len());
 
         // Step 5: Enumerate users
-        println!("[v0] Step 5: Enumerating users...");
+        eprintln!("[v0] Step 5: Enumerating users...");
         result.users = self.enumerate_users(url).await.unwrap_or_default();
-        println!("[v0] Found {} users", result.users.len());
+        eprintln!("[v0] Found {} users", result.users.len());
+
+        // Step 5b: Hardening checks
+        eprintln!("[v0] Step 5b: Running hardening checks...");
+        let content_dir = fingerprint
+            .plugins_dir
+            .as_deref()
+            .and_then(|dir| dir.strip_suffix("/plugins"))
+            .or_else(|| {
+                fingerprint
+                    .themes_dir
+                    .as_deref()
+                    .and_then(|dir| dir.strip_suffix("/themes"))
+            })
+            .unwrap_or("wp-content");
+        match HardeningChecker::new(url, &content_url(url, content_dir, "")) {
+            Ok(checker) => {
+                let report = checker.run(&result.users).await;
+                for user in report.users {
+                    if !result.users.iter().any(|u| u.login == user.login) {
+                        result.users.push(user);
+                    }
+                }
+                result.findings = report.findings;
+            }
+            Err(e) => eprintln!("[v0] Skipping hardening checks: {}", e),
+        }
+        eprintln!("[v0] Found {} hardening issues", result.findings.len());
+
+        // Steps 6-8: a failed lookup (quota, rate limit, API error) fails the whole scan,
+        // since missing vulnerabilities would otherwise look fixed
 
         // Step 6: Check vulnerabilities for WordPress core
         if let Some(ref version) = result.wordpress_version {
-            println!(
+            eprintln!(
                 "[v0] Step 6: Checking WordPress core vulnerabilities for version {}...",
                 version
             );
             let vulns = self
                 .check_wordpress_vulnerabilities(version)
                 .await
-                .unwrap_or_default();
-            println!("[v0] Found {} core vulnerabilities", vulns.len());
+                .map_err(|e| format!("Failed to look up WordPress {}: {}", version, e))?;
+            eprintln!("[v0] Found {} core vulnerabilities", vulns.len());
             result.vulnerabilities.extend(vulns);
         }
 
         // Step 7: Check vulnerabilities for each plugin
-        println!("[v0] Step 7: Checking plugin vulnerabilities...");
+        eprintln!("[v0] Step 7: Checking plugin vulnerabilities...");
         for plugin in &mut result.plugins {
-            println!("[v0] Checking vulnerabilities for plugin: {}", plugin.slug);
+            eprintln!("[v0] Checking vulnerabilities for plugin: {}", plugin.slug);
             plugin.vulnerabilities = self
-                .check_plugin_vulnerabilities(&plugin.slug)
+                .check_plugin_vulnerabilities(&plugin.slug, plugin.version.as_deref())
                 .await
-                .unwrap_or_default();
-            println!(
+                .map_err(|e| format!("Failed to look up plugin {}: {}", plugin.slug, e))?;
+            eprintln!(
                 "[v0] Found {} vulnerabilities for {}",
                 plugin.vulnerabilities.len(),
                 plugin.slug
@@ -96,59 +231,34 @@ impl WpscanService {
         }
 
         // Step 8: Check vulnerabilities for each theme
-        println!("[v0] Step 8: Checking theme vulnerabilities...");
+        eprintln!("[v0] Step 8: Checking theme vulnerabilities...");
         for theme in &mut result.themes {
-            println!("[v0] Checking vulnerabilities for theme: {}", theme.slug);
+            eprintln!("[v0] Checking vulnerabilities for theme: {}", theme.slug);
             theme.vulnerabilities = self
-                .check_theme_vulnerabilities(&theme.slug)
+                .check_theme_vulnerabilities(&theme.slug, theme.version.as_deref())
                 .await
-                .unwrap_or_default();
-            println!(
+                .map_err(|e| format!("Failed to look up theme {}: {}", theme.slug, e))?;
+            eprintln!(
                 "[v0] Found {} vulnerabilities for {}",
                 theme.vulnerabilities.len(),
                 theme.slug
             );
         }
 
-        println!("[v0] Scan complete!");
-        Ok(result)
-    }
+        result.api_requests_remaining = Self::requests_remaining();
 
-    // ... rest of your existing methods remain the same ...
-    async fn detect_wordpress(&self, url: &str) -> Result<bool, String> {
-        let response = self
-            .client
-            .get(url)
-            .timeout(std::time::Duration::from_secs(10))
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        let html = response.text().await.map_err(|e| e.to_string())?;
-
-        Ok(html.contains("wp-content")
-            || html.contains("wp-includes")
-            || html.contains("WordPress")
-            || html.contains("wp-json"))
+        eprintln!("[v0] Scan complete!");
+        Ok(result)
     }
 
-    async fn detect_wordpress_version(&self, url: &str) -> Result<String, String> {
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let html = response.text().await.map_err(|e| e.to_string())?;
-
-        // Look for generator meta tag
-        if let Some(start) = html.find(r#"<meta name="generator" content="WordPress "#) {
-            if let Some(version_start) = html[start..].find("WordPress ") {
-                let version_text = &html[start + version_start + 10..];
-                if let Some(end) = version_text.find('"') {
-                    return Ok(version_text[..end].to_string());
-                }
-            }
+    async fn detect_wordpress_version(
+        &self,
+        url: &str,
+        fingerprint: &PageFingerprint,
+    ) -> Result<String, String> {
+        // Generator meta tag
+        if let Some(version) = &fingerprint.generator_version {
+            return Ok(version.clone());
         }
 
         // Try readme.html
@@ -164,107 +274,208 @@ impl WpscanService {
             }
         }
 
-        Err("Could not detect WordPress version".to_string())
+        // ?ver= on core scripts and styles
+        fingerprint
+            .core_version_hint
+            .clone()
+            .ok_or_else(|| "Could not detect WordPress version".to_string())
     }
 
-    async fn enumerate_plugins(&self, url: &str) -> Result<Vec<Plugin>, String> {
+    /// Plugins referenced by assets, generator tags, comments and REST API namespaces.
+    /// Versions come from readme.txt, falling back to `?ver=` hints.
+    async fn enumerate_plugins(
+        &self,
+        url: &str,
+        fingerprint: &PageFingerprint,
+        core_version: Option<&str>,
+    ) -> Vec<Plugin> {
+        let plugins_dir = fingerprint
+            .plugins_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_PLUGINS_DIR);
+
+        let mut found: BTreeMap<String, Option<String>> = fingerprint
+            .plugins
+            .iter()
+            .map(|(slug, hints)| (slug.clone(), fingerprint.version_hint(hints, core_version)))
+            .collect();
+        for (slug, version) in &fingerprint.signature_plugins {
+            let hint = found.entry(slug.clone()).or_default();
+            if hint.is_none() {
+                *hint = version.clone();
+            }
+        }
+
+        let namespaces = self.rest_namespaces(url, fingerprint).await;
+        let (known, unknown) = plugins_from_namespaces(&namespaces);
+        for slug in known {
+            found.entry(slug).or_default();
+        }
+
         let mut plugins = Vec::new();
-        let base_url = url.trim_end_matches('/');
+        for (slug, hint) in found {
+            let version = self
+                .get_plugin_version(url, plugins_dir, &slug)
+                .await
+                .ok()
+                .or(hint);
+            plugins.push(Plugin {
+                name: slug.replace('-', " ").to_string(),
+                version,
+                slug,
+                vulnerabilities: Vec::new(),
+            });
+        }
 
-        let response = self
-            .client
-            .get(base_url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let html = response.text().await.map_err(|e| e.to_string())?;
-
-        // Find plugin references in HTML
-        for line in html.lines() {
-            if line.contains("/wp-content/plugins/") {
-                if let Some(start) = line.find("/wp-content/plugins/") {
-                    let plugin_path = &line[start + 20..];
-                    if let Some(end) = plugin_path.find('/') {
-                        let slug = plugin_path[..end].to_string();
-
-                        if !plugins.iter().any(|p: &Plugin| p.slug == slug) {
-                            let version = self.get_plugin_version(base_url, &slug).await.ok();
-
-                            plugins.push(Plugin {
-                                name: slug.replace('-', " ").to_string(),
-                                version,
-                                slug,
-                                vulnerabilities: Vec::new(),
-                            });
-                        }
-                    }
-                }
+        // Unrecognised namespaces only count once a readme.txt confirms the plugin
+        for slug in unknown {
+            if plugins.iter().any(|p| p.slug == slug) {
+                continue;
+            }
+            if let Ok(version) = self.get_plugin_version(url, plugins_dir, &slug).await {
+                plugins.push(Plugin {
+                    name: slug.replace('-', " ").to_string(),
+                    version: Some(version),
+                    slug,
+                    vulnerabilities: Vec::new(),
+                });
             }
         }
 
-        Ok(plugins)
+        plugins
     }
 
-    async fn get_plugin_version(&self, base_url: &str, slug: &str) -> Result<String, String> {
-        let readme_url = format!("{}/wp-content/plugins/{}/readme.txt", base_url, slug);
+    /// Namespaces advertised by the REST API index, empty when the API is disabled
+    async fn rest_namespaces(&self, url: &str, fingerprint: &PageFingerprint) -> Vec<String> {
+        let api_url = fingerprint
+            .rest_api_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/wp-json/", url.trim_end_matches('/')));
 
-        if let Ok(response) = self.client.get(&readme_url).send().await {
-            if let Ok(text) = response.text().await {
-                for line in text.lines() {
-                    if line.to_lowercase().starts_with("stable tag:") {
-                        return Ok(line.split(':').nth(1).unwrap_or("").trim().to_string());
-                    }
-                }
+        let Ok(response) = self.client.get(&api_url).send().await else {
+            return Vec::new();
+        };
+        let Ok(index) = response.json::<serde_json::Value>().await else {
+            return Vec::new();
+        };
+
+        index["namespaces"]
+            .as_array()
+            .map(|namespaces| {
+                namespaces
+                    .iter()
+                    .filter_map(|n| n.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Probe readme.txt for every wordlist plugin the passive checks did not find.
+    /// Requests are spread out to `probes_per_second` with at most `probe_concurrency` in flight.
+    async fn enumerate_plugins_aggressively(
+        &self,
+        url: &str,
+        plugins_dir: &str,
+        found: &[Plugin],
+    ) -> Vec<Plugin> {
+        let mut pending = plugin_wordlist().filter(|slug| !found.iter().any(|p| p.slug == *slug));
+        let mut interval =
+            tokio::time::interval(Duration::from_secs_f64(1.0 / self.probes_per_second as f64));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut running = JoinSet::new();
+        let mut plugins = Vec::new();
+
+        loop {
+            while running.len() < self.probe_concurrency {
+                let Some(slug) = pending.next() else {
+                    break;
+                };
+
+                interval.tick().await;
+                let client = self.client.clone();
+                let readme_url = content_url(url, plugins_dir, &format!("{}/readme.txt", slug));
+                running.spawn(async move { (slug, probe_readme(&client, &readme_url).await) });
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            if let Ok((slug, Some(version))) = joined {
+                eprintln!("[v0] Found plugin by probing: {}", slug);
+                plugins.push(Plugin {
+                    name: slug.replace('-', " "),
+                    version,
+                    slug: slug.to_string(),
+                    vulnerabilities: Vec::new(),
+                });
             }
         }
 
-        Err("Version not found".to_string())
+        plugins.sort_by(|a, b| a.slug.cmp(&b.slug));
+        plugins
     }
 
-    async fn enumerate_themes(&self, url: &str) -> Result<Vec<Theme>, String> {
-        let mut themes = Vec::new();
-        let base_url = url.trim_end_matches('/');
+    async fn get_plugin_version(
+        &self,
+        url: &str,
+        plugins_dir: &str,
+        slug: &str,
+    ) -> Result<String, String> {
+        let readme_url = content_url(url, plugins_dir, &format!("{}/readme.txt", slug));
 
         let response = self
             .client
-            .get(base_url)
+            .get(&readme_url)
             .send()
             .await
             .map_err(|e| e.to_string())?;
-        let html = response.text().await.map_err(|e| e.to_string())?;
-
-        for line in html.lines() {
-            if line.contains("/wp-content/themes/") {
-                if let Some(start) = line.find("/wp-content/themes/") {
-                    let theme_path = &line[start + 19..];
-                    if let Some(end) = theme_path.find('/') {
-                        let slug = theme_path[..end].to_string();
-
-                        if !themes.iter().any(|t: &Theme| t.slug == slug) {
-                            let version = self.get_theme_version(base_url, &slug).await.ok();
-
-                            themes.push(Theme {
-                                name: slug.replace('-', " ").to_string(),
-                                version,
-                                slug,
-                                vulnerabilities: Vec::new(),
-                            });
-                        }
-                    }
-                }
-            }
+        let text = response.text().await.map_err(|e| e.to_string())?;
+
+        parse_stable_tag(&text).ok_or_else(|| "Version not found".to_string())
+    }
+
+    async fn enumerate_themes(
+        &self,
+        url: &str,
+        fingerprint: &PageFingerprint,
+        core_version: Option<&str>,
+    ) -> Vec<Theme> {
+        let themes_dir = fingerprint
+            .themes_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_THEMES_DIR);
+
+        let mut themes = Vec::new();
+        for (slug, hints) in &fingerprint.themes {
+            let version = self
+                .get_theme_version(url, themes_dir, slug)
+                .await
+                .ok()
+                .or_else(|| fingerprint.version_hint(hints, core_version));
+
+            themes.push(Theme {
+                name: slug.replace('-', " ").to_string(),
+                version,
+                slug: slug.clone(),
+                vulnerabilities: Vec::new(),
+            });
         }
 
-        Ok(themes)
+        themes
     }
 
-    async fn get_theme_version(&self, base_url: &str, slug: &str) -> Result<String, String> {
-        let style_url = format!("{}/wp-content/themes/{}/style.css", base_url, slug);
+    async fn get_theme_version(
+        &self,
+        url: &str,
+        themes_dir: &str,
+        slug: &str,
+    ) -> Result<String, String> {
+        let style_url = content_url(url, themes_dir, &format!("{}/style.css", slug));
 
         if let Ok(response) = self.client.get(&style_url).send().await {
             if let Ok(text) = response.text().await {
                 for line in text.lines() {
-                    if line.to_lowercase().starts_with("version:") {
+                    if line.trim().to_lowercase().starts_with("version:") {
                         return Ok(line.split(':').nth(1).unwrap_or("").trim().to_string());
                     }
                 }
@@ -301,94 +512,166 @@ impl WpscanService {
         &self,
         version: &str,
     ) -> Result<Vec<Vulnerability>, String> {
-        let url = format!("https://wpscan.com/api/v3/wordpresses/{}", version);
-        println!("[v0] Calling WPScan API: {}", url);
-
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Token {}", self.api_key))
-            .send()
+        self.fetch_vulnerabilities("wordpresses", version, Some(version))
             .await
-            .map_err(|e| format!("API request failed: {}", e))?;
-
-        if !response.status().is_success() {
-            println!("[v0] API returned status: {}", response.status());
-            return Ok(Vec::new());
-        }
+    }
 
-        let api_response: WpscanApiResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+    async fn check_plugin_vulnerabilities(
+        &self,
+        slug: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<Vulnerability>, String> {
+        self.fetch_vulnerabilities("plugins", slug, version).await
+    }
 
-        Ok(self.parse_vulnerabilities(api_response))
+    async fn check_theme_vulnerabilities(
+        &self,
+        slug: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<Vulnerability>, String> {
+        self.fetch_vulnerabilities("themes", slug, version).await
     }
 
-    async fn check_plugin_vulnerabilities(&self, slug: &str) -> Result<Vec<Vulnerability>, String> {
-        let url = format!("https://wpscan.com/api/v3/plugins/{}", slug);
-        println!("[v0] Calling WPScan API: {}", url);
+    /// Vulnerabilities affecting the installed version; all known ones when it is unknown
+    async fn fetch_vulnerabilities(
+        &self,
+        kind: &str,
+        slug: &str,
+        installed_version: Option<&str>,
+    ) -> Result<Vec<Vulnerability>, String> {
+        let body = self.fetch_body(kind, slug).await?;
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Token {}", self.api_key))
-            .send()
-            .await
-            .map_err(|e| format!("API request failed: {}", e))?;
+        let api_response: WpscanApiResponse =
+            serde_json::from_str(&body).map_err(|e| format!("Failed to parse response: {}", e))?;
 
-        if !response.status().is_success() {
-            println!("[v0] API returned status: {}", response.status());
-            return Ok(Vec::new());
+        Ok(self.parse_vulnerabilities(api_response, installed_version))
+    }
+
+    /// Raw API response for a core version / plugin / theme, taken from the offline
+    /// database when configured, otherwise from the on-disk cache or the API
+    async fn fetch_body(&self, kind: &str, slug: &str) -> Result<String, String> {
+        if let Some(db) = &self.offline_db {
+            return Ok(db.lookup(kind, slug));
         }
 
-        let api_response: WpscanApiResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        if let Some(body) = self.cache.get(kind, slug) {
+            eprintln!("[v0] WPScan cache hit: {}/{}", kind, slug);
+            return Ok(body);
+        }
 
-        Ok(self.parse_vulnerabilities(api_response))
+        let body = self.request_api(kind, slug).await?;
+        self.cache.put(kind, slug, &body);
+        Ok(body)
     }
 
-    async fn check_theme_vulnerabilities(&self, slug: &str) -> Result<Vec<Vulnerability>, String> {
-        let url = format!("https://wpscan.com/api/v3/themes/{}", slug);
-        println!("[v0] Calling WPScan API: {}", url);
+    /// Call the WPScan API, backing off on HTTP 429 and tracking the remaining quota.
+    /// Unknown slugs (404) yield an empty object so they get cached too.
+    async fn request_api(&self, kind: &str, slug: &str) -> Result<String, String> {
+        let exhausted = REQUESTS_REMAINING
+            .lock()
+            .unwrap()
+            .is_some_and(|(remaining, at)| remaining == 0 && at.elapsed() < QUOTA_RECHECK);
+        if exhausted {
+            return Err("WPScan API quota exhausted, serving cached results only".to_string());
+        }
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Token {}", self.api_key))
-            .send()
-            .await
-            .map_err(|e| format!("API request failed: {}", e))?;
+        // Core versions are addressed without dots, e.g. /wordpresses/641
+        let path = if kind == "wordpresses" {
+            slug.replace('.', "")
+        } else {
+            slug.to_string()
+        };
+        let url = format!("{}/{}/{}", self.api_base_url, kind, path);
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 0..=MAX_RETRIES {
+            eprintln!("[v0] Calling WPScan API: {}", url);
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Token {}", self.api_key))
+                .send()
+                .await
+                .map_err(|e| format!("API request failed: {}", e))?;
+
+            if let Some(remaining) = header_number(response.headers(), RATE_LIMIT_HEADERS) {
+                *REQUESTS_REMAINING.lock().unwrap() = Some((remaining, Instant::now()));
+            }
 
-        if !response.status().is_success() {
-            println!("[v0] API returned status: {}", response.status());
-            return Ok(Vec::new());
+            match response.status() {
+                StatusCode::TOO_MANY_REQUESTS if attempt < MAX_RETRIES => {
+                    let wait = header_number(response.headers(), &["retry-after"])
+                        .map(|secs| Duration::from_secs(secs as u64))
+                        .unwrap_or(backoff)
+                        .min(MAX_BACKOFF);
+                    eprintln!("[v0] WPScan rate limited, retrying in {:?}", wait);
+                    tokio::time::sleep(wait).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                StatusCode::TOO_MANY_REQUESTS => {
+                    *REQUESTS_REMAINING.lock().unwrap() = Some((0, Instant::now()));
+                    return Err("WPScan API rate limit exceeded".to_string());
+                }
+                StatusCode::NOT_FOUND => return Ok("{}".to_string()),
+                status if !status.is_success() => {
+                    eprintln!("[v0] API returned status: {}", status);
+                    return Err(format!("WPScan API returned status {}", status));
+                }
+                _ => {
+                    return response
+                        .text()
+                        .await
+                        .map_err(|e| format!("Failed to read response: {}", e));
+                }
+            }
         }
 
-        let api_response: WpscanApiResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        Err("WPScan API rate limit exceeded".to_string())
+    }
 
-        Ok(self.parse_vulnerabilities(api_response))
+    /// Remaining daily API requests as last reported by WPScan
+    pub fn requests_remaining() -> Option<u32> {
+        REQUESTS_REMAINING
+            .lock()
+            .unwrap()
+            .map(|(remaining, _)| remaining)
     }
 
-    fn parse_vulnerabilities(&self, response: WpscanApiResponse) -> Vec<Vulnerability> {
+    fn parse_vulnerabilities(
+        &self,
+        response: WpscanApiResponse,
+        installed_version: Option<&str>,
+    ) -> Vec<Vulnerability> {
         let mut vulnerabilities = Vec::new();
 
-        for (_, vulns) in response.vulnerabilities {
-            for vuln in vulns {
+        for (_, component) in response.components {
+            for vuln in component.vulnerabilities {
+                if !vuln.affects(installed_version) {
+                    continue;
+                }
+
+                let cve = vuln
+                    .references
+                    .cve
+                    .as_ref()
+                    .filter(|ids| !ids.is_empty())
+                    .map(|ids| {
+                        ids.iter()
+                            .map(|id| format!("CVE-{}", id.trim_start_matches("CVE-")))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    });
+
                 vulnerabilities.push(Vulnerability {
                     id: vuln.id.unwrap_or_else(|| "unknown".to_string()),
                     title: vuln.title,
                     description: None,
                     vuln_type: vuln.vuln_type,
-                    severity: None,
+                    severity: vuln.cvss.and_then(|cvss| cvss.severity()),
                     fixed_in: vuln.fixed_in,
                     references: vuln.references.url.unwrap_or_default(),
-                    cve: None,
+                    cve,
                 });
             }
         }
@@ -397,10 +680,54 @@ impl WpscanService {
     }
 }
 
+/// Compare two WordPress-style versions ("6.4", "6.4.2", "5.0-beta1").
+/// Missing components count as zero and pre-releases sort before the release.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn parse(version: &str) -> (Vec<u64>, Option<&str>) {
+        let version = version.trim().trim_start_matches(['v', 'V']);
+        let (release, pre_release) = match version.find(['-', '+', ' ']) {
+            Some(index) => (&version[..index], Some(&version[index + 1..])),
+            None => (version, None),
+        };
+        let numbers = release
+            .split('.')
+            .map(|part| {
+                let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().unwrap_or(0)
+            })
+            .collect();
+        (numbers, pre_release.filter(|p| !p.is_empty()))
+    }
+
+    let (a_numbers, a_pre) = parse(a);
+    let (b_numbers, b_pre) = parse(b);
+
+    for i in 0..a_numbers.len().max(b_numbers.len()) {
+        let x = a_numbers.get(i).copied().unwrap_or(0);
+        let y = b_numbers.get(i).copied().unwrap_or(0);
+        if x != y {
+            return x.cmp(&y);
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => x.cmp(y),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct WpscanApiResponse {
     #[serde(flatten)]
-    vulnerabilities: HashMap<String, Vec<WpscanVulnerability>>,
+    components: HashMap<String, WpscanComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WpscanComponent {
+    #[serde(default)]
+    vulnerabilities: Vec<WpscanVulnerability>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -409,10 +736,483 @@ struct WpscanVulnerability {
     title: String,
     vuln_type: Option<String>,
     fixed_in: Option<String>,
+    introduced_in: Option<String>,
+    #[serde(default)]
     references: WpscanReferences,
+    cvss: Option<WpscanCvss>,
 }
 
-#[derive(Debug, Deserialize)]
+impl WpscanVulnerability {
+    /// Whether the installed version falls between `introduced_in` and `fixed_in`
+    fn affects(&self, installed_version: Option<&str>) -> bool {
+        let Some(installed) = installed_version.filter(|v| is_known_version(v)) else {
+            return true;
+        };
+
+        let fixed = self
+            .fixed_in
+            .as_deref()
+            .filter(|v| is_known_version(v))
+            .is_some_and(|fixed_in| compare_versions(installed, fixed_in) != Ordering::Less);
+        let not_yet_introduced = self
+            .introduced_in
+            .as_deref()
+            .filter(|v| is_known_version(v))
+            .is_some_and(|introduced_in| {
+                compare_versions(installed, introduced_in) == Ordering::Less
+            });
+
+        !fixed && !not_yet_introduced
+    }
+}
+
+fn is_known_version(version: &str) -> bool {
+    version.trim().starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[derive(Debug, Default, Deserialize)]
 struct WpscanReferences {
     url: Option<Vec<String>>,
+    cve: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WpscanCvss {
+    score: Option<serde_json::Value>, // "9.8" or 9.8 depending on the source
+    severity: Option<String>,
+}
+
+impl WpscanCvss {
+    /// CVSS v3 qualitative rating, lowercase like the rest of the app
+    fn severity(&self) -> Option<String> {
+        if let Some(severity) = &self.severity {
+            return Some(severity.to_lowercase());
+        }
+
+        let score = match self.score.as_ref()? {
+            serde_json::Value::Number(n) => n.as_f64()?,
+            serde_json::Value::String(s) => s.trim().parse().ok()?,
+            _ => return None,
+        };
+
+        let severity = match score {
+            s if s >= 9.0 => "critical",
+            s if s >= 7.0 => "high",
+            s if s >= 4.0 => "medium",
+            s if s > 0.0 => "low",
+            _ => "none",
+        };
+        Some(severity.to_string())
+    }
+}
+
+/// A local vulnerability dump, laid out like the API responses:
+/// `{"wordpresses": {"6.4.1": {...}}, "plugins": {"<slug>": {...}}, "themes": {"<slug>": {...}}}`
+struct OfflineDatabase {
+    entries: HashMap<String, HashMap<String, serde_json::Value>>,
+}
+
+impl OfflineDatabase {
+    fn load(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read vulnerability database {}: {}", path, e))?;
+        Self::from_json(&contents)
+    }
+
+    fn from_json(contents: &str) -> Result<Self, String> {
+        let entries = serde_json::from_str(contents)
+            .map_err(|e| format!("Failed to parse vulnerability database: {}", e))?;
+        Ok(Self { entries })
+    }
+
+    /// The entry wrapped the way the API would return it, `{}` when unknown
+    fn lookup(&self, kind: &str, slug: &str) -> String {
+        match self.entries.get(kind).and_then(|entries| entries.get(slug)) {
+            Some(entry) => serde_json::json!({ slug: entry }).to_string(),
+            None => "{}".to_string(),
+        }
+    }
+}
+
+fn plugin_wordlist() -> impl Iterator<Item = &'static str> {
+    PLUGIN_WORDLIST
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn parse_stable_tag(readme: &str) -> Option<String> {
+    readme
+        .lines()
+        .find(|line| line.trim().to_lowercase().starts_with("stable tag:"))
+        .map(|line| line.split(':').nth(1).unwrap_or("").trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// `Some(version)` if a plugin readme exists at `readme_url`. Soft 404 pages are
+/// rejected by requiring the readme header or plugin metadata.
+async fn probe_readme(client: &Client, readme_url: &str) -> Option<Option<String>> {
+    let response = client
+        .get(readme_url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let text = response.text().await.ok()?;
+    let looks_like_readme = text.trim_start().starts_with("===")
+        || text.contains("Stable tag:")
+        || text.contains("Contributors:");
+
+    looks_like_readme.then(|| parse_stable_tag(&text))
+}
+
+/// URL of a file inside a content directory. Detected directories are absolute paths
+/// ("/app/plugins"), the defaults are relative to the site URL ("wp-content/plugins").
+fn content_url(site_url: &str, dir: &str, file: &str) -> String {
+    let base = format!("{}/", site_url.trim_end_matches('/'));
+    let path = format!("{}/{}", dir.trim_end_matches('/'), file);
+
+    Url::parse(&base)
+        .and_then(|base| base.join(&path))
+        .map(String::from)
+        .unwrap_or_else(|_| format!("{}{}", base, path.trim_start_matches('/')))
+}
+
+fn header_number(headers: &HeaderMap, names: &[&str]) -> Option<u32> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok()?.trim().parse().ok())
+}
+
+/// FNV-1a, stable across Rust releases unlike `DefaultHasher`
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    fetched_at: i64, // Unix timestamp
+    body: String,
+}
+
+/// Raw API responses on disk, one file per core version / plugin / theme
+struct WpscanCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl WpscanCache {
+    fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    /// The user cache directory, namespaced by API so a mirror or self-hosted API
+    /// never serves responses cached from another one
+    fn for_api(api_base_url: &str) -> Self {
+        Self::new(
+            dirs::cache_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("webheartbeat")
+                .join("wpscan")
+                .join(format!("{:016x}", fnv1a(api_base_url))),
+            CACHE_TTL,
+        )
+    }
+
+    fn path(&self, kind: &str, slug: &str) -> PathBuf {
+        let slug: String = slug
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(kind).join(format!("{}.json", slug))
+    }
+
+    fn get(&self, kind: &str, slug: &str) -> Option<String> {
+        let contents = fs::read_to_string(self.path(kind, slug)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&contents).ok()?;
+
+        let age = chrono::Utc::now().timestamp() - cached.fetched_at;
+        if age < 0 || age as u64 > self.ttl.as_secs() {
+            return None;
+        }
+
+        Some(cached.body)
+    }
+
+    fn put(&self, kind: &str, slug: &str, body: &str) {
+        let path = self.path(kind, slug);
+        let cached = CachedResponse {
+            fetched_at: chrono::Utc::now().timestamp(),
+            body: body.to_string(),
+        };
+
+        let written = path
+            .parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string(&cached).map_err(|e| e.to_string()))
+            .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+
+        if let Err(e) = written {
+            eprintln!(
+                "[v0] Failed to cache WPScan response {}/{}: {}",
+                kind, slug, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_serves_fresh_entries_and_expires_old_ones() {
+        let dir = std::env::temp_dir().join(format!(
+            "whb-wpscan-cache-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let cache = WpscanCache::new(dir.clone(), Duration::from_secs(60));
+
+        assert!(cache.get("plugins", "akismet").is_none());
+        cache.put("plugins", "akismet", "{\"akismet\":[]}");
+        assert_eq!(
+            cache.get("plugins", "akismet").as_deref(),
+            Some("{\"akismet\":[]}")
+        );
+
+        cache.put("themes", "twentytwenty", "{}");
+        let stale = CachedResponse {
+            fetched_at: chrono::Utc::now().timestamp() - 120,
+            body: "{}".to_string(),
+        };
+        fs::write(
+            cache.path("themes", "twentytwenty"),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .unwrap();
+        assert!(cache.get("themes", "twentytwenty").is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn cache_is_separate_per_api() {
+        let official = WpscanCache::for_api(DEFAULT_WPSCAN_API_URL);
+        let mirror = WpscanCache::for_api("https://wpscan.internal.example/api/v3");
+
+        assert_ne!(
+            official.path("plugins", "akismet"),
+            mirror.path("plugins", "akismet")
+        );
+        assert_eq!(
+            official.path("plugins", "akismet"),
+            WpscanCache::for_api(DEFAULT_WPSCAN_API_URL).path("plugins", "akismet")
+        );
+    }
+
+    #[tokio::test]
+    async fn offline_database_answers_without_api_key() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/wpscan_vulndb.json"
+        );
+        let options = WpscanOptions {
+            offline_db_path: Some(path.to_string()),
+            ..WpscanOptions::default()
+        };
+        let service = WpscanService::with_options(None, &options).unwrap();
+
+        let body = service
+            .fetch_body("plugins", "contact-form-7")
+            .await
+            .unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            entry["contact-form-7"]["vulnerabilities"]
+                .as_array()
+                .map(Vec::len),
+            Some(2)
+        );
+
+        assert_eq!(
+            service.fetch_body("plugins", "unknown").await.unwrap(),
+            "{}"
+        );
+        assert!(WpscanService::with_options(None, &WpscanOptions::default()).is_err());
+
+        let flood = WpscanOptions {
+            probes_per_second: 1000,
+            ..options.clone()
+        };
+        assert!(WpscanService::with_options(None, &flood).is_err());
+        let stalled = WpscanOptions {
+            probe_concurrency: 0,
+            ..options
+        };
+        assert!(WpscanService::with_options(None, &stalled).is_err());
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(compare_versions("5.10", "5.9.5"), Ordering::Greater);
+        assert_eq!(compare_versions("6.4", "6.4.0"), Ordering::Equal);
+        assert_eq!(compare_versions("5.0-beta1", "5.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.2.3", "1.2.4"), Ordering::Less);
+    }
+
+    #[tokio::test]
+    async fn only_vulnerabilities_affecting_the_installed_version_are_reported() {
+        let options = WpscanOptions {
+            offline_db_path: Some(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/wpscan_vulndb.json"
+                )
+                .to_string(),
+            ),
+            ..WpscanOptions::default()
+        };
+        let service = WpscanService::with_options(None, &options).unwrap();
+
+        let vulns = service
+            .check_plugin_vulnerabilities("contact-form-7", Some("5.4"))
+            .await
+            .unwrap();
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].fixed_in.as_deref(), Some("5.9.5"));
+        assert_eq!(vulns[0].severity.as_deref(), Some("medium"));
+        assert_eq!(vulns[0].cve.as_deref(), Some("CVE-2024-4704"));
+
+        let unknown_version = service
+            .check_plugin_vulnerabilities("contact-form-7", None)
+            .await
+            .unwrap();
+        assert_eq!(unknown_version.len(), 2);
+
+        let core = service
+            .check_wordpress_vulnerabilities("6.4.1")
+            .await
+            .unwrap();
+        assert_eq!(core[0].severity.as_deref(), Some("critical"));
+
+        let patched = service
+            .check_theme_vulnerabilities("twentyfifteen", Some("3.8"))
+            .await
+            .unwrap();
+        assert!(patched.is_empty());
+    }
+
+    /// Minimal HTTP server answering readme.txt requests for the given plugins only
+    async fn serve_readmes(plugins: &'static [&'static str]) -> String {
+        serve(move |path| {
+            let found = plugins
+                .iter()
+                .any(|slug| path == format!("/wp-content/plugins/{}/readme.txt", slug));
+            if found {
+                ("200 OK", "=== Plugin ===\nStable tag: 1.2.3\n")
+            } else {
+                ("404 Not Found", "not found")
+            }
+        })
+        .await
+    }
+
+    /// Minimal HTTP server answering every request with the status and body for its path
+    async fn serve(
+        respond: impl Fn(&str) -> (&'static str, &'static str) + Send + 'static,
+    ) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = [0u8; 2048];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+                let (status, body) = respond(&path);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn aggressive_mode_probes_wordlist_readmes() {
+        let url = serve_readmes(&["akismet", "wordfence"]).await;
+        let options = WpscanOptions {
+            offline_db_path: Some(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/wpscan_vulndb.json"
+                )
+                .to_string(),
+            ),
+            aggressive: true,
+            probe_concurrency: 8,
+            probes_per_second: MAX_PROBES_PER_SECOND,
+            ..WpscanOptions::default()
+        };
+        let service = WpscanService::with_options(None, &options).unwrap();
+
+        let already_found = vec![Plugin {
+            name: "wordfence".to_string(),
+            version: None,
+            vulnerabilities: Vec::new(),
+            slug: "wordfence".to_string(),
+        }];
+        let probed = service
+            .enumerate_plugins_aggressively(&url, DEFAULT_PLUGINS_DIR, &already_found)
+            .await;
+
+        assert_eq!(probed.len(), 1);
+        assert_eq!(probed[0].slug, "akismet");
+        assert_eq!(probed[0].version.as_deref(), Some("1.2.3"));
+    }
+
+    #[tokio::test]
+    async fn failed_vulnerability_lookups_fail_the_scan() {
+        let url = serve(|path| match path {
+            "/" => (
+                "200 OK",
+                "<html><head><meta name=\"generator\" content=\"WordPress 6.4.1\">\
+                 <link rel=\"stylesheet\" href=\"/wp-content/themes/astra/style.css\"></head></html>",
+            ),
+            _ if path.starts_with("/api/") => ("500 Internal Server Error", "unavailable"),
+            _ => ("404 Not Found", "not found"),
+        })
+        .await;
+        let options = WpscanOptions {
+            api_base_url: format!("{}/api", url),
+            ..WpscanOptions::default()
+        };
+        let service = WpscanService::with_options(Some("key".to_string()), &options).unwrap();
+
+        let error = service.scan_website(&url).await.unwrap_err();
+        assert!(error.contains("WordPress 6.4.1"), "{}", error);
+    }
 }